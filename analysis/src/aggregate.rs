@@ -0,0 +1,119 @@
+use crate::{Analysis, PlayerGlobalId, kill::KillStreak};
+use dod::Weapon;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// Results of the players of a match recorded in several demos, e.g. one for each half of a clan
+/// match, merged by [PlayerGlobalId].
+#[derive(Debug, Default)]
+pub struct MatchAggregation<'a> {
+    /// Number of analyses merged.
+    pub analyses: usize,
+
+    /// Players in the order they were first seen.
+    pub players: Vec<AggregatedPlayer<'a>>,
+}
+
+/// Results of a player summed across the analyses of a [MatchAggregation].
+#[derive(Debug)]
+pub struct AggregatedPlayer<'a> {
+    pub id: PlayerGlobalId,
+
+    /// Name in the last analysis the player was seen in.
+    pub name: String,
+
+    /// Score, kills, and deaths.
+    pub stats: (i32, i32, i32),
+
+    pub objective_score: i32,
+    pub rounds_played: usize,
+
+    /// Number of analyses the player was seen in.
+    pub analyses: usize,
+
+    pub weapon_breakdown: BTreeMap<Weapon, (u32, u32)>,
+
+    /// Kill streaks of every analysis, in the order of the analyses. Streaks never carry over from
+    /// one analysis to the next, since the demos are recorded apart.
+    pub kill_streaks: Vec<&'a KillStreak>,
+}
+
+impl<'a> MatchAggregation<'a> {
+    pub fn new(analyses: impl IntoIterator<Item = &'a Analysis>) -> Self {
+        let mut aggregation = Self::default();
+
+        for analysis in analyses {
+            aggregation.analyses += 1;
+
+            for player in &analysis.state.players {
+                let i = match aggregation.players.iter().position(|p| p.id == player.id) {
+                    Some(i) => i,
+                    None => {
+                        aggregation.players.push(AggregatedPlayer {
+                            id: player.id.clone(),
+                            name: String::new(),
+                            stats: (0, 0, 0),
+                            objective_score: 0,
+                            rounds_played: 0,
+                            analyses: 0,
+                            weapon_breakdown: BTreeMap::new(),
+                            kill_streaks: vec![],
+                        });
+
+                        aggregation.players.len() - 1
+                    }
+                };
+
+                let aggregated = &mut aggregation.players[i];
+
+                aggregated.name = player.name.clone();
+                aggregated.stats.0 += player.stats.0;
+                aggregated.stats.1 += player.stats.1;
+                aggregated.stats.2 += player.stats.2;
+                aggregated.objective_score += player.objective_score;
+                aggregated.rounds_played += player.rounds_played(&analysis.state.rounds);
+                aggregated.analyses += 1;
+
+                for (weapon, (kills, teamkills)) in &player.weapon_breakdown {
+                    let (total_kills, total_teamkills) = aggregated
+                        .weapon_breakdown
+                        .entry(weapon.clone())
+                        .or_default();
+
+                    *total_kills += kills;
+                    *total_teamkills += teamkills;
+                }
+
+                aggregated
+                    .kill_streaks
+                    .extend(player.kill_streaks.iter().filter(|s| !s.kills.is_empty()));
+            }
+        }
+
+        aggregation
+    }
+}
+
+impl AggregatedPlayer<'_> {
+    /// Returns the kills and teamkills with each weapon, ordered by most kills.
+    pub fn weapons_by_kills(&self) -> Vec<(&Weapon, u32, u32)> {
+        let mut weapons = self
+            .weapon_breakdown
+            .iter()
+            .map(|(weapon, (kills, teamkills))| (weapon, *kills, *teamkills))
+            .collect::<Vec<_>>();
+
+        weapons.sort_by_key(|(_, kills, _)| Reverse(*kills));
+
+        weapons
+    }
+
+    /// Returns the number of kills in the player's longest kill streak of any analysis.
+    pub fn longest_kill_streak(&self) -> usize {
+        self.kill_streaks
+            .iter()
+            .map(|streak| streak.kills.len())
+            .max()
+            .unwrap_or_default()
+    }
+}
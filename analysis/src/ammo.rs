@@ -0,0 +1,194 @@
+use crate::{AnalyzerEvent, AnalyzerState, ClientSlot, Player, mortality::MortalityState};
+use dem::types::FrameData;
+use dod::{Ammo, UserMessage, Weapon};
+use std::collections::BTreeMap;
+
+/// Rounds fired with a weapon by the player that recorded the demo.
+#[derive(Clone, Debug, Default)]
+pub struct AmmoUsage {
+    /// Rounds fired, estimated from the drops of the clip, or of the grenades carried for
+    /// grenades, which have no clip.
+    pub shots: u32,
+
+    /// Reloads completed with the weapon.
+    pub reloads: u32,
+}
+
+/// Ammo last reported to the player that recorded the demo.
+///
+/// Ammo messages are only sent to the POV, so shots are only estimated for that player, found by
+/// the view recorded in each frame.
+#[derive(Debug, Default)]
+pub struct AmmoTracking {
+    pov: Option<ClientSlot>,
+
+    /// Weapon selected by the POV.
+    weapon: Option<Weapon>,
+
+    /// Rounds left in the clip of each weapon carried by the POV.
+    clips: BTreeMap<Weapon, u8>,
+
+    /// Grenades carried by the POV.
+    grenades: Option<u16>,
+
+    /// Ammo used by grenades, as listed by the mod in [dod::WeaponList].
+    ///
+    /// Grenade ammo has no id in [dod::AMMO_IDS], so the list is the only way to tell it apart.
+    grenade_ammo: Option<Ammo>,
+}
+
+/// Estimates the shots fired with each weapon by the POV from the changes of its ammo.
+///
+/// Reloads refill the clip, so only drops of the clip count as shots.
+pub fn use_ammo_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    let (weapon, shots, reloads) = match event {
+        AnalyzerEvent::Frame(frame) => {
+            let FrameData::NetworkMessage(box_type) = &frame.frame_data else {
+                return;
+            };
+
+            let refdef = &box_type.1.info.refdef;
+
+            // Spectator views follow a camera rather than the recording player
+            if refdef.spectator == 0
+                && let Ok(slot) = u8::try_from(refdef.playernum).map(ClientSlot::from)
+            {
+                state.ammo_tracking.pov = Some(slot);
+            }
+
+            return;
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::ResetHUD(_)) => {
+            // Players spawn with full clips, so ammo reported before is stale
+            state.ammo_tracking.clips.clear();
+            state.ammo_tracking.grenades = None;
+
+            return;
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::CurWeapon(cur_weapon)) => {
+            // Switching weapons also reports the weapon put away, which is not fired
+            if !cur_weapon.is_active {
+                return;
+            }
+
+            let tracking = &mut state.ammo_tracking;
+            let weapon = cur_weapon.weapon.clone();
+
+            tracking.weapon = Some(weapon.clone());
+
+            let Some(last_clip) = tracking.clips.insert(weapon.clone(), cur_weapon.clip_ammo)
+            else {
+                return;
+            };
+
+            (
+                weapon,
+                last_clip.saturating_sub(cur_weapon.clip_ammo) as u32,
+                0,
+            )
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::WeaponList(weapon_list)) => {
+            if weapon_list.weapon.is_grenade() {
+                state.ammo_tracking.grenade_ammo = Some(weapon_list.primary_ammo.clone());
+            }
+
+            return;
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::ReloadDone(_)) => {
+            let Some(weapon) = state.ammo_tracking.weapon.clone() else {
+                return;
+            };
+
+            (weapon, 0, 1)
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::AmmoX(ammo_x)) => {
+            match track_grenades(state, &ammo_x.ammo, ammo_x.amount as u16) {
+                Some(update) => update,
+                None => return,
+            }
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::AmmoShort(ammo_short)) => {
+            match track_grenades(state, &ammo_short.ammo, ammo_short.amount) {
+                Some(update) => update,
+                None => return,
+            }
+        }
+
+        _ => return,
+    };
+
+    if shots == 0 && reloads == 0 {
+        return;
+    }
+
+    let Some(slot) = state.ammo_tracking.pov else {
+        return;
+    };
+
+    if let Some(player) = state.find_player_by_slot_mut(slot)
+        && player.is_alive()
+    {
+        let usage = player.ammo.entry(weapon).or_default();
+
+        usage.shots += shots;
+        usage.reloads += reloads;
+    }
+}
+
+/// Records the grenades carried, returning the grenade thrown when there is one less of them.
+///
+/// Grenade ammo is the one listed for grenades by [dod::WeaponList], since [Ammo::Grenade] is never
+/// parsed. Other ammo missing from [dod::AMMO_IDS] is parsed the same way, so only changes while a
+/// grenade is selected are recorded then.
+fn track_grenades(
+    state: &mut AnalyzerState,
+    ammo: &Ammo,
+    amount: u16,
+) -> Option<(Weapon, u32, u32)> {
+    let tracking = &mut state.ammo_tracking;
+    let grenade_ammo = tracking.grenade_ammo.as_ref().unwrap_or(&Ammo::Grenade);
+
+    let weapon = tracking.weapon.clone().filter(Weapon::is_grenade);
+
+    if ammo != grenade_ammo || (*grenade_ammo == Ammo::Unknown && weapon.is_none()) {
+        return None;
+    }
+
+    let last_amount = tracking.grenades.replace(amount)?;
+
+    Some((weapon?, last_amount.saturating_sub(amount) as u32, 0))
+}
+
+pub(crate) fn reset_ammo(state: &mut AnalyzerState) {
+    for player in state.players.iter_mut() {
+        player.ammo.clear();
+    }
+}
+
+impl Player {
+    /// Returns the kills made for each shot fired with the weapon, or [None] if no shots were
+    /// fired with it.
+    ///
+    /// Only available for the player that recorded the demo.
+    pub fn kills_per_shot(&self, weapon: &Weapon) -> Option<f32> {
+        let shots = self.ammo.get(weapon)?.shots;
+
+        if shots == 0 {
+            return None;
+        }
+
+        let (kills, teamkills) = self
+            .weapon_breakdown
+            .get(weapon)
+            .copied()
+            .unwrap_or_default();
+
+        Some((kills + teamkills) as f32 / shots as f32)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{AnalyzerEvent, AnalyzerState, time::GameTime};
+use dem::types::{EngineMessage, FrameData};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Time between two network frames of the recording above which the server, or the connection to
+/// it, is assumed to have stalled.
+const FRAME_GAP: Duration = Duration::from_secs(1);
+
+/// Time without the server sending its clock above which the times in the reports may drift.
+const SERVER_TIME_GAP: Duration = Duration::from_secs(2);
+
+/// Share of the usual number of network frames per second below which a second counts as a drop
+/// in the update rate.
+const UPDATE_RATE_DROP: f32 = 0.5;
+
+/// Kind of [ServerAnomaly].
+#[derive(Debug, PartialEq)]
+pub enum ServerAnomalyKind {
+    /// No network frames were recorded at all.
+    FrameGap,
+
+    /// Fewer network frames than usual were recorded.
+    UpdateRateDrop,
+
+    /// Network frames kept arriving, but the server did not send its clock.
+    MissingServerTime,
+}
+
+impl Display for ServerAnomalyKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerAnomalyKind::FrameGap => write!(f, "Frame time gap"),
+            ServerAnomalyKind::UpdateRateDrop => write!(f, "Update rate drop"),
+            ServerAnomalyKind::MissingServerTime => write!(f, "No server time"),
+        }
+    }
+}
+
+/// Stretch of the recording where the server lagged, which explains missing kills or times that
+/// drift in the reports better than a parsing error does.
+#[derive(Debug)]
+pub struct ServerAnomaly {
+    pub kind: ServerAnomalyKind,
+    pub start: GameTime,
+
+    /// Time the anomaly lasted on the recording clock.
+    pub duration: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct AnomalyDetection {
+    last_frame_time: Option<GameTime>,
+    last_server_time: Option<GameTime>,
+
+    /// Time of the first network frame in each second of the recording that had any, with the
+    /// number of network frames in that second.
+    updates_per_second: Vec<(GameTime, u32)>,
+}
+
+/// Records the stretches of the recording where frames or the server clock went missing.
+pub fn use_server_anomaly_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    match event {
+        AnalyzerEvent::Frame(frame) if matches!(frame.frame_data, FrameData::NetworkMessage(_)) => {
+            let time = state.current_time.clone();
+            let detection = &mut state.anomaly_detection;
+
+            if let Some(last_frame_time) = detection.last_frame_time.replace(time.clone()) {
+                let gap = time.real_offset.saturating_sub(last_frame_time.real_offset);
+
+                if gap > FRAME_GAP {
+                    state.server_anomalies.push(ServerAnomaly {
+                        kind: ServerAnomalyKind::FrameGap,
+                        start: last_frame_time,
+                        duration: gap,
+                    });
+                }
+            }
+
+            let second = time.real_offset.as_secs();
+
+            match detection.updates_per_second.last_mut() {
+                Some((start, count)) if start.real_offset.as_secs() == second => *count += 1,
+                _ => detection.updates_per_second.push((time, 1)),
+            }
+        }
+
+        AnalyzerEvent::EngineMessage(EngineMessage::SvcTime(_)) => {
+            let time = state.current_time.clone();
+
+            if let Some(gap) = server_time_gap(state, &time) {
+                let start = state.anomaly_detection.last_server_time.clone().unwrap();
+
+                state.server_anomalies.push(ServerAnomaly {
+                    kind: ServerAnomalyKind::MissingServerTime,
+                    start,
+                    duration: gap,
+                });
+            }
+
+            state.anomaly_detection.last_server_time = Some(time);
+        }
+
+        AnalyzerEvent::Finalization => {
+            if let Some(last_frame_time) = state.anomaly_detection.last_frame_time.clone()
+                && let Some(gap) = server_time_gap(state, &last_frame_time)
+            {
+                let start = state.anomaly_detection.last_server_time.clone().unwrap();
+
+                state.server_anomalies.push(ServerAnomaly {
+                    kind: ServerAnomalyKind::MissingServerTime,
+                    start,
+                    duration: gap,
+                });
+            }
+
+            let drops = update_rate_drops(&state.anomaly_detection.updates_per_second);
+
+            state.server_anomalies.extend(drops);
+            state
+                .server_anomalies
+                .sort_by_key(|anomaly| anomaly.start.real_offset);
+        }
+
+        _ => {}
+    }
+}
+
+/// Returns the time since the server last sent its clock, if it is long enough to be an anomaly.
+fn server_time_gap(state: &AnalyzerState, time: &GameTime) -> Option<Duration> {
+    let last_server_time = state.anomaly_detection.last_server_time.as_ref()?;
+    let gap = time
+        .real_offset
+        .saturating_sub(last_server_time.real_offset);
+
+    (gap > SERVER_TIME_GAP).then_some(gap)
+}
+
+/// Returns the stretches of consecutive seconds with far fewer network frames than the median
+/// second of the recording.
+fn update_rate_drops(updates_per_second: &[(GameTime, u32)]) -> Vec<ServerAnomaly> {
+    // The first and last seconds are only partly recorded
+    let Some(seconds) = updates_per_second.get(1..updates_per_second.len().saturating_sub(1))
+    else {
+        return vec![];
+    };
+
+    let mut counts = seconds.iter().map(|(_, count)| *count).collect::<Vec<_>>();
+    counts.sort();
+
+    let Some(median) = counts.get(counts.len() / 2) else {
+        return vec![];
+    };
+
+    let threshold = *median as f32 * UPDATE_RATE_DROP;
+    let mut drops: Vec<ServerAnomaly> = vec![];
+    let mut previous_second = None;
+
+    for (time, count) in seconds {
+        let second = time.real_offset.as_secs();
+
+        if *count as f32 >= threshold {
+            previous_second = None;
+            continue;
+        }
+
+        match drops.last_mut() {
+            Some(drop) if previous_second == Some(second - 1) => {
+                drop.duration += Duration::from_secs(1);
+            }
+            _ => drops.push(ServerAnomaly {
+                kind: ServerAnomalyKind::UpdateRateDrop,
+                start: time.clone(),
+                duration: Duration::from_secs(1),
+            }),
+        }
+
+        previous_second = Some(second);
+    }
+
+    drops
+}
@@ -0,0 +1,161 @@
+use crate::{
+    AnalyzerEvent, AnalyzerState, PlayerGlobalId, kill::killer_index, position::distance,
+    time::GameTime,
+};
+use dod::{Team, UserMessage};
+use std::time::Duration;
+
+/// Time before a kill in which a teammate of the killer must have fired, and blood must have been
+/// seen near the victim, for the teammate to be credited with a probable assist.
+pub const ASSIST_WINDOW: Duration = Duration::from_secs(3);
+
+/// Distance in world units from where the victim died within which blood counts as the victim
+/// being hit, since they may have moved between being hit and dying.
+const ASSIST_RADIUS: f32 = 256.;
+
+/// Maximum time between a kill and the victim's corpse for the corpse to be matched to it.
+const MAX_CORPSE_DELAY: Duration = Duration::from_secs(1);
+
+/// A probable assist, inferred since the game does not report damage or assists.
+///
+/// A teammate of the killer is credited when they were heard firing shortly before the kill, and
+/// blood was seen near where the victim died in that time. Neither proves that the teammate hit
+/// the victim, so assists are an estimate.
+#[derive(Debug)]
+pub struct Assist {
+    /// Time of the kill that was assisted.
+    pub time: GameTime,
+
+    pub assister: PlayerGlobalId,
+    pub killer: PlayerGlobalId,
+    pub victim: PlayerGlobalId,
+}
+
+/// A kill waiting for the victim's corpse to locate them.
+#[derive(Debug)]
+struct PendingAssist {
+    time: GameTime,
+    killer: PlayerGlobalId,
+    victim: PlayerGlobalId,
+    team: Team,
+}
+
+#[derive(Debug, Default)]
+pub struct AssistDetection {
+    /// Time and position of the blood seen within [ASSIST_WINDOW] of now.
+    blood_puffs: Vec<(GameTime, [f32; 3])>,
+
+    pending_assist: Option<PendingAssist>,
+}
+
+/// Infers probable assists from shots heard and blood seen before each kill between opponents.
+pub fn use_assist_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    match event {
+        AnalyzerEvent::UserMessage(UserMessage::BloodPuff(blood_puff)) => {
+            let time = state.current_time.clone();
+            let (x, y, z) = blood_puff.0;
+
+            state
+                .assist_detection
+                .blood_puffs
+                .retain(|(puff_time, _)| is_within_window(puff_time, &time));
+            state
+                .assist_detection
+                .blood_puffs
+                .push((time, [x as f32, y as f32, z as f32]));
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) => {
+            let killer = state.find_player_by_entity_index(killer_index(death_msg));
+            let victim = state.find_player_by_entity_index(death_msg.victim_client_index);
+
+            state.assist_detection.pending_assist = match (killer, victim) {
+                (Some(killer), Some(victim)) if killer != victim && killer.team != victim.team => {
+                    killer.team.clone().map(|team| PendingAssist {
+                        time: state.current_time.clone(),
+                        killer: killer.id.clone(),
+                        victim: victim.id.clone(),
+                        team,
+                    })
+                }
+                _ => None,
+            };
+        }
+
+        // The corpse is spawned where the victim died, right after the death message
+        AnalyzerEvent::UserMessage(UserMessage::ClCorpse(cl_corpse)) => {
+            let Some(pending) = state.assist_detection.pending_assist.take() else {
+                return;
+            };
+
+            if &state.current_time - &pending.time > MAX_CORPSE_DELAY {
+                return;
+            }
+
+            let (x, y, z) = cl_corpse.origin;
+            let victim_origin = [x as f32, y as f32, z as f32];
+
+            let was_hit = state
+                .assist_detection
+                .blood_puffs
+                .iter()
+                .any(|(time, origin)| {
+                    // Blood from the killing shot may arrive after the death message
+                    (time.viewdemo_offset >= pending.time.viewdemo_offset
+                        || is_within_window(time, &pending.time))
+                        && distance(*origin, victim_origin) <= ASSIST_RADIUS
+                });
+
+            if !was_hit {
+                return;
+            }
+
+            let assisters = state
+                .players
+                .iter()
+                .filter(|player| player.id != pending.killer)
+                .filter(|player| player.team.as_ref() == Some(&pending.team))
+                .filter(|player| {
+                    player
+                        .shots
+                        .iter()
+                        .any(|shot| is_within_window(shot, &pending.time))
+                })
+                .map(|player| player.id.clone())
+                .collect::<Vec<_>>();
+
+            for assister in assisters {
+                state.assists.push(Assist {
+                    time: pending.time.clone(),
+                    assister,
+                    killer: pending.killer.clone(),
+                    victim: pending.victim.clone(),
+                });
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Returns whether the time is at most [ASSIST_WINDOW] before the kill.
+fn is_within_window(time: &GameTime, kill_time: &GameTime) -> bool {
+    kill_time
+        .viewdemo_offset
+        .checked_sub(time.viewdemo_offset)
+        .is_some_and(|elapsed| elapsed <= ASSIST_WINDOW)
+}
+
+pub(crate) fn reset_assists(state: &mut AnalyzerState) {
+    state.assists.clear();
+}
+
+impl AnalyzerState {
+    /// Returns the number of probable assists credited to the player.
+    pub fn assists_by(&self, player: &PlayerGlobalId) -> usize {
+        self.assists
+            .iter()
+            .filter(|assist| assist.assister == *player)
+            .count()
+    }
+}
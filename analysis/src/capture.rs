@@ -0,0 +1,49 @@
+use crate::{AnalyzerEvent, AnalyzerState, PlayerGlobalId, time::GameTime};
+use dod::{Team, UserMessage};
+
+/// An objective captured by a player.
+#[derive(Debug)]
+pub struct Capture {
+    /// Time when the objective was captured.
+    pub time: GameTime,
+
+    /// Player that was credited with the capture.
+    pub player: PlayerGlobalId,
+
+    /// Name of the objective that was captured.
+    pub point_name: String,
+
+    /// Team that captured the objective.
+    pub team: Team,
+}
+
+pub fn use_capture_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    if let AnalyzerEvent::UserMessage(UserMessage::CapMsg(cap_msg)) = event {
+        let player = state.find_player_by_entity_index(cap_msg.client_index);
+
+        if let Some(player) = player {
+            let capture = Capture {
+                time: state.current_time.clone(),
+                player: player.id.clone(),
+                point_name: cap_msg.point_name.clone(),
+                team: cap_msg.team.clone(),
+            };
+
+            state.captures.push(capture);
+        }
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the number of objectives the player was credited with capturing.
+    pub fn captures_by(&self, player: &PlayerGlobalId) -> usize {
+        self.captures
+            .iter()
+            .filter(|capture| capture.player == *player)
+            .count()
+    }
+}
+
+pub(crate) fn reset_captures(state: &mut AnalyzerState) {
+    state.captures.clear();
+}
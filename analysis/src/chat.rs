@@ -0,0 +1,109 @@
+use crate::{AnalyzerEvent, AnalyzerState, PlayerGlobalId, Round, time::GameTime};
+use dod::{TextMsg, UserMessage};
+
+/// A line of text shown to players, either sent by a player or by the game.
+#[derive(Debug)]
+pub struct ChatMessage {
+    /// Time when the message was shown.
+    pub time: GameTime,
+
+    /// Player that sent the message, or none if it was sent by the game.
+    pub sender: Option<PlayerGlobalId>,
+
+    /// Text of the message, with localization tokens resolved.
+    pub text: String,
+}
+
+/// Number of lines kept before and after the end of a round in a [RoundChat].
+pub const ROUND_CHAT_LINES: usize = 3;
+
+/// What players said around the end of a round, e.g. "gg" or "nice hold".
+#[derive(Debug)]
+pub struct RoundChat<'a> {
+    /// Last messages sent during the round.
+    pub before: Vec<&'a ChatMessage>,
+
+    /// First messages sent after the round ended, before the next one started.
+    pub after: Vec<&'a ChatMessage>,
+}
+
+impl RoundChat<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the messages players sent around the end of the round at the index, or [None] if
+    /// the round has not ended.
+    pub fn round_chat(&self, index: usize) -> Option<RoundChat<'_>> {
+        let Some(Round::Completed {
+            start_time,
+            end_time,
+            ..
+        }) = self.rounds.get(index)
+        else {
+            return None;
+        };
+
+        let next_start_time = self.rounds.get(index + 1).map(Round::start_time);
+
+        // Messages from the game, such as flag captures, are not chat
+        let player_chat = || self.chat.iter().filter(|message| message.sender.is_some());
+
+        let mut before = player_chat()
+            .filter(|message| {
+                message.time.viewdemo_offset >= start_time.viewdemo_offset
+                    && message.time.viewdemo_offset < end_time.viewdemo_offset
+            })
+            .collect::<Vec<_>>();
+
+        before.drain(..before.len().saturating_sub(ROUND_CHAT_LINES));
+
+        let after = player_chat()
+            .filter(|message| {
+                message.time.viewdemo_offset >= end_time.viewdemo_offset
+                    && next_start_time
+                        .is_none_or(|next| message.time.viewdemo_offset < next.viewdemo_offset)
+            })
+            .take(ROUND_CHAT_LINES)
+            .collect();
+
+        Some(RoundChat { before, after })
+    }
+}
+
+pub fn use_chat_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    let message = match event {
+        AnalyzerEvent::UserMessage(UserMessage::SayText(say_text)) => {
+            // The world entity is the server itself, e.g. from the `say` command
+            let sender = state
+                .find_player_by_entity_index(say_text.client_index)
+                .map(|player| player.id.clone());
+
+            Some((sender, say_text.text.clone()))
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::TextMsg(text_msg))
+            if text_msg.destination != TextMsg::DESTINATION_CONSOLE =>
+        {
+            Some((None, text_msg.to_string()))
+        }
+
+        _ => None,
+    };
+
+    if let Some((sender, text)) = message {
+        let text = text.chars().filter(|c| !c.is_control()).collect::<String>();
+
+        if text.trim().is_empty() {
+            return;
+        }
+
+        state.chat.push(ChatMessage {
+            time: state.current_time.clone(),
+            sender,
+            text: text.trim().to_string(),
+        });
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{AnalyzerState, PlayerGlobalId, SteamId};
+use dod::Team;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// A named group of players, supplied by the user to label teams when tag detection is not enough.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ClanRoster {
+    /// Name shown in place of the team.
+    pub name: String,
+
+    /// Player ids or SteamIDs (`STEAM_X:Y:Z`) of the clan members.
+    #[serde(default)]
+    pub members: Vec<String>,
+}
+
+impl ClanRoster {
+    fn contains(&self, id: &PlayerGlobalId) -> bool {
+        let steam_id = SteamId::try_from(id).map(|steam_id| steam_id.to_string());
+
+        self.members.iter().any(|member| {
+            *member == id.to_string() || steam_id.as_ref().is_ok_and(|steam_id| member == steam_id)
+        })
+    }
+}
+
+/// The players that played on one side of a match, labeled by their clan.
+#[derive(Debug)]
+pub struct Clan {
+    /// Side the clan played on.
+    pub team: Team,
+
+    /// Clan name from a matching [ClanRoster], or the tag shared by most of the players' names.
+    pub tag: Option<String>,
+
+    /// Players that participated in at least one round on this side.
+    pub players: Vec<PlayerGlobalId>,
+}
+
+impl Clan {
+    /// Shortest tag that is considered meaningful, in characters.
+    const MIN_TAG_LENGTH: usize = 2;
+
+    /// Identifies the clan that played on a team.
+    ///
+    /// A roster is used when it contains at least half of the players on the team, otherwise the
+    /// tag is inferred from a prefix or suffix shared by at least half of the players' names.
+    pub fn new(state: &AnalyzerState, team: Team, rosters: &[ClanRoster]) -> Self {
+        let players = state
+            .players_on(team.clone())
+            .filter(|player| {
+                state
+                    .rounds
+                    .iter()
+                    .any(|round| round.is_participant(&player.id))
+            })
+            .collect::<Vec<_>>();
+
+        let quorum = players.len().div_ceil(2).max(1);
+
+        let roster = rosters
+            .iter()
+            .map(|roster| {
+                let members = players
+                    .iter()
+                    .filter(|player| roster.contains(&player.id))
+                    .count();
+
+                (roster, members)
+            })
+            .filter(|(_, members)| *members >= quorum)
+            .max_by_key(|(_, members)| *members);
+
+        let tag = match roster {
+            Some((roster, _)) => Some(roster.name.clone()),
+            None => {
+                let names = players
+                    .iter()
+                    .map(|player| player.name.chars().collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+
+                let prefix = shared_affix(&names, quorum);
+
+                let reversed_names = names
+                    .into_iter()
+                    .map(|name| name.into_iter().rev().collect())
+                    .collect::<Vec<_>>();
+
+                let suffix = shared_affix(&reversed_names, quorum)
+                    .map(|suffix| suffix.into_iter().rev().collect::<Vec<_>>());
+
+                [prefix, suffix]
+                    .into_iter()
+                    .flatten()
+                    .map(|tag| tag.into_iter().collect::<String>())
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| tag.chars().count() >= Self::MIN_TAG_LENGTH)
+                    .max_by_key(|tag| tag.chars().count())
+            }
+        };
+
+        Self {
+            team,
+            tag,
+            players: players
+                .into_iter()
+                .map(|player| player.id.clone())
+                .collect(),
+        }
+    }
+
+    /// Returns the clan tag, or the team name when no tag was found.
+    pub fn name(&self) -> String {
+        self.tag
+            .clone()
+            .unwrap_or_else(|| format!("{:?}", self.team))
+    }
+
+    /// Returns the share of players that both clans have in common, between 0 and 1.
+    ///
+    /// Useful for matching the same clan across demos where the sides were swapped.
+    pub fn roster_overlap(&self, other: &Clan) -> f32 {
+        let ours = self.players.iter().collect::<HashSet<_>>();
+        let theirs = other.players.iter().collect::<HashSet<_>>();
+
+        let union = ours.union(&theirs).count();
+
+        if union == 0 {
+            return 0.;
+        }
+
+        ours.intersection(&theirs).count() as f32 / union as f32
+    }
+}
+
+/// Finds the longest prefix that starts at least `quorum` of the names.
+///
+/// Tags are usually separated from the name by punctuation, so a prefix that ends in the middle of
+/// a word is cut back to the last non-alphanumeric character.
+fn shared_affix(names: &[Vec<char>], quorum: usize) -> Option<Vec<char>> {
+    if names.len() < 2 {
+        return None;
+    }
+
+    let mut candidates = vec![];
+
+    for (i, a) in names.iter().enumerate() {
+        for b in &names[i + 1..] {
+            let len = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+            let mut candidate = a[..len].to_vec();
+
+            if let Some(boundary) = candidate.iter().rposition(|c| !c.is_alphanumeric()) {
+                candidate.truncate(boundary + 1);
+            }
+
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter(|candidate| !candidate.is_empty())
+        .filter(|candidate| {
+            names
+                .iter()
+                .filter(|name| name.starts_with(candidate))
+                .count()
+                >= quorum
+        })
+        .max_by_key(|candidate| candidate.len())
+}
@@ -1,5 +1,4 @@
-use crate::mortality::{Mortality, MortalityChange};
-use crate::{AnalyzerEvent, AnalyzerState, round::Round, time::GameTime};
+use crate::{AnalyzerEvent, AnalyzerState, Round, time::GameTime};
 use dod::{RoundState, Team, UserMessage};
 use std::time::Duration;
 
@@ -40,26 +39,8 @@ pub fn use_clan_match_detection_updates(
             && state.team_scores.get_team_score(Team::Allies) == 0
             && state.team_scores.get_team_score(Team::Axis) == 0 =>
         {
-            state.rounds.clear();
-            state.rounds.push(Round::Active {
-                allies_kills: 0,
-                axis_kills: 0,
-                start_time: reset_time.clone(),
-            });
-
-            state.team_scores.reset();
-
-            for player in state.players.iter_mut() {
-                player.kill_streaks.clear();
-                player.weapon_breakdown.clear();
-
-                player.mortality.clear();
-                player.mortality.push(MortalityChange::new(
-                    state.current_time.clone(),
-                    Mortality::Alive,
-                ));
-            }
-
+            state.match_start_candidates.push(reset_time.clone());
+            state.request_match_start(reset_time.clone());
             state.clan_match_detection = ClanMatchDetection::MatchIsLive;
         }
 
@@ -76,6 +57,26 @@ pub fn use_clan_match_detection_updates(
             AnalyzerEvent::UserMessage(UserMessage::ClanTimer(_)),
         ) => state.clan_match_detection = ClanMatchDetection::WaitingForReset,
 
+        // Match is live, but the round was reset again before anyone won a round. We infer that
+        // the server restarts several times before going live, and wait for the last restart.
+        (
+            ClanMatchDetection::MatchIsLive,
+            AnalyzerEvent::UserMessage(UserMessage::RoundState(RoundState::Reset)),
+        ) if !state.rounds.iter().any(|round| {
+            matches!(
+                round,
+                Round::Completed {
+                    winner_stats: Some(_),
+                    ..
+                }
+            )
+        }) =>
+        {
+            state.clan_match_detection = ClanMatchDetection::WaitingForNormal {
+                reset_time: state.current_time.clone(),
+            };
+        }
+
         _ => {}
     };
 }
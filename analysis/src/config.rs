@@ -0,0 +1,145 @@
+use crate::{Codepage, IdentityMap, NarrativeTemplates, impact::WinModel, time::Clock};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Options that control how demos are analyzed and reported.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AnalysisConfig {
+    /// How kills on teammates are counted.
+    pub teamkill_policy: TeamkillPolicy,
+
+    /// Detect when a clan match goes live and reset the results gathered before it. Turn off for
+    /// public server demos, to treat the whole demo as one session.
+    pub clan_match_detection: bool,
+
+    /// Seconds allowed between a round reset and the round start for the reset to be treated as a
+    /// clan match going live.
+    pub clan_match_window_secs: u64,
+
+    /// Kill streaks with fewer kills than this are left out of reports.
+    pub min_streak: usize,
+
+    /// Show team scores for the players that started on each team, following them when the teams
+    /// swap sides, instead of for each side.
+    pub scores_by_clan: bool,
+
+    /// Credit probable assists to players, inferred from shots heard and blood seen before each
+    /// kill, since the game does not report them.
+    pub infer_assists: bool,
+
+    /// Show what players said around the end of each round in the rounds table.
+    pub round_chat: bool,
+
+    /// Character encoding used to decode player names and chat that are not valid UTF-8.
+    #[serde(with = "CodepageDef")]
+    pub codepage: Codepage,
+
+    /// Clock used to show times in reports.
+    pub clock: Clock,
+
+    /// Seconds into the demo, as shown by `viewdemo`, when the clan match went live. Replaces the
+    /// clan match detection when set.
+    ///
+    /// Never saved, since it only applies to a single demo.
+    #[serde(skip)]
+    pub match_start_secs: Option<u64>,
+
+    /// Round win probability model used to rate the impact of kills, instead of the one shipped
+    /// with the program.
+    ///
+    /// Never saved, since it is read from its own file.
+    #[serde(skip)]
+    pub win_model: Option<WinModel>,
+
+    /// Scoring formula of the league, to check the scores reported by the server against. Scores
+    /// are not checked when unset.
+    pub score_formula: Option<ScoreFormula>,
+
+    /// Wording of the narrative that summarizes the match, e.g. to write it in another language.
+    pub narrative: NarrativeTemplates,
+
+    /// Global IDs declared to belong to the same person.
+    ///
+    /// Never saved with the settings, since it is read from its own file.
+    #[serde(skip)]
+    pub identities: IdentityMap,
+}
+
+impl AnalysisConfig {
+    pub fn clan_match_window(&self) -> Duration {
+        Duration::from_secs(self.clan_match_window_secs)
+    }
+
+    pub fn match_start(&self) -> Option<Duration> {
+        self.match_start_secs.map(Duration::from_secs)
+    }
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            teamkill_policy: TeamkillPolicy::default(),
+            clan_match_detection: true,
+            clan_match_window_secs: 10,
+            min_streak: 1,
+            scores_by_clan: false,
+            infer_assists: false,
+            round_chat: false,
+            codepage: Codepage::default(),
+            clock: Clock::default(),
+            match_start_secs: None,
+            win_model: None,
+            score_formula: None,
+            narrative: NarrativeTemplates::default(),
+            identities: IdentityMap::default(),
+        }
+    }
+}
+
+/// Points a league gives players for each scoring event, for servers that run scoring plugins.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ScoreFormula {
+    pub points_per_capture: i32,
+    pub points_per_kill: i32,
+
+    /// Points for each kill on a teammate, usually zero or negative.
+    pub points_per_teamkill: i32,
+
+    /// Points for each death by the player's own weapon, usually zero or negative.
+    pub points_per_suicide: i32,
+}
+
+impl Default for ScoreFormula {
+    fn default() -> Self {
+        Self {
+            points_per_capture: 1,
+            points_per_kill: 1,
+            points_per_teamkill: 0,
+            points_per_suicide: 0,
+        }
+    }
+}
+
+/// Serialized form of [Codepage], which the parsers crate declares without serde.
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Codepage")]
+enum CodepageDef {
+    Latin1,
+    Windows1252,
+}
+
+/// How kills on teammates are counted in kill streaks and round kills.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum TeamkillPolicy {
+    /// Teamkills are left out.
+    #[default]
+    Exclude,
+
+    /// Teamkills are left out, and they also end the killer's current streak.
+    Penalize,
+
+    /// Teamkills count like any other kill.
+    Include,
+}
@@ -0,0 +1,71 @@
+use crate::{Player, Round};
+
+/// How evenly a [Player]'s kills are spread across the completed rounds they played in a match.
+#[derive(Debug)]
+pub struct Consistency {
+    /// Number of kills made by the player in each completed round they participated in.
+    pub kills_per_round: Vec<u32>,
+
+    /// Average number of kills per round.
+    pub mean: f32,
+
+    /// Population variance of the kills per round.
+    pub variance: f32,
+
+    /// Score between 0 and 1, where 1 means the player got the same number of kills every round.
+    ///
+    /// Calculated as `1 / (1 + cv)`, where `cv` is the coefficient of variation of the kills per
+    /// round.
+    pub index: f32,
+}
+
+impl Consistency {
+    /// Index at or above which a player is considered steady rather than streaky.
+    const STEADY_THRESHOLD: f32 = 0.5;
+
+    pub fn new(player: &Player, rounds: &[Round]) -> Self {
+        let kills_per_round = rounds
+            .iter()
+            .filter(|round| matches!(round, Round::Completed { .. }))
+            .filter(|round| round.is_participant(&player.id))
+            .map(|round| player.kills_in(round).count() as u32)
+            .collect::<Vec<_>>();
+
+        let num_rounds = kills_per_round.len() as f32;
+
+        if num_rounds == 0. {
+            return Self {
+                kills_per_round,
+                mean: 0.,
+                variance: 0.,
+                index: 0.,
+            };
+        }
+
+        let mean = kills_per_round.iter().sum::<u32>() as f32 / num_rounds;
+
+        let variance = kills_per_round
+            .iter()
+            .map(|kills| (*kills as f32 - mean).powi(2))
+            .sum::<f32>()
+            / num_rounds;
+
+        let index = if mean > 0. {
+            1. / (1. + variance.sqrt() / mean)
+        } else {
+            0.
+        };
+
+        Self {
+            kills_per_round,
+            mean,
+            variance,
+            index,
+        }
+    }
+
+    /// Returns true if the player's kills are evenly spread across rounds.
+    pub fn is_steady(&self) -> bool {
+        self.index >= Self::STEADY_THRESHOLD
+    }
+}
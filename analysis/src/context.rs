@@ -0,0 +1,208 @@
+use crate::{
+    AnalysisConfig, AnalyzerEvent, AnalyzerState, Player,
+    ammo::{reset_ammo, use_ammo_updates},
+    anomaly::use_server_anomaly_updates,
+    assist::{reset_assists, use_assist_updates},
+    capture::{reset_captures, use_capture_updates},
+    chat::use_chat_updates,
+    clan_match::use_clan_match_detection_updates,
+    danger::{reset_death_positions, use_death_position_updates},
+    distance::{reset_kill_distances, use_kill_distance_updates},
+    kill::{
+        reset_kills, use_kill_feed_updates, use_kill_streak_updates, use_weapon_breakdown_updates,
+    },
+    latency::use_hltv_detection_updates,
+    malformed::use_malformed_message_updates,
+    mortality::{reset_mortality, with_mortality_detection},
+    player::use_player_updates,
+    position::use_position_updates,
+    round::{reset_rounds, use_rounds_updates},
+    scoreboard::{
+        reset_scoreboard, use_scoreboard_history_updates, use_scoreboard_updates,
+        use_team_score_updates,
+    },
+    shots::{reset_shots, use_shot_updates},
+    swap::{reset_team_swaps, use_team_swap_updates},
+    teamkills::{reset_teamkills, use_teamkill_updates},
+    time::{GameTime, use_timing_updates},
+    time_left::{reset_time_left, use_time_left_updates},
+    warmup::stash_warmup,
+};
+use std::time::Duration;
+
+/// Starts the match at the first event at or after the time given by the user, when the clan match
+/// detection picked the wrong moment or none at all.
+fn use_match_start_override(match_start: Duration, state: &mut AnalyzerState) {
+    if state.match_start_time.is_none()
+        && state.requested_match_start.is_none()
+        && state.current_time.viewdemo_offset >= match_start
+    {
+        state.request_match_start(state.current_time.clone());
+    }
+}
+
+/// Runs the analyzers over the events of a demo.
+///
+/// The clock and the players are shared by all analyzers, which keep their own results in the
+/// [AnalyzerState]. Analyzers never clear each other's results: a reset is requested with
+/// [AnalyzerState::request_match_start], and the context applies it once every analyzer has seen
+/// the event, by asking each one to reset its own results.
+pub(crate) struct AnalysisContext<'a> {
+    config: &'a AnalysisConfig,
+    state: AnalyzerState,
+}
+
+impl<'a> AnalysisContext<'a> {
+    pub fn new(config: &'a AnalysisConfig) -> Self {
+        Self {
+            config,
+            state: AnalyzerState::default(),
+        }
+    }
+
+    pub fn handle(&mut self, event: &AnalyzerEvent) {
+        let config = self.config;
+        let state = &mut self.state;
+
+        use_timing_updates(state, event);
+        use_player_updates(&config.identities, config.codepage, state, event);
+        with_mortality_detection(state, event);
+        use_position_updates(state, event);
+        use_shot_updates(state, event);
+        use_ammo_updates(state, event);
+        use_team_swap_updates(state, event);
+        use_scoreboard_updates(state, event);
+        use_scoreboard_history_updates(state, event);
+        use_kill_feed_updates(state, event);
+        use_teamkill_updates(state, event);
+        use_kill_streak_updates(&config.teamkill_policy, state, event);
+        use_weapon_breakdown_updates(state, event);
+        use_kill_distance_updates(state, event);
+        use_death_position_updates(state, event);
+        use_team_score_updates(state, event);
+        use_rounds_updates(&config.teamkill_policy, state, event);
+        use_capture_updates(state, event);
+        use_chat_updates(state, event);
+        use_hltv_detection_updates(state, event);
+        use_malformed_message_updates(state, event);
+        use_server_anomaly_updates(state, event);
+        use_time_left_updates(state, event);
+
+        if config.infer_assists {
+            use_assist_updates(state, event);
+        }
+
+        match config.match_start() {
+            Some(match_start) => use_match_start_override(match_start, state),
+            None if config.clan_match_detection => {
+                use_clan_match_detection_updates(config.clan_match_window(), state, event)
+            }
+            None => {}
+        }
+
+        if let Some(start_time) = state.requested_match_start.take() {
+            self.start_match(start_time);
+        }
+    }
+
+    /// Moves the results gathered before the clan match went live into the warmup, and clears
+    /// the rest.
+    fn start_match(&mut self, start_time: GameTime) {
+        let state = &mut self.state;
+
+        assert_every_result_is_reset(state);
+        stash_warmup(state);
+
+        reset_rounds(state, start_time.clone());
+        reset_scoreboard(state);
+        reset_captures(state);
+        reset_kill_distances(state);
+        reset_death_positions(state);
+        reset_kills(state);
+        reset_teamkills(state);
+        reset_assists(state);
+        reset_team_swaps(state);
+        reset_shots(state);
+        reset_ammo(state);
+        reset_mortality(state);
+        reset_time_left(state);
+
+        state.match_start_time = Some(start_time);
+    }
+
+    pub fn into_state(self) -> AnalyzerState {
+        self.state
+    }
+}
+
+/// Lists every field of the [AnalyzerState] and of its [Player]s with the reset that clears it when
+/// the match starts, or as kept across the match start.
+///
+/// Does nothing when run. The patterns name every field, so a field added to either struct does not
+/// compile until it is listed here, and with it, until its analyzer is registered for the reset in
+/// [AnalysisContext::start_match].
+fn assert_every_result_is_reset(state: &AnalyzerState) {
+    let AnalyzerState {
+        // stash_warmup, then the reset of each analyzer
+        kills: _,
+        captures: _,
+        rounds: _,
+        round_scoreboard: _,
+        team_scores: _,
+        scoreboard_history: _,
+        kill_distances: _,
+        death_positions: _,
+        teamkills: _,
+        assists: _,
+        team_swaps: _,
+        pending_side_switches: _,
+        time_left_samples: _,
+        match_start_time: _,
+
+        // Kept: the clock and the players are shared by all analyzers
+        current_time: _,
+        segment_offset: _,
+        segment_started: _,
+        players,
+
+        // Kept: tracking of what is in progress, which goes on across the match start
+        ammo_tracking: _,
+        anomaly_detection: _,
+        assist_detection: _,
+        clan_match_detection: _,
+        match_start_candidates: _,
+        pending_kill: _,
+        recorded_by_hltv: _,
+        requested_match_start: _,
+
+        // Kept: results over the whole demo
+        chat: _,
+        malformed_messages: _,
+        server_anomalies: _,
+        user_info_history: _,
+        warmup: _,
+    } = state;
+
+    for player in players {
+        let Player {
+            // reset_kills, reset_mortality, reset_shots, and reset_ammo
+            kill_streaks: _,
+            weapon_breakdown: _,
+            deaths_by_weapon: _,
+            mortality: _,
+            shots: _,
+            ammo: _,
+
+            // Kept: who the player is, and what the server reports
+            id: _,
+            connection: _,
+            name: _,
+            team: _,
+            class: _,
+            stats: _,
+            objective_score: _,
+            first_connected: _,
+            positions: _,
+        } = player;
+    }
+}
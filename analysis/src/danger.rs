@@ -0,0 +1,110 @@
+use crate::{AnalyzerEvent, AnalyzerState, time::GameTime};
+use dod::{Team, UserMessage};
+use std::collections::BTreeMap;
+
+/// Where a player died, located by their corpse.
+#[derive(Debug)]
+pub struct DeathPosition {
+    pub time: GameTime,
+
+    /// Team of the player that died.
+    pub team: Team,
+
+    /// Position of the corpse in world units.
+    pub origin: [f32; 3],
+}
+
+/// An area of the map where players died, from a grid laid over the whole map.
+#[derive(Debug)]
+pub struct DangerZone {
+    /// Grid reference of the area, such as `F7`, with lettered columns running west to east and
+    /// numbered rows running north to south.
+    pub name: String,
+
+    /// Center of the area on the horizontal plane, in world units.
+    pub center: [f32; 2],
+
+    pub allies_deaths: usize,
+    pub axis_deaths: usize,
+}
+
+impl DangerZone {
+    /// Length of a side of an area in world units.
+    pub const SIZE: f32 = 512.;
+
+    pub fn deaths(&self) -> usize {
+        self.allies_deaths + self.axis_deaths
+    }
+}
+
+/// Largest coordinate of a map in world units, in either direction from the origin.
+const WORLD_EXTENT: f32 = 4096.;
+
+/// Number of areas along each side of the grid.
+const GRID_SIZE: i32 = (2. * WORLD_EXTENT / DangerZone::SIZE) as i32;
+
+pub fn use_death_position_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    let AnalyzerEvent::UserMessage(UserMessage::ClCorpse(cl_corpse)) = event else {
+        return;
+    };
+
+    let (x, y, z) = cl_corpse.origin;
+
+    state.death_positions.push(DeathPosition {
+        time: state.current_time.clone(),
+        team: cl_corpse.team.clone(),
+        origin: [x as f32, y as f32, z as f32],
+    });
+}
+
+pub(crate) fn reset_death_positions(state: &mut AnalyzerState) {
+    state.death_positions.clear();
+}
+
+/// Returns the column and row of the area that contains a position.
+fn grid_cell(origin: [f32; 3]) -> (i32, i32) {
+    let column = ((origin[0] + WORLD_EXTENT) / DangerZone::SIZE).floor() as i32;
+    let row = ((WORLD_EXTENT - origin[1]) / DangerZone::SIZE).floor() as i32;
+
+    (column.clamp(0, GRID_SIZE - 1), row.clamp(0, GRID_SIZE - 1))
+}
+
+impl AnalyzerState {
+    /// Returns the areas where players died, most deaths first.
+    ///
+    /// The grid is the same on every map, so areas with the same name can be compared across
+    /// demos of a map.
+    pub fn danger_zones(&self) -> Vec<DangerZone> {
+        let mut zones = BTreeMap::new();
+
+        for death in &self.death_positions {
+            let (column, row) = grid_cell(death.origin);
+
+            let zone = zones.entry((column, row)).or_insert_with(|| DangerZone {
+                name: format!("{}{}", char::from(b'A' + column as u8), row + 1),
+                center: [
+                    (column as f32 + 0.5) * DangerZone::SIZE - WORLD_EXTENT,
+                    WORLD_EXTENT - (row as f32 + 0.5) * DangerZone::SIZE,
+                ],
+                allies_deaths: 0,
+                axis_deaths: 0,
+            });
+
+            match death.team {
+                Team::Allies => zone.allies_deaths += 1,
+                Team::Axis => zone.axis_deaths += 1,
+                Team::Spectators => {}
+            }
+        }
+
+        let mut zones = zones
+            .into_values()
+            .filter(|zone| zone.deaths() > 0)
+            .collect::<Vec<_>>();
+
+        // Stable, so ties keep the grid order
+        zones.sort_by(|l, r| l.deaths().cmp(&r.deaths()).reverse());
+
+        zones
+    }
+}
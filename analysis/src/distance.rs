@@ -0,0 +1,165 @@
+use crate::{AnalyzerEvent, AnalyzerState, PlayerGlobalId, position::distance, time::GameTime};
+use dod::{UserMessage, Weapon};
+use std::time::Duration;
+
+/// Distance between a killer and their victim when the kill happened.
+#[derive(Debug)]
+pub struct KillDistance {
+    /// Time when the kill happened.
+    pub time: GameTime,
+
+    pub killer: PlayerGlobalId,
+    pub victim: PlayerGlobalId,
+    pub weapon: Weapon,
+
+    /// Distance in world units, where one unit is roughly one inch.
+    pub distance: f32,
+}
+
+impl KillDistance {
+    /// Distance in world units at or above which a kill is considered long range.
+    pub const LONG_RANGE: f32 = 1500.;
+
+    /// Returns the distance in meters.
+    pub fn meters(&self) -> f32 {
+        self.distance * METERS_PER_UNIT
+    }
+
+    pub fn is_long_range(&self) -> bool {
+        self.distance >= Self::LONG_RANGE
+    }
+}
+
+/// Approximate length of a world unit, which is roughly one inch.
+const METERS_PER_UNIT: f32 = 0.0254;
+
+/// A kill waiting for the victim's corpse to locate them.
+#[derive(Debug)]
+pub(crate) struct PendingKill {
+    time: GameTime,
+    killer: PlayerGlobalId,
+    victim: PlayerGlobalId,
+    weapon: Weapon,
+    killer_origin: [f32; 3],
+}
+
+/// Maximum age of the killer's last position sample for it to count as their position at the kill,
+/// and of a kill for a corpse to be matched to it.
+const MAX_SAMPLE_AGE: Duration = Duration::from_secs(1);
+
+pub fn use_kill_distance_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    match event {
+        AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) => {
+            let killer = state.find_player_by_entity_index(death_msg.killer_client_index);
+            let victim = state.find_player_by_entity_index(death_msg.victim_client_index);
+
+            state.pending_kill = match (killer, victim) {
+                (Some(killer), Some(victim)) if killer != victim && killer.team != victim.team => {
+                    killer
+                        .positions
+                        .last()
+                        .filter(|sample| &state.current_time - &sample.time <= MAX_SAMPLE_AGE)
+                        .map(|sample| PendingKill {
+                            time: state.current_time.clone(),
+                            killer: killer.id.clone(),
+                            victim: victim.id.clone(),
+                            weapon: death_msg.weapon.clone(),
+                            killer_origin: sample.origin,
+                        })
+                }
+                _ => None,
+            };
+        }
+
+        // The corpse is spawned where the victim died, right after the death message
+        AnalyzerEvent::UserMessage(UserMessage::ClCorpse(cl_corpse)) => {
+            if let Some(pending_kill) = state.pending_kill.take()
+                && &state.current_time - &pending_kill.time <= MAX_SAMPLE_AGE
+            {
+                let (x, y, z) = cl_corpse.origin;
+                let victim_origin = [x as f32, y as f32, z as f32];
+
+                state.kill_distances.push(KillDistance {
+                    distance: distance(pending_kill.killer_origin, victim_origin),
+                    time: pending_kill.time,
+                    killer: pending_kill.killer,
+                    victim: pending_kill.victim,
+                    weapon: pending_kill.weapon,
+                });
+            }
+        }
+
+        _ => {}
+    }
+}
+
+pub(crate) fn reset_kill_distances(state: &mut AnalyzerState) {
+    state.kill_distances.clear();
+}
+
+/// Kill distances of a player with a single weapon.
+#[derive(Debug)]
+pub struct WeaponRange {
+    pub weapon: Weapon,
+    pub kills: u32,
+
+    /// Average distance in world units.
+    pub avg_distance: f32,
+
+    /// Number of kills at or above [KillDistance::LONG_RANGE].
+    pub long_range_kills: u32,
+}
+
+impl WeaponRange {
+    /// Returns the average distance in meters.
+    pub fn avg_meters(&self) -> f32 {
+        self.avg_distance * METERS_PER_UNIT
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the kill distances of a player grouped by weapon, most used first.
+    pub fn weapon_ranges(&self, player: &PlayerGlobalId) -> Vec<WeaponRange> {
+        let mut ranges: Vec<WeaponRange> = vec![];
+
+        for kill in self
+            .kill_distances
+            .iter()
+            .filter(|kill| kill.killer == *player)
+        {
+            let range = match ranges.iter_mut().find(|range| range.weapon == kill.weapon) {
+                Some(range) => range,
+                None => {
+                    ranges.push(WeaponRange {
+                        weapon: kill.weapon.clone(),
+                        kills: 0,
+                        avg_distance: 0.,
+                        long_range_kills: 0,
+                    });
+
+                    ranges.last_mut().unwrap()
+                }
+            };
+
+            range.avg_distance = (range.avg_distance * range.kills as f32 + kill.distance)
+                / (range.kills + 1) as f32;
+            range.kills += 1;
+
+            if kill.is_long_range() {
+                range.long_range_kills += 1;
+            }
+        }
+
+        ranges.sort_by(|l, r| l.kills.cmp(&r.kills).reverse());
+
+        ranges
+    }
+
+    /// Returns the kill made from the farthest away by a player.
+    pub fn longest_kill(&self, player: &PlayerGlobalId) -> Option<&KillDistance> {
+        self.kill_distances
+            .iter()
+            .filter(|kill| kill.killer == *player)
+            .max_by(|l, r| l.distance.total_cmp(&r.distance))
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{AnalyzerState, Kill};
+use dod::Team;
+use std::time::Duration;
+
+/// Longest time after a teammate's death for a kill to count as trading it.
+pub const TRADE_WINDOW: Duration = Duration::from_secs(5);
+
+/// How many lives a side spent for the flags it captured, and how often it answered its deaths.
+#[derive(Debug)]
+pub struct DeathEconomy {
+    pub team: Team,
+
+    /// Deaths of the players on the side, including teamkills and suicides.
+    pub deaths: usize,
+
+    /// Flags captured by the side.
+    pub captures: usize,
+
+    /// Deaths that a teammate answered with a kill within the [TRADE_WINDOW].
+    pub traded_deaths: usize,
+
+    /// Kills made within the [TRADE_WINDOW] after a teammate died.
+    pub trade_kills: usize,
+}
+
+impl DeathEconomy {
+    pub fn new(state: &AnalyzerState, team: Team) -> Self {
+        let deaths = state
+            .kills
+            .iter()
+            .filter(|kill| victim_side(state, kill).as_ref() == Some(&team))
+            .collect::<Vec<_>>();
+
+        let kills = state
+            .kills
+            .iter()
+            .filter(|kill| kill.is_enemy_kill() && killer_side(state, kill).as_ref() == Some(&team))
+            .collect::<Vec<_>>();
+
+        let is_trade = |death: &Kill, kill: &Kill| {
+            kill.time.viewdemo_offset >= death.time.viewdemo_offset
+                && &kill.time - &death.time <= TRADE_WINDOW
+                && kill.killer.as_ref() != Some(&death.victim)
+        };
+
+        let traded_deaths = deaths
+            .iter()
+            .filter(|death| kills.iter().any(|kill| is_trade(death, kill)))
+            .count();
+
+        let trade_kills = kills
+            .iter()
+            .filter(|kill| deaths.iter().any(|death| is_trade(death, kill)))
+            .count();
+
+        Self {
+            captures: state
+                .captures
+                .iter()
+                .filter(|capture| capture.team == team)
+                .count(),
+            deaths: deaths.len(),
+            traded_deaths,
+            trade_kills,
+            team,
+        }
+    }
+
+    /// Returns the deaths spent for each flag captured, or [None] if the side captured none.
+    pub fn deaths_per_capture(&self) -> Option<f32> {
+        (self.captures > 0).then(|| self.deaths as f32 / self.captures as f32)
+    }
+
+    /// Returns the share of deaths that were traded, between 0 and 1.
+    pub fn trade_rate(&self) -> f32 {
+        if self.deaths == 0 {
+            return 0.;
+        }
+
+        self.traded_deaths as f32 / self.deaths as f32
+    }
+}
+
+fn killer_side(state: &AnalyzerState, kill: &Kill) -> Option<Team> {
+    let killer = state.find_player_by_id(kill.killer.as_ref()?)?;
+
+    state.side_of(killer, &kill.time)
+}
+
+fn victim_side(state: &AnalyzerState, kill: &Kill) -> Option<Team> {
+    let victim = state.find_player_by_id(&kill.victim)?;
+
+    state.side_of(victim, &kill.time)
+}
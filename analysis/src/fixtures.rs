@@ -0,0 +1,25 @@
+//! States shared by the tests of the analyzers.
+
+use crate::{AnalyzerState, ClientSlot, Connection, Player, PlayerGlobalId, time::GameTime};
+use dod::Team;
+
+/// Returns a state with a connected player on each of the teams, in client indexes starting at 1.
+pub(crate) fn state_with_players(teams: &[Team]) -> AnalyzerState {
+    let mut state = AnalyzerState::default();
+
+    for (client_id, team) in teams.iter().enumerate() {
+        let mut player = Player::new(
+            PlayerGlobalId(format!("PLAYER_{client_id}")),
+            GameTime::default(),
+        );
+
+        player.connection = Connection::Connected {
+            client_id: ClientSlot::from(client_id as u8),
+        };
+        player.team = Some(team.clone());
+
+        state.players.push(player);
+    }
+
+    state
+}
@@ -0,0 +1,85 @@
+use crate::{Player, position::PositionSample, time::GameTime};
+use std::time::Duration;
+
+/// A stretch of time where a player stayed in one spot and got kills from it, such as a machine
+/// gun or sniper holding an angle.
+#[derive(Debug)]
+pub struct PositionHold {
+    pub start_time: GameTime,
+    pub end_time: GameTime,
+
+    /// Position where the hold started, in world units.
+    pub origin: [f32; 3],
+
+    /// Number of kills made during the hold.
+    pub kills: usize,
+}
+
+impl PositionHold {
+    /// Maximum distance in world units a player can move from the origin and still hold it.
+    const RADIUS: f32 = 128.;
+
+    /// Minimum time a player must stay within [PositionHold::RADIUS] for it to count as a hold.
+    const MIN_DURATION: Duration = Duration::from_secs(20);
+
+    /// Largest gap between samples before they are considered separate lives.
+    const MAX_SAMPLE_GAP: Duration = Duration::from_secs(1);
+
+    pub fn duration(&self) -> Duration {
+        &self.end_time - &self.start_time
+    }
+}
+
+impl Player {
+    /// Returns the positions the player held while getting kills.
+    ///
+    /// Only available for the player that recorded the demo.
+    pub fn positions_held(&self) -> Vec<PositionHold> {
+        let mut holds = vec![];
+        let mut samples = self.positions.iter();
+
+        let Some(mut anchor) = samples.next() else {
+            return holds;
+        };
+
+        let mut last = anchor;
+
+        for sample in samples {
+            let moved = sample.distance_to(anchor.origin) > PositionHold::RADIUS;
+            let respawned = &sample.time - &last.time > PositionHold::MAX_SAMPLE_GAP;
+
+            if moved || respawned {
+                holds.extend(self.hold_between(anchor, last));
+                anchor = sample;
+            }
+
+            last = sample;
+        }
+
+        holds.extend(self.hold_between(anchor, last));
+
+        holds
+    }
+
+    fn hold_between(&self, start: &PositionSample, end: &PositionSample) -> Option<PositionHold> {
+        if &end.time - &start.time < PositionHold::MIN_DURATION {
+            return None;
+        }
+
+        let held = start.time.viewdemo_offset..=end.time.viewdemo_offset;
+
+        let kills = self
+            .kill_streaks
+            .iter()
+            .flat_map(|streak| streak.kills.iter())
+            .filter(|(time, _)| held.contains(&time.viewdemo_offset))
+            .count();
+
+        (kills > 0).then(|| PositionHold {
+            start_time: start.time.clone(),
+            end_time: end.time.clone(),
+            origin: start.origin,
+            kills,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{PlayerGlobalId, SteamId};
+use serde::{Deserialize, Serialize};
+
+/// Global IDs that were declared to belong to the same person, e.g. an ID from an old `*fid` and
+/// their SteamID.
+///
+/// Players are recorded under the first ID of their group, so their stats are merged in every demo
+/// analyzed afterward.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct IdentityMap {
+    pub identities: Vec<Vec<PlayerGlobalId>>,
+}
+
+impl IdentityMap {
+    /// Returns the ID the player is recorded under.
+    pub fn canonical(&self, id: &PlayerGlobalId) -> PlayerGlobalId {
+        self.group_of(id)
+            .and_then(|group| group.first())
+            .unwrap_or(id)
+            .clone()
+    }
+
+    /// Returns the other IDs declared to belong to the same person.
+    pub fn aliases(&self, id: &PlayerGlobalId) -> Vec<&PlayerGlobalId> {
+        self.group_of(id)
+            .map(|group| group.iter().filter(|alias| *alias != id).collect())
+            .unwrap_or_default()
+    }
+
+    /// Declares that the IDs belong to the same person, joining any groups they were already in.
+    pub fn merge(&mut self, ids: &[PlayerGlobalId]) {
+        let mut merged: Vec<PlayerGlobalId> = vec![];
+
+        for id in ids {
+            let group = match self.identities.iter().position(|group| group.contains(id)) {
+                Some(i) => self.identities.swap_remove(i),
+                None => vec![id.clone()],
+            };
+
+            for id in group {
+                if !merged.contains(&id) {
+                    merged.push(id);
+                }
+            }
+        }
+
+        // SteamIDs never change, so they make the best ID to record players under
+        merged.sort_by_key(|id| SteamId::try_from(id).is_err());
+
+        if merged.len() > 1 {
+            self.identities.push(merged);
+        }
+    }
+
+    /// Removes the ID from its group, so it is recorded as a separate player again.
+    pub fn split(&mut self, id: &PlayerGlobalId) {
+        for group in &mut self.identities {
+            group.retain(|alias| alias != id);
+        }
+
+        self.identities.retain(|group| group.len() > 1);
+    }
+
+    fn group_of(&self, id: &PlayerGlobalId) -> Option<&Vec<PlayerGlobalId>> {
+        self.identities.iter().find(|group| group.contains(id))
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{AnalyzerState, Kill, PlayerGlobalId, Round, mortality::MortalityState};
+use dod::Team;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Players alive on each side at a moment in a round.
+#[derive(Clone, Debug)]
+pub struct RoundSituation {
+    pub allies_alive: u32,
+    pub axis_alive: u32,
+
+    /// Time since the round started.
+    pub elapsed: Duration,
+}
+
+impl RoundSituation {
+    fn features(&self) -> [f32; 3] {
+        let alive_difference = self.allies_alive as f32 - self.axis_alive as f32;
+        let elapsed_minutes = self.elapsed.as_secs_f32() / 60.;
+
+        [1., alive_difference, alive_difference * elapsed_minutes]
+    }
+}
+
+/// Logistic model of the chance that the Allies win a round, given a [RoundSituation].
+///
+/// Parameters are read from data shipped with the program, and can be recomputed from a set of
+/// demos with [WinModel::fit].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WinModel {
+    /// Log-odds of the Allies winning when both sides have as many players alive.
+    pub intercept: f32,
+
+    /// Change in log-odds for each player the Allies have alive over the Axis.
+    pub alive_difference: f32,
+
+    /// Change in log-odds for each player the Allies have alive over the Axis, for each minute
+    /// into the round, since an advantage late in a round is harder to recover from.
+    pub late_alive_difference: f32,
+}
+
+/// Rounds of gradient descent used to fit a [WinModel].
+const FIT_ITERATIONS: usize = 2000;
+
+/// Step size of gradient descent used to fit a [WinModel].
+const FIT_LEARNING_RATE: f32 = 0.05;
+
+impl WinModel {
+    fn weights(&self) -> [f32; 3] {
+        [
+            self.intercept,
+            self.alive_difference,
+            self.late_alive_difference,
+        ]
+    }
+
+    /// Returns the chance that the team wins the round, between 0 and 1.
+    pub fn win_probability(&self, team: &Team, situation: &RoundSituation) -> f32 {
+        let log_odds = self
+            .weights()
+            .iter()
+            .zip(situation.features())
+            .map(|(weight, feature)| weight * feature)
+            .sum::<f32>();
+
+        let allies = 1. / (1. + (-log_odds).exp());
+
+        match team {
+            Team::Axis => 1. - allies,
+            _ => allies,
+        }
+    }
+
+    /// Fits the model to situations and whether the Allies went on to win the round.
+    pub fn fit(samples: &[(RoundSituation, bool)]) -> Self {
+        let mut weights = [0f32; 3];
+
+        if samples.is_empty() {
+            return Self::from_weights(weights);
+        }
+
+        for _ in 0..FIT_ITERATIONS {
+            let mut gradient = [0f32; 3];
+
+            for (situation, allies_won) in samples {
+                let features = situation.features();
+                let predicted =
+                    Self::from_weights(weights).win_probability(&Team::Allies, situation);
+                let error = predicted - if *allies_won { 1. } else { 0. };
+
+                for (gradient, feature) in gradient.iter_mut().zip(features) {
+                    *gradient += error * feature;
+                }
+            }
+
+            for (weight, gradient) in weights.iter_mut().zip(gradient) {
+                *weight -= FIT_LEARNING_RATE * gradient / samples.len() as f32;
+            }
+        }
+
+        Self::from_weights(weights)
+    }
+
+    fn from_weights([intercept, alive_difference, late_alive_difference]: [f32; 3]) -> Self {
+        Self {
+            intercept,
+            alive_difference,
+            late_alive_difference,
+        }
+    }
+}
+
+/// Change in a team's chance to win the round caused by a kill.
+#[derive(Debug)]
+pub struct KillImpact<'a> {
+    pub kill: &'a Kill,
+
+    /// Position of the kill in the kill feed.
+    pub(crate) kill_index: usize,
+
+    /// Side the killer played on when they made the kill.
+    pub team: Team,
+
+    /// Chance the killer's team wins the round, just before and just after the kill.
+    pub before: f32,
+    pub after: f32,
+}
+
+impl KillImpact<'_> {
+    /// Returns how much the kill added to the killer's team's chance to win the round.
+    pub fn delta(&self) -> f32 {
+        self.after - self.before
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the change in win probability of each kill between opponents during a round.
+    pub fn kill_impacts(&self, model: &WinModel) -> Vec<KillImpact<'_>> {
+        self.kill_situations()
+            .into_iter()
+            .map(|(kill_index, team, situation)| {
+                let mut before = situation.clone();
+
+                // The victim was still alive just before the kill
+                match team {
+                    Team::Allies => before.axis_alive += 1,
+                    _ => before.allies_alive += 1,
+                }
+
+                KillImpact {
+                    kill: &self.kills[kill_index],
+                    kill_index,
+                    before: model.win_probability(&team, &before),
+                    after: model.win_probability(&team, &situation),
+                    team,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the win probability a player added with their kills, minus what they lost with
+    /// their deaths.
+    pub fn impact_rating(&self, player: &PlayerGlobalId, model: &WinModel) -> f32 {
+        self.impact_ratings(model)
+            .get(player)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the impact rating of every player with a kill or death between opponents, without
+    /// rating the kills again for each player.
+    pub fn impact_ratings(&self, model: &WinModel) -> HashMap<PlayerGlobalId, f32> {
+        let mut ratings = HashMap::new();
+
+        for impact in self.kill_impacts(model) {
+            if let Some(killer) = &impact.kill.killer {
+                *ratings.entry(killer.clone()).or_default() += impact.delta();
+            }
+
+            *ratings.entry(impact.kill.victim.clone()).or_default() -= impact.delta();
+        }
+
+        ratings
+    }
+
+    /// Returns the situation after each kill in a won round, and whether the Allies won it, to fit
+    /// a [WinModel].
+    pub fn win_model_samples(&self) -> Vec<(RoundSituation, bool)> {
+        self.kill_situations()
+            .into_iter()
+            .filter_map(|(kill_index, _, situation)| {
+                let kill = &self.kills[kill_index];
+                let round = self
+                    .rounds
+                    .iter()
+                    .find(|round| round.contains(&kill.time))?;
+
+                match round {
+                    Round::Completed {
+                        winner_stats: Some((winner, _)),
+                        ..
+                    } => Some((situation, *winner == Team::Allies)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the position in the kill feed of each kill between opponents, with the killer's side
+    /// and the situation right after it.
+    fn kill_situations(&self) -> Vec<(usize, Team, RoundSituation)> {
+        self.kills
+            .iter()
+            .enumerate()
+            .filter(|(_, kill)| kill.is_enemy_kill())
+            .filter_map(|(kill_index, kill)| {
+                let killer = self.find_player_by_id(kill.killer.as_ref()?)?;
+                let team = self.side_of(killer, &kill.time)?;
+                let round = self
+                    .rounds
+                    .iter()
+                    .find(|round| round.contains(&kill.time))?;
+
+                let alive = |side: Team| {
+                    self.players
+                        .iter()
+                        .filter(|player| self.side_of(player, &kill.time).as_ref() == Some(&side))
+                        .filter(|player| player.is_alive_at(&kill.time))
+                        .count() as u32
+                };
+
+                let situation = RoundSituation {
+                    allies_alive: alive(Team::Allies),
+                    axis_alive: alive(Team::Axis),
+                    elapsed: &kill.time - round.start_time(),
+                };
+
+                Some((kill_index, team, situation))
+            })
+            .collect()
+    }
+}
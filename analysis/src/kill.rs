@@ -1,35 +1,297 @@
-use crate::{AnalyzerEvent, AnalyzerState, mortality::MortalityState, time::GameTime};
-use dod::{RoundState, UserMessage, Weapon};
+use crate::{
+    AnalyzerEvent, AnalyzerState, PlayerGlobalId, Round, TeamkillPolicy, WinModel,
+    mortality::MortalityState, time::GameTime,
+};
+use dod::{DeathMsg, RoundState, UserMessage, Weapon};
+use std::collections::HashMap;
+
+/// An entry in the kill feed.
+#[derive(Debug)]
+pub struct Kill {
+    pub time: GameTime,
+
+    /// Player credited with the kill, or [None] if the victim died on their own.
+    pub killer: Option<PlayerGlobalId>,
+
+    pub victim: PlayerGlobalId,
+    pub weapon: Weapon,
+
+    /// True if the killer and victim were on the same team.
+    pub is_teamkill: bool,
+
+    pub cause: KillCause,
+}
+
+/// What caused a death in the kill feed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KillCause {
+    /// A player killed the victim with a weapon.
+    Player,
+
+    /// The victim killed themselves with their own weapon, e.g. a grenade.
+    Suicide,
+
+    /// The map killed the victim, e.g. with fall damage or a `trigger_hurt`.
+    World,
+}
+
+impl Kill {
+    /// Returns true if a player killed an opponent, rather than a teammate or themselves, or the
+    /// map killing the victim.
+    pub fn is_enemy_kill(&self) -> bool {
+        self.cause == KillCause::Player && !self.is_teamkill
+    }
+}
+
+/// A kill with what it meant for its round, as the reports show it in the kill feed.
+#[derive(Debug)]
+pub struct KillFeedEntry<'a> {
+    pub kill: &'a Kill,
+
+    /// True if this was the first kill between opponents in its round.
+    pub is_first_blood: bool,
+
+    /// True if this was the last kill between opponents in a round won by a team.
+    pub is_round_ender: bool,
+
+    /// Chance to win the round the kill added to the killer's team, for kills between opponents
+    /// during a round, if a [WinModel] was given.
+    pub win_probability_delta: Option<f32>,
+}
+
+impl KillFeedEntry<'_> {
+    /// Returns the notes shown next to the kill, such as `First blood` or `Teamkill`.
+    pub fn notes(&self) -> Vec<&'static str> {
+        [
+            (self.is_first_blood, "First blood"),
+            (self.is_round_ender, "Round ender"),
+            (self.kill.is_teamkill, "Teamkill"),
+            (self.kill.cause == KillCause::Suicide, "Suicide"),
+        ]
+        .into_iter()
+        .filter_map(|(is_noted, note)| is_noted.then_some(note))
+        .collect()
+    }
+}
+
+impl KillCause {
+    /// Returns the cause of the death in the message.
+    ///
+    /// The world is sent as the killer, or the victim is sent as their own killer without a known
+    /// weapon. With a known weapon, the victim killed themselves.
+    pub(crate) fn of(death_msg: &DeathMsg) -> Self {
+        let is_self = death_msg.killer_client_index == death_msg.victim_client_index;
+
+        if death_msg.killer_client_index == 0
+            || (is_self && matches!(death_msg.weapon, Weapon::Unknown(_)))
+        {
+            Self::World
+        } else if is_self {
+            Self::Suicide
+        } else {
+            Self::Player
+        }
+    }
+}
+
+/// Returns the entity index of the player credited with the kill, which is the world entity when
+/// the map caused the death.
+pub(crate) fn killer_index(death_msg: &DeathMsg) -> u8 {
+    match KillCause::of(death_msg) {
+        KillCause::Player | KillCause::Suicide => death_msg.killer_client_index,
+        KillCause::World => 0,
+    }
+}
+
+impl AnalyzerState {
+    /// Returns every kill in the kill feed with what it meant for its round, rating the kills with
+    /// the model if one is given.
+    pub fn kill_feed(&self, model: Option<&WinModel>) -> Vec<KillFeedEntry<'_>> {
+        let first_bloods = self.first_blood_indexes();
+        let round_enders = self.round_ender_indexes();
+
+        let win_probability_deltas = model
+            .map(|model| {
+                self.kill_impacts(model)
+                    .iter()
+                    .map(|impact| (impact.kill_index, impact.delta()))
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        self.kills
+            .iter()
+            .enumerate()
+            .map(|(index, kill)| KillFeedEntry {
+                kill,
+                is_first_blood: first_bloods.contains(&index),
+                is_round_ender: round_enders.contains(&index),
+                win_probability_delta: win_probability_deltas.get(&index).copied(),
+            })
+            .collect()
+    }
+
+    /// Returns the first kill of each round, excluding teamkills and suicides.
+    pub fn first_bloods(&self) -> Vec<&Kill> {
+        self.first_blood_indexes()
+            .into_iter()
+            .map(|index| &self.kills[index])
+            .collect()
+    }
+
+    /// Returns the last kill of each round that was won by a team, excluding teamkills and
+    /// suicides.
+    pub fn round_enders(&self) -> Vec<&Kill> {
+        self.round_ender_indexes()
+            .into_iter()
+            .map(|index| &self.kills[index])
+            .collect()
+    }
+
+    /// Returns the position in the kill feed of the [AnalyzerState::first_bloods].
+    fn first_blood_indexes(&self) -> Vec<usize> {
+        self.rounds
+            .iter()
+            .filter_map(|round| self.enemy_kills_in(round).next())
+            .collect()
+    }
+
+    /// Returns the position in the kill feed of the [AnalyzerState::round_enders].
+    fn round_ender_indexes(&self) -> Vec<usize> {
+        self.rounds
+            .iter()
+            .filter(|round| {
+                matches!(
+                    round,
+                    Round::Completed {
+                        winner_stats: Some(_),
+                        ..
+                    }
+                )
+            })
+            .filter_map(|round| self.enemy_kills_in(round).last())
+            .collect()
+    }
+
+    /// Returns the number of times the player was killed by the map instead of a player.
+    pub fn environment_deaths(&self, player: &PlayerGlobalId) -> usize {
+        self.kills
+            .iter()
+            .filter(|kill| kill.cause == KillCause::World && kill.victim == *player)
+            .count()
+    }
+
+    /// Returns the number of times the player killed themselves with their own weapon.
+    pub fn suicides(&self, player: &PlayerGlobalId) -> usize {
+        self.kills
+            .iter()
+            .filter(|kill| kill.cause == KillCause::Suicide && kill.victim == *player)
+            .count()
+    }
+
+    /// Returns the number of rounds in which the player got the first kill.
+    pub fn first_bloods_by(&self, player: &PlayerGlobalId) -> usize {
+        self.first_bloods()
+            .into_iter()
+            .filter(|kill| kill.killer.as_ref() == Some(player))
+            .count()
+    }
+
+    /// Returns the number of won rounds in which the player got the last kill.
+    pub fn round_enders_by(&self, player: &PlayerGlobalId) -> usize {
+        self.round_enders()
+            .into_iter()
+            .filter(|kill| kill.killer.as_ref() == Some(player))
+            .count()
+    }
+
+    /// Returns the position in the kill feed of each kill between opponents during the round.
+    fn enemy_kills_in<'a>(&'a self, round: &'a Round) -> impl Iterator<Item = usize> + 'a {
+        self.kills
+            .iter()
+            .enumerate()
+            .filter(move |(_, kill)| kill.is_enemy_kill() && round.contains(&kill.time))
+            .map(|(index, _)| index)
+    }
+}
+
+pub fn use_kill_feed_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    if let AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) = event {
+        let cause = KillCause::of(death_msg);
+
+        let killer = state.find_player_by_entity_index(killer_index(death_msg));
+
+        let victim = state.find_player_by_entity_index(death_msg.victim_client_index);
+
+        if let Some(victim) = victim {
+            let is_teamkill = killer.is_some_and(|killer| {
+                killer != victim && killer.team.is_some() && killer.team == victim.team
+            });
+
+            let kill = Kill {
+                time: state.current_time.clone(),
+                killer: killer.map(|killer| killer.id.clone()),
+                victim: victim.id.clone(),
+                weapon: death_msg.weapon.clone(),
+                is_teamkill,
+                cause,
+            };
+
+            state.kills.push(kill);
+        }
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct KillStreak {
     pub kills: Vec<(GameTime, Weapon)>,
 }
 
-pub fn use_kill_streak_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+pub fn use_kill_streak_updates(
+    teamkill_policy: &TeamkillPolicy,
+    state: &mut AnalyzerState,
+    event: &AnalyzerEvent,
+) {
     if let AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) = event {
         let current_time = state.current_time.clone();
 
-        let killer = state.find_player_by_client_index(death_msg.killer_client_index - 1);
-        let victim = state.find_player_by_client_index(death_msg.victim_client_index - 1);
-
-        let is_teamkill = match (killer, victim) {
-            (Some(fst), Some(snd)) => fst.team == snd.team,
-            _ => false,
-        };
-
-        let victim = state.find_player_by_client_index_mut(death_msg.victim_client_index - 1);
+        let victim = state.find_player_by_entity_index_mut(death_msg.victim_client_index);
 
         if let Some(victim) = victim {
             // End the victim's current streak by adding a new record
             victim.kill_streaks.push(KillStreak::default());
         }
 
-        if is_teamkill {
+        // Suicides and deaths to the world end the streak of the victim, but nobody killed them
+        if KillCause::of(death_msg) != KillCause::Player {
             return;
         }
 
-        let killer = state.find_player_by_client_index_mut(death_msg.killer_client_index - 1);
+        let killer = state.find_player_by_entity_index(killer_index(death_msg));
+        let victim = state.find_player_by_entity_index(death_msg.victim_client_index);
+
+        let is_teamkill = match (killer, victim) {
+            (Some(fst), Some(snd)) => fst.team == snd.team,
+            _ => false,
+        };
+
+        let killer = state.find_player_by_entity_index_mut(killer_index(death_msg));
+
+        if is_teamkill {
+            match teamkill_policy {
+                TeamkillPolicy::Exclude => return,
+
+                TeamkillPolicy::Penalize => {
+                    if let Some(killer) = killer {
+                        killer.kill_streaks.push(KillStreak::default());
+                    }
+
+                    return;
+                }
+
+                TeamkillPolicy::Include => {}
+            }
+        }
 
         if let Some(killer) = killer {
             if killer.kill_streaks.is_empty() {
@@ -37,9 +299,9 @@ pub fn use_kill_streak_updates(state: &mut AnalyzerState, event: &AnalyzerEvent)
             }
 
             let streak = if killer.is_dead() && death_msg.weapon.is_grenade() {
-                let prev_streak_index = killer.kill_streaks.len() - 2;
+                let prev_streak_index = killer.kill_streaks.len().checked_sub(2);
 
-                killer.kill_streaks.get_mut(prev_streak_index)
+                prev_streak_index.and_then(|i| killer.kill_streaks.get_mut(i))
             } else {
                 killer.kill_streaks.iter_mut().last()
             };
@@ -58,15 +320,21 @@ pub fn use_kill_streak_updates(state: &mut AnalyzerState, event: &AnalyzerEvent)
 
 pub fn use_weapon_breakdown_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
     if let AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) = event {
-        let killer = state.find_player_by_client_index(death_msg.killer_client_index - 1);
-        let victim = state.find_player_by_client_index(death_msg.victim_client_index - 1);
+        if KillCause::of(death_msg) != KillCause::Player {
+            return;
+        }
+
+        let killer = state.find_player_by_entity_index(killer_index(death_msg));
+        let victim = state.find_player_by_entity_index(death_msg.victim_client_index);
 
         let is_teamkill = match (killer, victim) {
             (Some(fst), Some(snd)) => fst.team == snd.team,
             _ => false,
         };
 
-        let killer = state.find_player_by_client_index_mut(death_msg.killer_client_index - 1);
+        let is_killed_by_enemy = killer.is_some() && victim.is_some() && !is_teamkill;
+
+        let killer = state.find_player_by_entity_index_mut(killer_index(death_msg));
 
         if let Some(killer) = killer {
             let (kills, teamkills) = killer
@@ -80,5 +348,143 @@ pub fn use_weapon_breakdown_updates(state: &mut AnalyzerState, event: &AnalyzerE
                 *kills += 1;
             }
         }
+
+        if is_killed_by_enemy
+            && let Some(victim) =
+                state.find_player_by_entity_index_mut(death_msg.victim_client_index)
+        {
+            *victim
+                .deaths_by_weapon
+                .entry(death_msg.weapon.clone())
+                .or_insert(0) += 1;
+        }
+    }
+}
+
+pub(crate) fn reset_kills(state: &mut AnalyzerState) {
+    state.kills.clear();
+
+    for player in state.players.iter_mut() {
+        player.kill_streaks.clear();
+        player.weapon_breakdown.clear();
+        player.deaths_by_weapon.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientSlot, fixtures::state_with_players};
+    use dod::{DeathMsg, Team};
+
+    fn handle_death(state: &mut AnalyzerState, killer: u8, victim: u8, weapon: Weapon) {
+        handle_death_with_policy(&TeamkillPolicy::default(), state, killer, victim, weapon);
+    }
+
+    fn handle_death_with_policy(
+        teamkill_policy: &TeamkillPolicy,
+        state: &mut AnalyzerState,
+        killer: u8,
+        victim: u8,
+        weapon: Weapon,
+    ) {
+        let event = AnalyzerEvent::UserMessage(UserMessage::DeathMsg(DeathMsg {
+            killer_client_index: killer,
+            victim_client_index: victim,
+            weapon,
+        }));
+
+        use_kill_feed_updates(state, &event);
+        use_kill_streak_updates(teamkill_policy, state, &event);
+        use_weapon_breakdown_updates(state, &event);
+    }
+
+    #[test]
+    fn entity_index_zero_is_the_world() {
+        assert_eq!(ClientSlot::from_entity_index(0u8), None);
+        assert_eq!(
+            ClientSlot::from_entity_index(1u8),
+            Some(ClientSlot::from(0))
+        );
+        assert_eq!(ClientSlot::from_entity_index(257u16), None);
+    }
+
+    #[test]
+    fn world_kills_have_no_killer() {
+        let mut state = state_with_players(&[Team::Allies, Team::Axis]);
+
+        handle_death(&mut state, 0, 1, Weapon::Unknown(0));
+
+        assert_eq!(state.kills.len(), 1);
+        assert_eq!(state.kills[0].killer, None);
+        assert_eq!(
+            state.kills[0].victim,
+            PlayerGlobalId("PLAYER_0".to_string())
+        );
+        assert_eq!(state.kills[0].cause, KillCause::World);
+        assert!(!state.kills[0].is_teamkill);
+
+        for player in &state.players {
+            assert!(player.weapon_breakdown.is_empty());
+            assert!(player.deaths_by_weapon.is_empty());
+        }
+    }
+
+    #[test]
+    fn self_kills_without_a_weapon_are_world_kills() {
+        let mut state = state_with_players(&[Team::Allies, Team::Axis]);
+
+        handle_death(&mut state, 2, 2, Weapon::Unknown(0));
+
+        assert_eq!(state.kills[0].killer, None);
+        assert_eq!(state.kills[0].cause, KillCause::World);
+        assert_eq!(state.environment_deaths(&state.players[1].id), 1);
+        assert_eq!(state.suicides(&state.players[1].id), 0);
+        assert!(state.players[1].weapon_breakdown.is_empty());
+    }
+
+    #[test]
+    fn suicides_are_not_counted_as_kills() {
+        let mut state = state_with_players(&[Team::Allies, Team::Axis]);
+
+        handle_death(&mut state, 1, 1, Weapon::Mk2Grenade);
+
+        assert_eq!(state.kills.len(), 1);
+        assert_eq!(state.kills[0].killer.as_ref(), Some(&state.kills[0].victim));
+        assert_eq!(state.kills[0].cause, KillCause::Suicide);
+        assert!(!state.kills[0].is_teamkill);
+        assert_eq!(state.suicides(&state.players[0].id), 1);
+        assert_eq!(state.environment_deaths(&state.players[0].id), 0);
+
+        for teamkill_policy in [
+            TeamkillPolicy::Exclude,
+            TeamkillPolicy::Penalize,
+            TeamkillPolicy::Include,
+        ] {
+            let mut state = state_with_players(&[Team::Allies, Team::Axis]);
+
+            handle_death_with_policy(&teamkill_policy, &mut state, 1, 1, Weapon::Mk2Grenade);
+
+            let player = &state.players[0];
+
+            assert!(
+                player
+                    .kill_streaks
+                    .iter()
+                    .all(|streak| streak.kills.is_empty()),
+                "{teamkill_policy:?}"
+            );
+            assert!(player.weapon_breakdown.is_empty(), "{teamkill_policy:?}");
+            assert!(player.deaths_by_weapon.is_empty(), "{teamkill_policy:?}");
+        }
+    }
+
+    #[test]
+    fn deaths_in_empty_slots_are_ignored() {
+        let mut state = state_with_players(&[Team::Allies, Team::Axis]);
+
+        handle_death(&mut state, 255, 32, Weapon::Garand);
+
+        assert!(state.kills.is_empty());
     }
 }
@@ -0,0 +1,64 @@
+use crate::{AnalyzerEvent, AnalyzerState};
+use dod::UserMessage;
+use std::time::Duration;
+
+/// How late kill messages reached the demo, compared between the server clock and the recording
+/// clock.
+///
+/// The gap between the two clocks stays the same when every message arrives on time, so each kill's
+/// delay is measured against the quickest one. POV demos usually lag by a fraction of a second,
+/// while HLTV demos lag by several seconds when the proxy delays the game.
+#[derive(Debug)]
+pub struct KillLatency {
+    /// True if the demo was recorded by an HLTV client instead of a player.
+    pub recorded_by_hltv: bool,
+
+    /// Delay of each kill message behind the quickest one, in the order of the kills.
+    pub delays: Vec<Duration>,
+
+    pub median_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl KillLatency {
+    /// Returns the delays of the kills, or [None] if there were no kills to measure.
+    pub fn new(state: &AnalyzerState) -> Option<Self> {
+        let offsets = state
+            .kills
+            .iter()
+            .map(|kill| {
+                kill.time.real_offset.as_secs_f64() - kill.time.viewdemo_offset.as_secs_f64()
+            })
+            .collect::<Vec<_>>();
+
+        let quickest = offsets.iter().copied().reduce(f64::min)?;
+
+        let delays = offsets
+            .iter()
+            .map(|offset| Duration::from_secs_f64(offset - quickest))
+            .collect::<Vec<_>>();
+
+        let mut sorted_delays = delays.clone();
+        sorted_delays.sort();
+
+        let median_delay = match sorted_delays.len() {
+            len if len % 2 == 0 => (sorted_delays[len / 2 - 1] + sorted_delays[len / 2]) / 2,
+            len => sorted_delays[len / 2],
+        };
+
+        Some(Self {
+            recorded_by_hltv: state.recorded_by_hltv,
+            max_delay: sorted_delays.last().copied().unwrap_or_default(),
+            median_delay,
+            delays,
+        })
+    }
+}
+
+/// Detects demos recorded by an HLTV client, since the server only sends the `HLTV` message to
+/// proxies.
+pub fn use_hltv_detection_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    if let AnalyzerEvent::UserMessage(UserMessage::Hltv(_)) = event {
+        state.recorded_by_hltv = true;
+    }
+}
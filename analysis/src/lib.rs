@@ -1,33 +1,102 @@
+mod aggregate;
+mod ammo;
+mod anomaly;
+mod assist;
+mod capture;
+mod chat;
+mod clan;
 mod clan_match;
+mod config;
+mod consistency;
+mod context;
+mod danger;
+mod distance;
+mod economy;
+#[cfg(test)]
+mod fixtures;
+mod hold;
+mod identity;
+mod impact;
 mod kill;
+mod latency;
+mod malformed;
+mod matchup;
 mod mortality;
+mod movement;
+mod narrative;
+mod pace;
+mod participation;
 mod player;
+mod position;
+mod query;
 mod round;
+mod score_check;
 mod scoreboard;
+mod shots;
+mod suspicious;
+mod swap;
+mod teamkills;
 mod time;
+mod time_left;
+mod ttk;
+mod warmup;
+mod watch;
 
 use crate::{
-    clan_match::{ClanMatchDetection, use_clan_match_detection_updates},
-    kill::{use_kill_streak_updates, use_weapon_breakdown_updates},
-    mortality::with_mortality_detection,
-    player::use_player_updates,
-    round::use_rounds_updates,
-    scoreboard::{TeamScores, use_scoreboard_updates, use_team_score_updates},
-    time::{GameTime, use_timing_updates},
+    ammo::AmmoTracking, anomaly::AnomalyDetection, assist::AssistDetection,
+    clan_match::ClanMatchDetection, context::AnalysisContext, distance::PendingKill,
+    swap::SideSwitch,
 };
 use dem::{
     open_demo_from_bytes,
-    types::{Demo, EngineMessage, Frame, FrameData, MessageData, NetMessage},
+    types::{Demo, DirectoryEntry, EngineMessage, Frame, FrameData, MessageData, NetMessage},
 };
-use dod::UserMessage;
+use dod::{MessageKind, UserMessage};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::time::Duration;
 
 pub use crate::{
+    aggregate::{AggregatedPlayer, MatchAggregation},
+    ammo::AmmoUsage,
+    anomaly::{ServerAnomaly, ServerAnomalyKind},
+    assist::{ASSIST_WINDOW, Assist},
+    capture::Capture,
+    chat::{ChatMessage, RoundChat},
+    clan::{Clan, ClanRoster},
+    config::{AnalysisConfig, ScoreFormula, TeamkillPolicy},
+    consistency::Consistency,
+    danger::{DangerZone, DeathPosition},
+    distance::{KillDistance, WeaponRange},
+    economy::{DeathEconomy, TRADE_WINDOW},
+    hold::PositionHold,
+    identity::IdentityMap,
+    impact::{KillImpact, RoundSituation, WinModel},
+    kill::{Kill, KillCause, KillFeedEntry},
+    latency::KillLatency,
+    malformed::MalformedMessage,
+    matchup::{KillMatrix, Matchup},
     mortality::MortalityState,
-    player::{Connection, Player, PlayerGlobalId, SteamId},
-    round::Round,
+    movement::LifeMovement,
+    narrative::{Narrative, NarrativeTemplates},
+    pace::{MatchPace, RoundPace},
+    participation::KillParticipation,
+    player::{
+        CategoryBreakdown, ClientSlot, Connection, Player, PlayerGlobalId, SteamId, UserInfoUpdate,
+    },
+    position::PositionSample,
+    query::TimedEvent,
+    round::{Round, RoundPlayerStats},
+    score_check::ScoreMismatch,
+    scoreboard::{ScoreboardSnapshot, TeamScoreChange, TeamScores, rank},
+    suspicious::{SuspiciousEvent, SuspiciousEventKind},
+    teamkills::Teamkill,
+    time::{Clock, GameTime},
+    ttk::WeaponTiming,
+    warmup::WarmupStats,
+    watch::{Highlight, HighlightKind, PlayerToWatch},
 };
-pub use dod::Team;
+pub use dod::{Codepage, Team, Version, Weapon, WeaponCategory, WeaponStats};
 
 #[derive(Debug)]
 pub enum AnalyzerEvent<'a> {
@@ -37,10 +106,17 @@ pub enum AnalyzerEvent<'a> {
     Frame(&'a Frame),
     EngineMessage(&'a EngineMessage),
     UserMessage(UserMessage),
+
+    /// A user message the mod sends whose payload could not be parsed, with its name.
+    MalformedUserMessage(String),
+
+    /// The demo was stopped and recorded again, and the frames of the next playback segment
+    /// follow.
+    SegmentStart,
 }
 
 impl<'a> AnalyzerEvent<'a> {
-    fn from_dem(frame: &'a Frame) -> Vec<Self> {
+    fn from_dem(frame: &'a Frame, version: &Version, codepage: Codepage) -> Vec<Self> {
         let mut events: Vec<Self> = vec![];
 
         events.push(AnalyzerEvent::Frame(frame));
@@ -53,9 +129,23 @@ impl<'a> AnalyzerEvent<'a> {
             .filter_map(|net_msg| match net_msg {
                 NetMessage::EngineMessage(engine_msg) => Some(Self::EngineMessage(engine_msg)),
                 NetMessage::UserMessage(user_msg) => {
-                    UserMessage::new(&user_msg.name, &user_msg.data)
-                        .ok()
-                        .map(Self::UserMessage)
+                    match UserMessage::new_for_version(
+                        version,
+                        &user_msg.name,
+                        &user_msg.data,
+                        codepage,
+                    ) {
+                        Ok(message) => Some(Self::UserMessage(message)),
+
+                        // Messages the mod does not send are expected on modified servers
+                        Err(dod::Error::UnknownMessage) => None,
+
+                        Err(dod::Error::ParserError) => Some(Self::MalformedUserMessage(
+                            dod::decode_string(&user_msg.name, codepage)
+                                .trim_end_matches('\x00')
+                                .to_string(),
+                        )),
+                    }
                 }
             })
             .for_each(|event| events.push(event))
@@ -67,27 +157,134 @@ impl<'a> AnalyzerEvent<'a> {
 
 #[derive(Debug, Default)]
 pub struct AnalyzerState {
+    ammo_tracking: AmmoTracking,
+    anomaly_detection: AnomalyDetection,
+    assist_detection: AssistDetection,
     clan_match_detection: ClanMatchDetection,
     current_time: GameTime,
+    match_start_candidates: Vec<GameTime>,
+    match_start_time: Option<GameTime>,
+    pending_kill: Option<PendingKill>,
+    pending_side_switches: Vec<SideSwitch>,
+    recorded_by_hltv: bool,
+    round_scoreboard: HashMap<PlayerGlobalId, [i32; 4]>,
+    requested_match_start: Option<GameTime>,
+    segment_offset: Duration,
+    segment_started: bool,
+    time_left_samples: Vec<(GameTime, Duration)>,
+    warmup: WarmupStats,
+
+    /// Probable assists, only inferred when enabled in the [AnalysisConfig].
+    pub assists: Vec<Assist>,
+
+    pub captures: Vec<Capture>,
+    pub chat: Vec<ChatMessage>,
+    pub death_positions: Vec<DeathPosition>,
+    pub kill_distances: Vec<KillDistance>,
+    pub kills: Vec<Kill>,
+
+    /// Parts of the demo that could not be read, over the whole demo.
+    pub malformed_messages: Vec<MalformedMessage>,
 
     pub players: Vec<Player>,
     pub rounds: Vec<Round>,
+    pub scoreboard_history: Vec<ScoreboardSnapshot>,
+
+    /// Stretches of the recording where the server lagged, over the whole demo.
+    pub server_anomalies: Vec<ServerAnomaly>,
+
     pub team_scores: TeamScores,
+    pub team_swaps: Vec<GameTime>,
+    pub teamkills: Vec<Teamkill>,
+    pub user_info_history: Vec<UserInfoUpdate>,
 }
 
 pub struct DemoInfo {
     /// Version of the demo protocol used to encode the demo.
     pub demo_protocol: i32,
 
+    /// Version of the mod estimated from the network protocol.
+    pub game_version: Version,
+
     /// Name of the map the demo was recorded on.
     pub map_name: String,
 
     /// Version of the network protocol used during the game.
     pub network_protocol: i32,
+
+    /// Names of the messages registered by the server that the mod does not register, such as
+    /// those added by community forks and server plugins.
+    pub extra_messages: Vec<String>,
+
+    /// Entries of the demo directory, in the order they were recorded.
+    pub directory_entries: Vec<DirectoryEntryInfo>,
+}
+
+/// An entry of the demo directory, which holds the frames of a part of the recording.
+pub struct DirectoryEntryInfo {
+    /// Name of the entry, e.g. `LOADING` or `Playback`.
+    pub name: String,
+
+    /// True if the entry holds gameplay, instead of the frames sent while the client connected.
+    pub is_playback: bool,
+
+    pub frame_count: usize,
+
+    /// Time between the first and the last frame of the entry, on the recording clock.
+    pub duration: Duration,
+}
+
+impl From<&DirectoryEntry> for DirectoryEntryInfo {
+    fn from(value: &DirectoryEntry) -> Self {
+        let first_time = value.frames.first().map(|frame| frame.time).unwrap_or(0.);
+        let last_time = value.frames.last().map(|frame| frame.time).unwrap_or(0.);
+
+        Self {
+            name: String::from_utf8_lossy(&value.description)
+                .trim_end_matches('\x00')
+                .to_string(),
+            is_playback: value.type_ != 0,
+            frame_count: value.frames.len(),
+            duration: Duration::try_from_secs_f32(last_time - first_time).unwrap_or_default(),
+        }
+    }
 }
 
-impl From<Demo> for DemoInfo {
-    fn from(value: Demo) -> Self {
+impl Display for DirectoryEntryInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} frames, {:.1} s)",
+            self.name,
+            self.frame_count,
+            self.duration.as_secs_f32()
+        )
+    }
+}
+
+impl DemoInfo {
+    /// Returns the directory entries that hold gameplay.
+    pub fn playback_segments(&self) -> impl Iterator<Item = &DirectoryEntryInfo> {
+        self.directory_entries
+            .iter()
+            .filter(|entry| entry.is_playback)
+    }
+
+    /// Returns true if the gameplay was recorded in more than one segment, e.g. when the demo was
+    /// stopped and recorded again from the console.
+    pub fn has_multiple_segments(&self) -> bool {
+        self.playback_segments().count() > 1
+    }
+
+    /// Returns true if the server registered messages that the mod does not, meaning it ran a
+    /// fork of the mod or plugins that talk to clients.
+    pub fn is_modified_server(&self) -> bool {
+        !self.extra_messages.is_empty()
+    }
+}
+
+impl From<&Demo> for DemoInfo {
+    fn from(value: &Demo) -> Self {
         let map_name = value
             .header
             .map_name
@@ -96,75 +293,334 @@ impl From<Demo> for DemoInfo {
             .unwrap()
             .to_string();
 
+        let extra_messages = value
+            .directory
+            .entries
+            .iter()
+            .flat_map(|entry| entry.frames.iter())
+            .filter_map(|frame| match &frame.frame_data {
+                FrameData::NetworkMessage(box_type) => Some(&box_type.1.messages),
+                _ => None,
+            })
+            .flat_map(|messages| match messages {
+                MessageData::Parsed(msgs) => msgs.iter(),
+                _ => [].iter(),
+            })
+            .filter_map(|net_msg| match net_msg {
+                NetMessage::EngineMessage(EngineMessage::SvcNewUserMsg(new_user_msg)) => {
+                    new_user_msg.name.to_str().ok()
+                }
+                _ => None,
+            })
+            .map(|name| name.trim_end_matches('\x00'))
+            .filter(|name| MessageKind::from_name(name).is_none())
+            .fold(vec![], |mut acc: Vec<String>, name| {
+                // Messages are registered again on every level change
+                if !acc.iter().any(|other| other == name) {
+                    acc.push(name.to_string());
+                }
+
+                acc
+            });
+
         Self {
             demo_protocol: value.header.demo_protocol,
+            game_version: Version::from_network_protocol(value.header.network_protocol),
             map_name,
             network_protocol: value.header.network_protocol,
+            extra_messages,
+            directory_entries: value
+                .directory
+                .entries
+                .iter()
+                .map(DirectoryEntryInfo::from)
+                .collect(),
         }
     }
 }
 
 pub struct Analysis {
+    pub config: AnalysisConfig,
     pub demo_info: DemoInfo,
     pub state: AnalyzerState,
 }
 
 impl Analysis {
-    fn new(demo_info: DemoInfo, state: AnalyzerState) -> Self {
-        Self { demo_info, state }
+    fn new(config: AnalysisConfig, demo_info: DemoInfo, state: AnalyzerState) -> Self {
+        Self {
+            config,
+            demo_info,
+            state,
+        }
     }
-}
 
-impl<'a> From<&'a [u8]> for Analysis {
-    fn from(value: &'a [u8]) -> Self {
-        let demo = open_demo_from_bytes(value).expect("Could not parse the file");
+    /// Analyzes the bytes of a demo file with the given options.
+    ///
+    /// Demos that were stopped and recorded again are analyzed as a whole, with the recording clock
+    /// carried over from one playback segment to the next.
+    pub fn with_config(bytes: &[u8], config: AnalysisConfig) -> Self {
+        let demo = open_demo_from_bytes(bytes).expect("Could not parse the file");
+        let state = Self::run(&demo, &playback_parts(&demo.directory.entries), &config);
+
+        Analysis::new(config, DemoInfo::from(&demo), state)
+    }
+
+    /// Analyzes each map played in a demo file separately, with the given options, so players and
+    /// rounds of different maps are not mixed, e.g. in long public server demos recorded by HLTV.
+    ///
+    /// Servers send their info and register their user messages again after every map change, so a
+    /// map starts at the first of these that follows gameplay. Each map is named after the map file
+    /// in its server info. Without one, the first map is named after the demo header, and the
+    /// others are named [UNKNOWN_MAP].
+    pub fn maps_with_config(bytes: &[u8], config: AnalysisConfig) -> Vec<Self> {
+        let demo = open_demo_from_bytes(bytes).expect("Could not parse the file");
+        let mut maps: Vec<Vec<(bool, &[Frame])>> = vec![vec![]];
+        let mut has_gameplay = false;
+
+        for entry in &demo.directory.entries {
+            let is_playback = entry.type_ != 0;
+            let mut start = 0;
+
+            for (i, frame) in entry.frames.iter().enumerate() {
+                let is_map_change = net_messages(frame).any(|net_msg| {
+                    matches!(
+                        net_msg,
+                        NetMessage::EngineMessage(
+                            EngineMessage::SvcServerInfo(_) | EngineMessage::SvcNewUserMsg(_)
+                        )
+                    )
+                });
+
+                if is_map_change && has_gameplay {
+                    if let Some(parts) = maps.last_mut() {
+                        parts.push((is_playback, &entry.frames[start..i]));
+                    }
+
+                    maps.push(vec![]);
+                    start = i;
+                    has_gameplay = false;
+                } else if !is_map_change {
+                    has_gameplay |= net_messages(frame)
+                        .any(|net_msg| matches!(net_msg, NetMessage::UserMessage(_)));
+                }
+            }
+
+            if let Some(parts) = maps.last_mut() {
+                parts.push((is_playback, &entry.frames[start..]));
+            }
+        }
+
+        maps.iter()
+            .enumerate()
+            .map(|(i, parts)| {
+                let state = Self::run(&demo, parts, &config);
+                let mut demo_info = DemoInfo::from(&demo);
+
+                match server_map_name(parts) {
+                    Some(map_name) => demo_info.map_name = map_name,
+                    None if i > 0 => demo_info.map_name = UNKNOWN_MAP.to_string(),
+                    None => {}
+                }
+
+                Analysis::new(config.clone(), demo_info, state)
+            })
+            .collect()
+    }
+
+    /// Analyzes each playback segment of a demo file separately, with the given options.
+    ///
+    /// Entries recorded before a playback segment, such as the frames sent while the client
+    /// connected, are analyzed with it.
+    pub fn segments_with_config(bytes: &[u8], config: AnalysisConfig) -> Vec<Self> {
+        let demo = open_demo_from_bytes(bytes).expect("Could not parse the file");
+        let entries = &demo.directory.entries;
+        let mut bounds: Vec<(usize, usize)> = vec![];
+        let mut start = 0;
+
+        for (i, entry) in entries.iter().enumerate() {
+            if entry.type_ != 0 {
+                bounds.push((start, i + 1));
+                start = i + 1;
+            }
+        }
+
+        // Trailing entries without gameplay belong to the last segment
+        match bounds.last_mut() {
+            Some((_, end)) => *end = entries.len(),
+            None => bounds.push((0, entries.len())),
+        }
+
+        bounds
+            .into_iter()
+            .map(|(start, end)| {
+                let entries = &entries[start..end];
+                let state = Self::run(&demo, &playback_parts(entries), &config);
+                let mut demo_info = DemoInfo::from(&demo);
+
+                demo_info.directory_entries =
+                    entries.iter().map(DirectoryEntryInfo::from).collect();
+
+                Analysis::new(config.clone(), demo_info, state)
+            })
+            .collect()
+    }
+
+    /// Analyzes the frames of directory entries, each given with whether the entry holds gameplay.
+    fn run(demo: &Demo, parts: &[(bool, &[Frame])], config: &AnalysisConfig) -> AnalyzerState {
+        let version = Version::from_network_protocol(demo.header.network_protocol);
+        let mut has_playback = false;
 
         let events = vec![AnalyzerEvent::Initialization]
             .into_iter()
-            .chain(
-                demo.directory
-                    .entries
-                    .iter()
-                    .flat_map(|entry| entry.frames.iter())
-                    .flat_map(AnalyzerEvent::from_dem),
-            )
+            .chain(parts.iter().flat_map(|(is_playback, frames)| {
+                let is_new_segment = *is_playback && has_playback;
+                has_playback |= is_playback;
+
+                is_new_segment
+                    .then_some(AnalyzerEvent::SegmentStart)
+                    .into_iter()
+                    .chain(frames.iter().flat_map(|frame| {
+                        AnalyzerEvent::from_dem(frame, &version, config.codepage)
+                    }))
+            }))
             .chain(vec![AnalyzerEvent::Finalization]);
 
-        let state = events.fold(AnalyzerState::default(), |mut state, ref event| {
-            use_timing_updates(&mut state, event);
-            use_player_updates(&mut state, event);
-            with_mortality_detection(&mut state, event);
-            use_scoreboard_updates(&mut state, event);
-            use_kill_streak_updates(&mut state, event);
-            use_weapon_breakdown_updates(&mut state, event);
-            use_team_score_updates(&mut state, event);
-            use_rounds_updates(&mut state, event);
-            use_clan_match_detection_updates(Duration::from_secs(10), &mut state, event);
+        let mut context = AnalysisContext::new(config);
+
+        for event in events {
+            context.handle(&event);
+        }
+
+        context.into_state()
+    }
+}
+
+/// Name given to maps that were played after the first one in a demo when the server info sent
+/// at the map change was not recorded, see [Analysis::maps_with_config].
+pub const UNKNOWN_MAP: &str = "unknown";
+
+/// Returns the frames of each directory entry, with whether the entry holds gameplay.
+fn playback_parts(entries: &[DirectoryEntry]) -> Vec<(bool, &[Frame])> {
+    entries
+        .iter()
+        .map(|entry| (entry.type_ != 0, entry.frames.as_slice()))
+        .collect()
+}
+
+/// Returns the messages sent in a frame, if it holds any.
+fn net_messages(frame: &Frame) -> impl Iterator<Item = &NetMessage> {
+    let messages = match &frame.frame_data {
+        FrameData::NetworkMessage(box_type) => match &box_type.1.messages {
+            MessageData::Parsed(msgs) => msgs.as_slice(),
+            _ => &[],
+        },
+        _ => &[],
+    };
+
+    messages.iter()
+}
 
-            state
-        });
+/// Returns the name of the map loaded by the first server info in the frames, e.g. `dod_anzio`
+/// for `maps/dod_anzio.bsp`.
+fn server_map_name(parts: &[(bool, &[Frame])]) -> Option<String> {
+    parts
+        .iter()
+        .flat_map(|(_, frames)| frames.iter())
+        .flat_map(net_messages)
+        .find_map(|net_msg| match net_msg {
+            NetMessage::EngineMessage(EngineMessage::SvcServerInfo(server_info)) => {
+                server_info.map_file_name.to_str().ok()
+            }
+            _ => None,
+        })
+        .map(|file_name| file_name.trim_end_matches('\x00'))
+        .map(|file_name| {
+            let file_name = file_name.strip_prefix("maps/").unwrap_or(file_name);
 
-        Analysis::new(demo.into(), state)
+            file_name
+                .strip_suffix(".bsp")
+                .unwrap_or(file_name)
+                .to_string()
+        })
+        .filter(|map_name| !map_name.is_empty())
+}
+
+impl<'a> From<&'a [u8]> for Analysis {
+    fn from(value: &'a [u8]) -> Self {
+        Analysis::with_config(value, AnalysisConfig::default())
     }
 }
 
 impl AnalyzerState {
-    fn find_player_by_client_index(&self, client_index: u8) -> Option<&Player> {
+    /// Asks for the results gathered so far to be cleared once every analyzer has seen the current
+    /// event, because the clan match went live at the [GameTime].
+    fn request_match_start(&mut self, start_time: GameTime) {
+        self.requested_match_start = Some(start_time);
+    }
+
+    /// Returns when the clan match went live, if one was detected.
+    pub fn match_start_time(&self) -> Option<&GameTime> {
+        self.match_start_time.as_ref()
+    }
+
+    /// Returns every moment the clan match detection saw the match go live, in order.
+    ///
+    /// Servers often restart the round several times before going live, e.g. with "live on three"
+    /// plugins. The last restart before a round was won is used as the match start, and the others
+    /// are kept so a wrong pick can be overridden with
+    /// [AnalysisConfig::match_start_secs](crate::AnalysisConfig::match_start_secs).
+    pub fn match_start_candidates(&self) -> &[GameTime] {
+        &self.match_start_candidates
+    }
+
+    /// Returns the players that first connected after the clan match went live and played in at
+    /// least one round.
+    ///
+    /// Players joining mid-match are a common source of ringer disputes in league play.
+    pub fn late_joins(&self) -> Vec<&Player> {
+        let Some(match_start_time) = &self.match_start_time else {
+            return vec![];
+        };
+
+        self.players
+            .iter()
+            .filter(|player| {
+                player.first_connected.viewdemo_offset > match_start_time.viewdemo_offset
+            })
+            .filter(|player| {
+                self.rounds
+                    .iter()
+                    .any(|round| round.is_participant(&player.id))
+            })
+            .collect()
+    }
+
+    fn find_player_by_slot(&self, slot: ClientSlot) -> Option<&Player> {
         self.players.iter().find(|player| match player.connection {
-            Connection::Connected { client_id } => client_id == client_index,
+            Connection::Connected { client_id } => client_id == slot,
             _ => false,
         })
     }
 
-    fn find_player_by_client_index_mut(&mut self, client_index: u8) -> Option<&mut Player> {
+    fn find_player_by_slot_mut(&mut self, slot: ClientSlot) -> Option<&mut Player> {
         self.players
             .iter_mut()
             .find(|player| match player.connection {
-                Connection::Connected { client_id } => client_id == client_index,
+                Connection::Connected { client_id } => client_id == slot,
                 _ => false,
             })
     }
 
+    /// Returns the player with the entity index, as sent in the mod's messages, or [None] for the
+    /// world entity.
+    fn find_player_by_entity_index(&self, entity_index: u8) -> Option<&Player> {
+        self.find_player_by_slot(ClientSlot::from_entity_index(entity_index)?)
+    }
+
+    fn find_player_by_entity_index_mut(&mut self, entity_index: u8) -> Option<&mut Player> {
+        self.find_player_by_slot_mut(ClientSlot::from_entity_index(entity_index)?)
+    }
+
     fn find_player_by_id(&self, id: &PlayerGlobalId) -> Option<&Player> {
         self.players.iter().find(|player| player.id == *id)
     }
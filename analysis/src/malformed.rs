@@ -0,0 +1,57 @@
+use crate::{AnalyzerEvent, AnalyzerState, time::GameTime};
+use dem::types::{FrameData, MessageData};
+use std::collections::BTreeMap;
+
+/// Part of the demo that could not be read, so the events it held are missing from the reports.
+#[derive(Debug)]
+pub struct MalformedMessage {
+    pub time: GameTime,
+
+    /// Name of the user message whose payload could not be parsed, or [None] when none of the
+    /// messages in a frame could be read.
+    pub name: Option<String>,
+}
+
+/// Records the messages that could not be parsed. The other messages in the same frame are still
+/// analyzed.
+pub fn use_malformed_message_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    let name = match event {
+        AnalyzerEvent::MalformedUserMessage(name) => Some(name.clone()),
+
+        AnalyzerEvent::Frame(frame) => match &frame.frame_data {
+            FrameData::NetworkMessage(box_type)
+                if matches!(box_type.1.messages, MessageData::Raw(_)) =>
+            {
+                None
+            }
+            _ => return,
+        },
+
+        _ => return,
+    };
+
+    state.malformed_messages.push(MalformedMessage {
+        time: state.current_time.clone(),
+        name,
+    });
+}
+
+impl MalformedMessage {
+    /// Returns the name of the message, or a description of the frame that could not be read.
+    pub fn description(&self) -> &str {
+        self.name.as_deref().unwrap_or("unreadable frame")
+    }
+}
+
+impl AnalyzerState {
+    /// Returns how many times each message could not be read, keyed by its
+    /// [MalformedMessage::description].
+    pub fn malformed_message_counts(&self) -> BTreeMap<&str, usize> {
+        self.malformed_messages
+            .iter()
+            .fold(BTreeMap::new(), |mut counts, message| {
+                *counts.entry(message.description()).or_insert(0) += 1;
+                counts
+            })
+    }
+}
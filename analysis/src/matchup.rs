@@ -0,0 +1,111 @@
+use crate::{AnalyzerState, Player, PlayerGlobalId};
+use dod::Weapon;
+use std::collections::BTreeMap;
+
+/// Kills made by one player on another, excluding teamkills.
+#[derive(Debug)]
+pub struct Matchup {
+    pub killer: PlayerGlobalId,
+    pub victim: PlayerGlobalId,
+
+    /// Number of kills made with each weapon.
+    pub weapons: BTreeMap<Weapon, u32>,
+}
+
+impl Matchup {
+    pub fn kills(&self) -> u32 {
+        self.weapons.values().sum()
+    }
+}
+
+/// Kills between every pair of players that fought each other, as a grid with a row for each
+/// killer and a column for each victim.
+#[derive(Debug)]
+pub struct KillMatrix<'a> {
+    /// Players that made or suffered a kill, in scoreboard order. Rows and columns follow this
+    /// order.
+    pub players: Vec<&'a Player>,
+
+    /// Kills made by the player of each row on the player of each column.
+    pub kills: Vec<Vec<u32>>,
+}
+
+impl KillMatrix<'_> {
+    /// Returns the kills made by the player of a row on every opponent.
+    pub fn total_kills(&self, row: usize) -> u32 {
+        self.kills[row].iter().sum()
+    }
+
+    /// Returns the deaths suffered by the player of a column to every opponent.
+    pub fn total_deaths(&self, column: usize) -> u32 {
+        self.kills.iter().map(|row| row[column]).sum()
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the kills between each pair of opponents as a grid, excluding teamkills.
+    pub fn kill_matrix(&self) -> KillMatrix<'_> {
+        let matchups = self.matchups();
+
+        let players = self
+            .players
+            .iter()
+            .filter(|player| {
+                matchups
+                    .iter()
+                    .any(|matchup| matchup.killer == player.id || matchup.victim == player.id)
+            })
+            .collect::<Vec<_>>();
+
+        let kills = players
+            .iter()
+            .map(|killer| {
+                players
+                    .iter()
+                    .map(|victim| {
+                        matchups
+                            .iter()
+                            .find(|matchup| {
+                                matchup.killer == killer.id && matchup.victim == victim.id
+                            })
+                            .map(|matchup| matchup.kills())
+                            .unwrap_or(0)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        KillMatrix { players, kills }
+    }
+
+    /// Returns the kills between each pair of opponents, in the order of their first kill.
+    pub fn matchups(&self) -> Vec<Matchup> {
+        let mut matchups: Vec<Matchup> = vec![];
+
+        for kill in self.kills.iter().filter(|kill| kill.is_enemy_kill()) {
+            let Some(killer) = &kill.killer else {
+                continue;
+            };
+
+            let matchup = match matchups
+                .iter_mut()
+                .position(|matchup| matchup.killer == *killer && matchup.victim == kill.victim)
+            {
+                Some(i) => &mut matchups[i],
+                None => {
+                    matchups.push(Matchup {
+                        killer: killer.clone(),
+                        victim: kill.victim.clone(),
+                        weapons: BTreeMap::new(),
+                    });
+
+                    matchups.last_mut().unwrap()
+                }
+            };
+
+            *matchup.weapons.entry(kill.weapon.clone()).or_insert(0) += 1;
+        }
+
+        matchups
+    }
+}
@@ -30,6 +30,15 @@ pub trait MortalityState {
             .unwrap_or(false)
     }
 
+    /// Returns true if the object was alive at the [GameTime].
+    fn is_alive_at(&self, time: &GameTime) -> bool {
+        self.mortality_changes()
+            .take_while(|change| change.time().viewdemo_offset <= time.viewdemo_offset)
+            .last()
+            .map(|change| matches!(change.mortality(), Mortality::Alive))
+            .unwrap_or(false)
+    }
+
     /// Returns the current [Mortality] state.
     fn mortality(&self) -> Option<&Mortality> {
         self.mortality_changes()
@@ -37,10 +46,11 @@ pub trait MortalityState {
             .map(|change| change.mortality())
     }
 
-    fn lifespans(&self) -> Vec<Duration> {
+    /// Returns the spawn and death times of every completed life.
+    fn lives(&self) -> Vec<(&GameTime, &GameTime)> {
         #[derive(Default)]
         struct State<'a> {
-            lifespans: Vec<Duration>,
+            lives: Vec<(&'a GameTime, &'a GameTime)>,
             spawn_time: Option<&'a GameTime>,
         }
 
@@ -55,7 +65,7 @@ pub trait MortalityState {
 
                     Mortality::Dead => {
                         if let Some(spawn_time) = state.spawn_time {
-                            state.lifespans.push(change.time() - spawn_time);
+                            state.lives.push((spawn_time, change.time()));
                             state.spawn_time = None;
                         };
                     }
@@ -63,7 +73,14 @@ pub trait MortalityState {
 
                 state
             })
-            .lifespans
+            .lives
+    }
+
+    fn lifespans(&self) -> Vec<Duration> {
+        self.lives()
+            .into_iter()
+            .map(|(spawn_time, death_time)| death_time - spawn_time)
+            .collect()
     }
 
     fn min_lifespan(&self) -> Duration {
@@ -138,19 +155,19 @@ pub fn with_mortality_detection(state: &mut AnalyzerState, event: &AnalyzerEvent
 
     let mortality_change = match event {
         AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) => {
-            Some((death_msg.victim_client_index - 1, Mortality::Dead))
+            Some((death_msg.victim_client_index, Mortality::Dead))
         }
 
         AnalyzerEvent::UserMessage(UserMessage::PStatus(p_status)) => {
-            Some((p_status.client_index - 1, Mortality::Alive))
+            Some((p_status.client_index, Mortality::Alive))
         }
 
         _ => None,
     };
 
-    mortality_change.and_then(|(client_index, mortality)| {
+    mortality_change.and_then(|(entity_index, mortality)| {
         let current_time = state.current_time.clone();
-        let player = state.find_player_by_client_index_mut(client_index)?;
+        let player = state.find_player_by_entity_index_mut(entity_index)?;
 
         if player.mortality() != Some(&mortality) {
             player.mortality_changed(MortalityChange(current_time, mortality));
@@ -159,3 +176,15 @@ pub fn with_mortality_detection(state: &mut AnalyzerState, event: &AnalyzerEvent
         Some(())
     });
 }
+
+/// Starts every player's lifespans over, alive as of now, since teams are respawned when the match
+/// goes live.
+pub(crate) fn reset_mortality(state: &mut AnalyzerState) {
+    for player in state.players.iter_mut() {
+        player.mortality.clear();
+        player.mortality.push(MortalityChange::new(
+            state.current_time.clone(),
+            Mortality::Alive,
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{Player, mortality::MortalityState, position::PositionSample, time::GameTime};
+use std::time::Duration;
+
+/// How far and how fast a player moved during one life.
+#[derive(Debug)]
+pub struct LifeMovement {
+    pub spawn_time: GameTime,
+    pub death_time: GameTime,
+
+    /// Total distance traveled in world units.
+    pub distance: f32,
+
+    /// Average speed in world units per second.
+    pub avg_speed: f32,
+
+    /// Highest horizontal speed in world units per second, and when it was reached.
+    pub max_speed: Option<(GameTime, f32)>,
+}
+
+impl LifeMovement {
+    /// Minimum time over which speed is measured, to smooth out jitter between samples.
+    const SPEED_WINDOW: Duration = Duration::from_millis(500);
+}
+
+impl Player {
+    /// Returns how the player moved in each of their lives.
+    ///
+    /// Only available for the player that recorded the demo.
+    pub fn movement_per_life(&self) -> Vec<LifeMovement> {
+        if self.positions.is_empty() {
+            return vec![];
+        }
+
+        self.lives()
+            .into_iter()
+            .filter_map(|(spawn_time, death_time)| {
+                let life = spawn_time.viewdemo_offset..=death_time.viewdemo_offset;
+
+                let samples = self
+                    .positions
+                    .iter()
+                    .filter(|sample| life.contains(&sample.time.viewdemo_offset))
+                    .collect::<Vec<_>>();
+
+                let (first, last) = (samples.first()?, samples.last()?);
+
+                let distance = samples
+                    .windows(2)
+                    .map(|pair| pair[1].distance_to(pair[0].origin))
+                    .sum::<f32>();
+
+                let elapsed = (&last.time - &first.time).as_secs_f32();
+
+                Some(LifeMovement {
+                    spawn_time: spawn_time.clone(),
+                    death_time: death_time.clone(),
+                    distance,
+                    avg_speed: if elapsed > 0. { distance / elapsed } else { 0. },
+                    max_speed: max_horizontal_speed(&samples),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the total distance the player traveled in world units.
+    pub fn distance_traveled(&self) -> f32 {
+        self.movement_per_life()
+            .iter()
+            .map(|movement| movement.distance)
+            .sum()
+    }
+}
+
+fn max_horizontal_speed(samples: &[&PositionSample]) -> Option<(GameTime, f32)> {
+    let mut speeds = vec![];
+    let mut window_start = samples.first()?;
+
+    for sample in samples {
+        let elapsed = &sample.time - &window_start.time;
+
+        if elapsed >= LifeMovement::SPEED_WINDOW {
+            let [x1, y1, _] = window_start.origin;
+            let [x2, y2, _] = sample.origin;
+            let horizontal_distance = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+
+            speeds.push((
+                window_start.time.clone(),
+                horizontal_distance / elapsed.as_secs_f32(),
+            ));
+
+            window_start = sample;
+        }
+    }
+
+    speeds.into_iter().max_by(|(_, l), (_, r)| l.total_cmp(r))
+}
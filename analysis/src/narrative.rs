@@ -0,0 +1,183 @@
+use crate::{AnalyzerState, Round, Team};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Wording used to build a [Narrative].
+///
+/// Templates contain placeholders in braces that are substituted when the narrative is built.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NarrativeTemplates {
+    /// Sentence for consecutive rounds won by the same team.
+    ///
+    /// Placeholders: `{team}`, `{rounds}`.
+    pub round_run: String,
+
+    /// Clause appended to [NarrativeTemplates::round_run] when a player on the winning team had a
+    /// notable kill streak during those rounds.
+    ///
+    /// Placeholders: `{player}`, `{kills}`.
+    pub streak: String,
+
+    /// Sentence for the player with the most objective captures.
+    ///
+    /// Placeholders: `{player}`, `{captures}`, e.g. `2 flag captures`.
+    pub top_capper: String,
+
+    /// Sentence for the final result when one team has more points.
+    ///
+    /// Placeholders: `{winner}`, `{loser}`, `{winner_score}`, `{loser_score}`.
+    pub match_won: String,
+
+    /// Sentence for the final result when both teams have the same points.
+    ///
+    /// Placeholders: `{score}`.
+    pub match_tied: String,
+}
+
+impl Default for NarrativeTemplates {
+    fn default() -> Self {
+        Self {
+            round_run: "{team} took {rounds}".to_string(),
+            streak: " behind {player}'s {kills}-kill streak".to_string(),
+            top_capper: "{player} led all players with {captures}.".to_string(),
+            match_won: "{winner} won the match {winner_score} to {loser_score}.".to_string(),
+            match_tied: "The match ended in a {score} to {score} tie.".to_string(),
+        }
+    }
+}
+
+/// Short, human-readable summary of a match built from round results, kill streaks and captures.
+pub struct Narrative(Vec<String>);
+
+impl Narrative {
+    /// Minimum number of kills for a streak to be mentioned.
+    const MIN_STREAK_KILLS: usize = 3;
+
+    pub fn new(state: &AnalyzerState, templates: &NarrativeTemplates) -> Self {
+        let mut sentences = vec![];
+
+        // Consecutive rounds won by the same team are told as a single run
+        let mut runs: Vec<(Team, Vec<(usize, &Round)>)> = vec![];
+
+        for (i, round) in state.rounds.iter().enumerate() {
+            let Round::Completed {
+                winner_stats: Some((winner, _)),
+                ..
+            } = round
+            else {
+                continue;
+            };
+
+            match runs.last_mut() {
+                Some((team, rounds)) if team == winner => rounds.push((i + 1, round)),
+                _ => runs.push((winner.clone(), vec![(i + 1, round)])),
+            }
+        }
+
+        for (team, rounds) in runs {
+            let rounds_fragment = match (rounds.first(), rounds.last()) {
+                (Some((first, _)), Some((last, _))) if first != last => {
+                    format!("rounds {first}-{last}")
+                }
+                (Some((first, _)), _) => format!("round {first}"),
+                _ => continue,
+            };
+
+            let mut sentence = templates
+                .round_run
+                .replace("{team}", &format!("{team:?}"))
+                .replace("{rounds}", &rounds_fragment);
+
+            // Players may have swapped sides since, so the streak counts for the side they were on
+            let best_streak = state
+                .players
+                .iter()
+                .flat_map(|player| {
+                    player
+                        .kill_streaks
+                        .iter()
+                        .map(move |streak| (player, streak))
+                })
+                .filter(|(player, streak)| {
+                    streak.kills.first().is_some_and(|(time, _)| {
+                        state.side_of(player, time).as_ref() == Some(&team)
+                            && rounds.iter().any(|(_, round)| round.contains(time))
+                    })
+                })
+                .max_by_key(|(_, streak)| streak.kills.len());
+
+            if let Some((player, streak)) = best_streak
+                && streak.kills.len() >= Self::MIN_STREAK_KILLS
+            {
+                sentence += &templates
+                    .streak
+                    .replace("{player}", &player.name)
+                    .replace("{kills}", &streak.kills.len().to_string());
+            }
+
+            sentence.push('.');
+            sentences.push(sentence);
+        }
+
+        let captures_by_player = state
+            .captures
+            .iter()
+            .fold(BTreeMap::new(), |mut acc, capture| {
+                *acc.entry(&capture.player).or_insert(0) += 1;
+                acc
+            });
+
+        let top_capper = captures_by_player
+            .into_iter()
+            .max_by_key(|(_, captures)| *captures)
+            .and_then(|(id, captures)| Some((state.find_player_by_id(id)?, captures)));
+
+        if let Some((player, captures)) = top_capper {
+            let captures = match captures {
+                1 => "1 flag capture".to_string(),
+                captures => format!("{captures} flag captures"),
+            };
+
+            sentences.push(
+                templates
+                    .top_capper
+                    .replace("{player}", &player.name)
+                    .replace("{captures}", &captures),
+            );
+        }
+
+        let allies_score = state.team_scores.get_team_score(Team::Allies);
+        let axis_score = state.team_scores.get_team_score(Team::Axis);
+
+        let result = if allies_score == axis_score {
+            templates
+                .match_tied
+                .replace("{score}", &allies_score.to_string())
+        } else {
+            let ((winner, winner_score), (loser, loser_score)) = if allies_score > axis_score {
+                ((Team::Allies, allies_score), (Team::Axis, axis_score))
+            } else {
+                ((Team::Axis, axis_score), (Team::Allies, allies_score))
+            };
+
+            templates
+                .match_won
+                .replace("{winner}", &format!("{winner:?}"))
+                .replace("{loser}", &format!("{loser:?}"))
+                .replace("{winner_score}", &winner_score.to_string())
+                .replace("{loser_score}", &loser_score.to_string())
+        };
+
+        sentences.push(result);
+
+        Self(sentences)
+    }
+}
+
+impl Display for Narrative {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.join(" "))
+    }
+}
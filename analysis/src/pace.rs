@@ -0,0 +1,102 @@
+use crate::{AnalyzerState, Round, time::GameTime};
+use std::time::Duration;
+
+/// How long a completed round lasted and how quickly kills happened in it.
+#[derive(Debug)]
+pub struct RoundPace {
+    /// Number of the round, starting at 1.
+    pub number: usize,
+
+    pub start_time: GameTime,
+    pub duration: Duration,
+
+    /// Number of kills between opponents in the round.
+    pub kills: usize,
+
+    pub kills_per_minute: f32,
+}
+
+/// Round duration statistics for a match.
+#[derive(Debug)]
+pub struct MatchPace {
+    pub rounds: Vec<RoundPace>,
+    pub avg_duration: Duration,
+    pub median_duration: Duration,
+}
+
+impl MatchPace {
+    pub fn new(state: &AnalyzerState) -> Self {
+        let rounds = state
+            .rounds
+            .iter()
+            .enumerate()
+            .filter_map(|(i, round)| {
+                let Round::Completed {
+                    start_time,
+                    end_time,
+                    ..
+                } = round
+                else {
+                    return None;
+                };
+
+                let duration = end_time - start_time;
+
+                let kills = state
+                    .kills
+                    .iter()
+                    .filter(|kill| kill.is_enemy_kill() && round.contains(&kill.time))
+                    .count();
+
+                let minutes = duration.as_secs_f32() / 60.;
+
+                Some(RoundPace {
+                    number: i + 1,
+                    start_time: start_time.clone(),
+                    duration,
+                    kills,
+                    kills_per_minute: if minutes > 0. {
+                        kills as f32 / minutes
+                    } else {
+                        0.
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut durations = rounds
+            .iter()
+            .map(|round| round.duration)
+            .collect::<Vec<_>>();
+
+        durations.sort();
+
+        let avg_duration = if durations.is_empty() {
+            Duration::ZERO
+        } else {
+            durations.iter().sum::<Duration>() / durations.len() as u32
+        };
+
+        let median_duration = match durations.len() {
+            0 => Duration::ZERO,
+            len if len % 2 == 0 => (durations[len / 2 - 1] + durations[len / 2]) / 2,
+            len => durations[len / 2],
+        };
+
+        Self {
+            rounds,
+            avg_duration,
+            median_duration,
+        }
+    }
+
+    /// Returns the shortest round.
+    pub fn fastest(&self) -> Option<&RoundPace> {
+        self.rounds.iter().min_by_key(|round| round.duration)
+    }
+
+    /// Returns the longest round.
+    pub fn slowest(&self) -> Option<&RoundPace> {
+        self.rounds.iter().max_by_key(|round| round.duration)
+    }
+}
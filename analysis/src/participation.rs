@@ -0,0 +1,49 @@
+use crate::{AnalyzerState, Player, Round};
+
+/// Share of their team's kills that a [Player] made in the completed rounds they played in.
+#[derive(Debug)]
+pub struct KillParticipation {
+    /// Fraction of the team's kills made by the player in each completed round they participated
+    /// in, leaving out rounds in which the team got no kills.
+    pub per_round: Vec<f32>,
+
+    /// Average fraction across rounds, between 0 and 1.
+    pub average: f32,
+}
+
+impl KillParticipation {
+    pub fn new(player: &Player, state: &AnalyzerState) -> Self {
+        let teammates = state
+            .players
+            .iter()
+            .filter(|other| other.team.is_some() && other.team == player.team)
+            .collect::<Vec<_>>();
+
+        let per_round = state
+            .rounds
+            .iter()
+            .filter(|round| matches!(round, Round::Completed { .. }))
+            .filter(|round| round.is_participant(&player.id))
+            .filter_map(|round| {
+                let team_kills = teammates
+                    .iter()
+                    .map(|teammate| teammate.kills_in(round).count())
+                    .sum::<usize>();
+
+                if team_kills == 0 {
+                    return None;
+                }
+
+                Some(player.kills_in(round).count() as f32 / team_kills as f32)
+            })
+            .collect::<Vec<_>>();
+
+        let average = if per_round.is_empty() {
+            0.
+        } else {
+            per_round.iter().sum::<f32>() / per_round.len() as f32
+        };
+
+        Self { per_round, average }
+    }
+}
@@ -1,12 +1,18 @@
-use crate::{AnalyzerEvent, AnalyzerState, kill::KillStreak, mortality::MortalityChange};
+use crate::{
+    AmmoUsage, AnalyzerEvent, AnalyzerState, IdentityMap, MortalityState, Round, kill::KillStreak,
+    mortality::MortalityChange, position::PositionSample, time::GameTime,
+};
 use dem::types::EngineMessage;
-use dod::{Class, Team, Weapon};
-use std::collections::HashMap;
+use dod::{Class, Codepage, Team, Weapon, WeaponCategory};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::time::Duration;
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct PlayerGlobalId(String);
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct PlayerGlobalId(pub(crate) String);
 
 impl Display for PlayerGlobalId {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -22,9 +28,29 @@ pub struct Player {
     pub team: Option<Team>,
     pub class: Option<Class>,
     pub stats: (i32, i32, i32),
+
+    /// Points accrued from objectives, such as capturing flags, as opposed to the total score.
+    pub objective_score: i32,
+
     pub kill_streaks: Vec<KillStreak>,
-    pub weapon_breakdown: HashMap<Weapon, (u32, u32)>,
+    pub weapon_breakdown: BTreeMap<Weapon, (u32, u32)>,
+
+    /// Number of times the player was killed by an enemy with each weapon.
+    pub deaths_by_weapon: BTreeMap<Weapon, u32>,
+
     pub mortality: Vec<MortalityChange>,
+
+    /// When the player was first seen connecting to the server.
+    pub first_connected: GameTime,
+
+    /// Where the player was while alive, only available for the player that recorded the demo.
+    pub positions: Vec<PositionSample>,
+
+    /// When the player was heard firing their weapon.
+    pub shots: Vec<GameTime>,
+
+    /// Rounds fired with each weapon, only available for the player that recorded the demo.
+    pub ammo: BTreeMap<Weapon, AmmoUsage>,
 }
 
 impl Hash for Player {
@@ -41,8 +67,21 @@ impl PartialEq for Player {
 
 impl Eq for Player {}
 
+/// Kills made by a [Player] with the weapons of a [WeaponCategory].
+#[derive(Debug)]
+pub struct CategoryBreakdown<'a> {
+    pub category: WeaponCategory,
+
+    /// Kills and teamkills with all weapons of the category.
+    pub kills: u32,
+    pub teamkills: u32,
+
+    /// Kills and teamkills with each weapon of the category.
+    pub weapons: Vec<(&'a Weapon, u32, u32)>,
+}
+
 impl Player {
-    fn new(id: PlayerGlobalId) -> Self {
+    pub(crate) fn new(id: PlayerGlobalId, first_connected: GameTime) -> Self {
         Self {
             connection: Connection::Disconnected,
             name: String::new(),
@@ -50,10 +89,116 @@ impl Player {
             team: None,
             class: None,
             stats: (0, 0, 0),
+            objective_score: 0,
             kill_streaks: vec![],
-            weapon_breakdown: HashMap::new(),
+            weapon_breakdown: BTreeMap::new(),
+            deaths_by_weapon: BTreeMap::new(),
             mortality: vec![],
+            first_connected,
+            positions: vec![],
+            shots: vec![],
+            ammo: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the kills made by the player during a [Round].
+    pub fn kills_in<'a>(
+        &'a self,
+        round: &'a Round,
+    ) -> impl Iterator<Item = &'a (GameTime, Weapon)> + 'a {
+        self.kill_streaks
+            .iter()
+            .flat_map(|streak| streak.kills.iter())
+            .filter(|(time, _)| round.contains(time))
+    }
+
+    /// Returns the weapon breakdown grouped by [WeaponCategory], with the weapons in each category
+    /// ordered by most kills.
+    pub fn weapon_breakdown_by_category(&self) -> Vec<CategoryBreakdown<'_>> {
+        let mut categories: BTreeMap<WeaponCategory, CategoryBreakdown> = BTreeMap::new();
+
+        for (weapon, (kills, teamkills)) in &self.weapon_breakdown {
+            let category =
+                categories
+                    .entry(weapon.category())
+                    .or_insert_with(|| CategoryBreakdown {
+                        category: weapon.category(),
+                        kills: 0,
+                        teamkills: 0,
+                        weapons: vec![],
+                    });
+
+            category.kills += kills;
+            category.teamkills += teamkills;
+            category.weapons.push((weapon, *kills, *teamkills));
+        }
+
+        categories
+            .into_values()
+            .map(|mut category| {
+                category
+                    .weapons
+                    .sort_by_key(|(_, kills, teamkills)| Reverse((*kills, *teamkills)));
+                category
+            })
+            .collect()
+    }
+
+    /// Returns the kill streaks with at least `min_kills` kills, along with their index among all
+    /// of the player's streaks.
+    pub fn notable_kill_streaks(
+        &self,
+        min_kills: usize,
+    ) -> impl Iterator<Item = (usize, &KillStreak)> {
+        self.kill_streaks
+            .iter()
+            .enumerate()
+            .filter(move |(_, streak)| !streak.kills.is_empty() && streak.kills.len() >= min_kills)
+    }
+
+    /// Returns, for every life in which the player got a kill, the time from spawning to the first
+    /// kill.
+    pub fn times_to_first_kill(&self) -> Vec<Duration> {
+        self.lives()
+            .into_iter()
+            .filter_map(|(spawn_time, death_time)| {
+                let life = spawn_time.viewdemo_offset..=death_time.viewdemo_offset;
+
+                self.kill_streaks
+                    .iter()
+                    .flat_map(|streak| streak.kills.iter())
+                    .map(|(time, _)| time)
+                    .filter(|time| life.contains(&time.viewdemo_offset))
+                    .min_by_key(|time| time.viewdemo_offset)
+                    .map(|time| time - spawn_time)
+            })
+            .collect()
+    }
+
+    pub fn min_time_to_first_kill(&self) -> Duration {
+        let times = self.times_to_first_kill();
+        let duration = times.iter().min().unwrap_or(&Duration::ZERO);
+
+        *duration
+    }
+
+    pub fn avg_time_to_first_kill(&self) -> Duration {
+        let times = self.times_to_first_kill();
+
+        if times.is_empty() {
+            return Duration::ZERO;
         }
+
+        times.iter().sum::<Duration>() / times.len() as u32
+    }
+
+    /// Returns the number of completed rounds the player participated in.
+    pub fn rounds_played(&self, rounds: &[Round]) -> usize {
+        rounds
+            .iter()
+            .filter(|round| matches!(round, Round::Completed { .. }))
+            .filter(|round| round.is_participant(&self.id))
+            .count()
     }
 
     fn with_connection(&mut self, connection: Connection) -> &mut Self {
@@ -99,30 +244,88 @@ impl TryFrom<&PlayerGlobalId> for SteamId {
     }
 }
 
+/// Slot of a player on the server, counted from 0.
+///
+/// Engine messages refer to players by their slot, while the mod's messages and sounds refer to
+/// them by their entity index, which counts the world entity first. Indexes are only converted
+/// here, so analyzers never offset them by hand.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ClientSlot(u8);
+
+impl ClientSlot {
+    /// Returns the slot of the player entity, or [None] for the world entity, e.g. the killer of a
+    /// player who fell to their death, or the sender of a message from the server console.
+    pub fn from_entity_index(entity_index: impl Into<u16>) -> Option<Self> {
+        let slot = entity_index.into().checked_sub(1)?;
+
+        u8::try_from(slot).ok().map(Self)
+    }
+}
+
+impl From<u8> for ClientSlot {
+    /// Returns the slot sent by the engine, which is already counted from 0.
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
 /// Represents whether a [Player] is connected to the server.
 #[derive(Debug)]
 pub enum Connection {
     /// Player is currently connected to the server.
     Connected {
-        /// Identifier assigned by the server that represents the [Player]'s connection.
-        client_id: u8,
+        /// Slot assigned by the server to the [Player]'s connection.
+        client_id: ClientSlot,
     },
 
     Disconnected,
 }
 
-pub fn use_player_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+/// A userinfo string the server sent for a player slot, kept as it was received.
+///
+/// Useful for investigating identity resolution issues, since only the parsed fields are kept on
+/// the [Player].
+#[derive(Debug)]
+pub struct UserInfoUpdate {
+    /// Time when the update was received.
+    pub time: GameTime,
+
+    /// Slot the userinfo was sent for.
+    pub slot: u8,
+
+    /// Identifier assigned by the server to the connection in the slot.
+    pub connection_id: u32,
+
+    /// Full userinfo string, including fields that are not parsed.
+    pub user_info: String,
+}
+
+pub fn use_player_updates(
+    identities: &IdentityMap,
+    codepage: Codepage,
+    state: &mut AnalyzerState,
+    event: &AnalyzerEvent,
+) {
     let svc_update_user_info = match event {
         AnalyzerEvent::EngineMessage(EngineMessage::SvcUpdateUserInfo(msg)) => Some(msg),
         _ => None,
     };
 
     if let Some(svc_update_user_info) = svc_update_user_info {
-        let fields = svc_update_user_info
-            .user_info
-            .to_str()
-            .map(|s| s.trim_matches(['\0', '\\']).split("\\").collect::<Vec<_>>())
-            .unwrap_or_default()
+        // Names are sent in the client's codepage, so this cannot assume the string is UTF-8
+        let user_info = dod::decode_string(&svc_update_user_info.user_info[..], codepage);
+
+        state.user_info_history.push(UserInfoUpdate {
+            time: state.current_time.clone(),
+            slot: svc_update_user_info.index,
+            connection_id: svc_update_user_info.id,
+            user_info: user_info.clone(),
+        });
+
+        let fields = user_info
+            .trim_matches(['\0', '\\'])
+            .split("\\")
+            .collect::<Vec<_>>()
             .chunks_exact(2)
             .fold(HashMap::new(), |mut map, chunk| {
                 if let [key, value] = chunk {
@@ -135,7 +338,7 @@ pub fn use_player_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
         // Missing fields indicates that the user has disconnected, so we only update their
         // connection status and preserve the last known details.
         if fields.is_empty() {
-            let player = state.find_player_by_client_index_mut(svc_update_user_info.index);
+            let player = state.find_player_by_slot_mut(svc_update_user_info.index.into());
 
             if let Some(disconnected_player) = player {
                 disconnected_player.connection = Connection::Disconnected;
@@ -164,6 +367,7 @@ pub fn use_player_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
             })
             .or_else(|| Some(format!("CONNECTION_{}", svc_update_user_info.id)))
             .map(PlayerGlobalId)
+            .map(|id| identities.canonical(&id))
             .unwrap_or_else(|| {
                 panic!(
                     "Could not resolve a global id for player {} in slot {}",
@@ -179,14 +383,14 @@ pub fn use_player_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
         // Make sure a record of this player exists first
         if state.find_player_by_id(&id).is_none() {
             let insert_id = id.clone();
-            let new_player = Player::new(insert_id);
+            let new_player = Player::new(insert_id, state.current_time.clone());
 
             state.players.push(new_player);
         };
 
         // Flush any existing player from this slot
         if let Some(player_in_slot) =
-            state.find_player_by_client_index_mut(svc_update_user_info.index)
+            state.find_player_by_slot_mut(svc_update_user_info.index.into())
         {
             player_in_slot.with_connection(Connection::Disconnected);
         }
@@ -195,7 +399,7 @@ pub fn use_player_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
         if let Some(player) = state.find_player_by_id_mut(&id) {
             player
                 .with_connection(Connection::Connected {
-                    client_id: svc_update_user_info.index,
+                    client_id: svc_update_user_info.index.into(),
                 })
                 .with_name(player_name)
                 .with_team(
@@ -0,0 +1,67 @@
+use crate::{AnalyzerEvent, AnalyzerState, ClientSlot, mortality::MortalityState, time::GameTime};
+use dem::types::FrameData;
+
+/// Location and view direction of a player at a moment in time.
+///
+/// Demos only carry the view of the player that recorded them, so samples exist for the POV
+/// player alone.
+#[derive(Clone, Debug)]
+pub struct PositionSample {
+    /// Time when the player was at this position.
+    pub time: GameTime,
+
+    /// Position of the player's view in world units.
+    pub origin: [f32; 3],
+
+    /// Pitch, yaw, and roll of the player's view in degrees.
+    pub view_angles: [f32; 3],
+}
+
+impl PositionSample {
+    /// Returns the straight-line distance to a point in world units.
+    pub fn distance_to(&self, point: [f32; 3]) -> f32 {
+        distance(self.origin, point)
+    }
+}
+
+/// Returns the straight-line distance between two points in world units.
+pub fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+pub fn use_position_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    let AnalyzerEvent::Frame(frame) = event else {
+        return;
+    };
+
+    let FrameData::NetworkMessage(box_type) = &frame.frame_data else {
+        return;
+    };
+
+    let refdef = &box_type.1.info.refdef;
+
+    // Spectator views follow a camera rather than the recording player
+    if refdef.spectator != 0 {
+        return;
+    }
+
+    let Ok(slot) = u8::try_from(refdef.playernum).map(ClientSlot::from) else {
+        return;
+    };
+
+    let time = state.current_time.clone();
+
+    if let Some(player) = state.find_player_by_slot_mut(slot)
+        && player.is_alive()
+    {
+        player.positions.push(PositionSample {
+            time,
+            origin: refdef.vieworg,
+            view_angles: refdef.viewangles,
+        });
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{AnalyzerState, Capture, ChatMessage, Kill, Player, PlayerGlobalId, time::GameTime};
+use dod::Team;
+use std::cmp::Reverse;
+use std::ops::RangeBounds;
+use std::time::Duration;
+
+/// Something that happened at a moment of the demo, as returned by [AnalyzerState::events_in].
+#[derive(Debug)]
+pub enum TimedEvent<'a> {
+    Kill(&'a Kill),
+    Capture(&'a Capture),
+    Chat(&'a ChatMessage),
+
+    /// The teams swapped sides.
+    TeamSwap(&'a GameTime),
+}
+
+impl TimedEvent<'_> {
+    pub fn time(&self) -> &GameTime {
+        match self {
+            Self::Kill(kill) => &kill.time,
+            Self::Capture(capture) => &capture.time,
+            Self::Chat(message) => &message.time,
+            Self::TeamSwap(time) => time,
+        }
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the players with the most kills, at most `n` of them, ordered by kills and then by
+    /// fewest deaths.
+    pub fn top_fraggers(&self, n: usize) -> Vec<&Player> {
+        let mut players = self.players.iter().collect::<Vec<_>>();
+
+        players.sort_by_key(|player| (Reverse(player.stats.1), player.stats.2));
+        players.truncate(n);
+
+        players
+    }
+
+    /// Returns the players last seen on the team.
+    pub fn players_on(&self, team: Team) -> impl Iterator<Item = &Player> {
+        self.players
+            .iter()
+            .filter(move |player| player.team.as_ref() == Some(&team))
+    }
+
+    /// Returns the kills of either player on the other, in the order they happened.
+    pub fn kills_between<'a>(
+        &'a self,
+        a: &'a PlayerGlobalId,
+        b: &'a PlayerGlobalId,
+    ) -> impl Iterator<Item = &'a Kill> + 'a {
+        self.kills.iter().filter(move |kill| {
+            let killer = kill.killer.as_ref();
+
+            (killer == Some(a) && kill.victim == *b) || (killer == Some(b) && kill.victim == *a)
+        })
+    }
+
+    /// Returns the kills, captures, chat messages, and team swaps that happened in the range of
+    /// times on the server clock, as shown by `viewdemo`, in the order they happened.
+    pub fn events_in(&self, range: impl RangeBounds<Duration>) -> Vec<TimedEvent<'_>> {
+        let mut events = self
+            .kills
+            .iter()
+            .map(TimedEvent::Kill)
+            .chain(self.captures.iter().map(TimedEvent::Capture))
+            .chain(self.chat.iter().map(TimedEvent::Chat))
+            .chain(self.team_swaps.iter().map(TimedEvent::TeamSwap))
+            .filter(|event| range.contains(&event.time().viewdemo_offset))
+            .collect::<Vec<_>>();
+
+        // Stable, so events at the same moment keep the order above
+        events.sort_by_key(|event| event.time().viewdemo_offset);
+
+        events
+    }
+}
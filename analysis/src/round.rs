@@ -1,6 +1,10 @@
 use crate::time::GameTime;
-use crate::{AnalyzerEvent, AnalyzerState};
+use crate::{
+    AnalyzerEvent, AnalyzerState, Connection, KillCause, PlayerGlobalId, TeamkillPolicy,
+    kill::killer_index,
+};
 use dod::{RoundState, Team, UserMessage};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum Round {
@@ -8,90 +12,170 @@ pub enum Round {
         allies_kills: u32,
         axis_kills: u32,
         start_time: GameTime,
+
+        /// Players that were connected and on a team at any point during the round.
+        participants: Vec<PlayerGlobalId>,
+
+        /// Scoreboard gains of each player during the round so far.
+        player_stats: Vec<RoundPlayerStats>,
     },
 
     Completed {
         start_time: GameTime,
         end_time: GameTime,
         winner_stats: Option<(Team, u32)>,
+
+        /// Players that were connected and on a team at any point during the round.
+        participants: Vec<PlayerGlobalId>,
+
+        /// Scoreboard gains of each player during the round, for the players whose row changed.
+        player_stats: Vec<RoundPlayerStats>,
     },
 }
 
-pub fn use_rounds_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+/// Points, kills, deaths, and objective points a player gained on the scoreboard during a
+/// [Round].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundPlayerStats {
+    pub player: PlayerGlobalId,
+    pub score: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub objective_score: i32,
+}
+
+impl Round {
+    /// Returns true if the [GameTime] falls within the round.
+    ///
+    /// An active round has no end yet, so any time after its start is considered part of it.
+    pub fn contains(&self, time: &GameTime) -> bool {
+        match self {
+            Round::Active { start_time, .. } => time.viewdemo_offset >= start_time.viewdemo_offset,
+
+            Round::Completed {
+                start_time,
+                end_time,
+                ..
+            } => (start_time.viewdemo_offset..end_time.viewdemo_offset)
+                .contains(&time.viewdemo_offset),
+        }
+    }
+
+    /// Returns when the round started.
+    pub fn start_time(&self) -> &GameTime {
+        let (Round::Active { start_time, .. } | Round::Completed { start_time, .. }) = self;
+
+        start_time
+    }
+
+    /// Returns true if the player was connected and on a team at any point during the round.
+    pub fn is_participant(&self, id: &PlayerGlobalId) -> bool {
+        let (Round::Active { participants, .. } | Round::Completed { participants, .. }) = self;
+
+        participants.contains(id)
+    }
+
+    /// Returns the scoreboard gains of each player during the round.
+    pub fn player_stats(&self) -> &[RoundPlayerStats] {
+        let (Round::Active { player_stats, .. } | Round::Completed { player_stats, .. }) = self;
+
+        player_stats
+    }
+
+    /// Returns the scoreboard gains of the player during the round, if their row changed.
+    pub fn stats_of(&self, id: &PlayerGlobalId) -> Option<&RoundPlayerStats> {
+        self.player_stats().iter().find(|stats| stats.player == *id)
+    }
+}
+
+pub fn use_rounds_updates(
+    teamkill_policy: &TeamkillPolicy,
+    state: &mut AnalyzerState,
+    event: &AnalyzerEvent,
+) {
     match event {
-        AnalyzerEvent::Initialization => {
-            if state.rounds.is_empty() {
-                state.rounds.push(Round::Active {
-                    allies_kills: 0,
-                    axis_kills: 0,
-                    start_time: state.current_time.clone(),
-                });
-            }
+        AnalyzerEvent::Initialization if state.rounds.is_empty() => {
+            state.rounds.push(Round::Active {
+                allies_kills: 0,
+                axis_kills: 0,
+                start_time: state.current_time.clone(),
+                participants: vec![],
+                player_stats: vec![],
+            });
         }
 
-        AnalyzerEvent::Finalization => {
-            if let Some(Round::Active { start_time, .. }) = state.rounds.pop() {
-                state.rounds.push(Round::Completed {
-                    start_time: start_time.clone(),
-                    end_time: state.current_time.clone(),
-                    winner_stats: None,
+        AnalyzerEvent::Finalization => end_active_round(state, None),
+
+        AnalyzerEvent::Frame(_) => {
+            if let Some(Round::Active { participants, .. }) = state.rounds.last_mut() {
+                let playing = state.players.iter().filter(|player| {
+                    matches!(player.connection, Connection::Connected { .. })
+                        && matches!(player.team, Some(Team::Allies | Team::Axis))
                 });
+
+                for player in playing {
+                    if !participants.contains(&player.id) {
+                        participants.push(player.id.clone());
+                    }
+                }
             }
         }
 
         AnalyzerEvent::UserMessage(UserMessage::RoundState(round_state)) => {
             match round_state {
                 RoundState::Reset => {
+                    // Servers can reset a round before anyone wins it, e.g. when restarting
+                    end_active_round(state, None);
+
                     state.rounds.push(Round::Active {
                         allies_kills: 0,
                         axis_kills: 0,
                         start_time: state.current_time.clone(),
+                        participants: vec![],
+                        player_stats: vec![],
                     });
                 }
 
-                RoundState::AlliesWin | RoundState::AxisWin => {
-                    let active_round = state
-                        .rounds
-                        .pop()
-                        .expect("Got a RoundState(Win) with no active round");
-
-                    if let Round::Active {
-                        start_time,
-                        allies_kills,
-                        axis_kills,
-                    } = active_round
-                    {
-                        let winner_stats = if matches!(round_state, RoundState::AlliesWin) {
-                            (Team::Allies, allies_kills)
-                        } else {
-                            (Team::Axis, axis_kills)
-                        };
-
-                        let completed_round = Round::Completed {
-                            start_time,
-                            end_time: state.current_time.clone(),
-                            winner_stats: Some(winner_stats),
-                        };
-
-                        state.rounds.push(completed_round);
-                    } else {
-                        panic!("Got a RoundState(Win) with no active round")
-                    }
-                }
+                RoundState::AlliesWin => end_active_round(state, Some(Team::Allies)),
+                RoundState::AxisWin => end_active_round(state, Some(Team::Axis)),
+                RoundState::Draw => end_active_round(state, None),
 
-                _ => {}
+                RoundState::Start => {}
             };
         }
 
+        // The scoreboard analyzer has already applied the new rows
+        AnalyzerEvent::UserMessage(UserMessage::ScoreShort(score_short)) => {
+            add_scoreboard_changes(state, score_short.client_index)
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::ScoreInfo(score_info)) => {
+            add_scoreboard_changes(state, score_info.client_index)
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::ObjScore(obj_score)) => {
+            add_scoreboard_changes(state, obj_score.client_index)
+        }
+
+        AnalyzerEvent::UserMessage(UserMessage::Frags(frags)) => {
+            add_scoreboard_changes(state, frags.client_index)
+        }
+
         AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) => {
-            let killer = state.find_player_by_client_index(death_msg.killer_client_index - 1);
-            let victim = state.find_player_by_client_index(death_msg.victim_client_index - 1);
+            // Suicides and deaths to the world are nobody's kill, whatever the teamkill policy
+            if KillCause::of(death_msg) != KillCause::Player {
+                return;
+            }
+
+            let killer = state.find_player_by_entity_index(killer_index(death_msg));
+
+            let victim = state.find_player_by_entity_index(death_msg.victim_client_index);
 
             let kill_info = match (killer, victim) {
-                (Some(killer), Some(victim)) => Some((
-                    killer.team.clone(),
-                    killer.team.is_some() && killer.team == victim.team,
-                )),
+                (Some(killer), Some(victim)) => killer
+                    .team
+                    .clone()
+                    .map(|team| (team, killer.team == victim.team)),
                 _ => None,
             };
 
@@ -104,14 +188,14 @@ pub fn use_rounds_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
                 Some((team, is_teamkill)),
             ) = (state.rounds.last_mut(), kill_info)
             {
-                if is_teamkill {
+                if is_teamkill && *teamkill_policy != TeamkillPolicy::Include {
                     return;
                 }
 
-                if let Some(Team::Allies) = team {
-                    *allies_kills += 1;
-                } else {
-                    *axis_kills += 1;
+                match team {
+                    Team::Allies => *allies_kills += 1,
+                    Team::Axis => *axis_kills += 1,
+                    Team::Spectators => {}
                 }
             }
         }
@@ -119,3 +203,325 @@ pub fn use_rounds_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
         _ => {}
     };
 }
+
+/// Adds the change of the player's scoreboard row since it was last seen to the active round.
+///
+/// The first row seen of a player, e.g. one who was already playing when the recording started or
+/// when the match went live, only marks where their changes start from, so the results they had
+/// before are not counted in the round.
+fn add_scoreboard_changes(state: &mut AnalyzerState, client_index: u8) {
+    let Some(player) = state.find_player_by_entity_index(client_index) else {
+        return;
+    };
+
+    let id = player.id.clone();
+    let totals = [
+        player.stats.0,
+        player.stats.1,
+        player.stats.2,
+        player.objective_score,
+    ];
+
+    let Some(last_totals) = state.round_scoreboard.insert(id.clone(), totals) else {
+        return;
+    };
+
+    if totals == last_totals {
+        return;
+    }
+
+    let Some(Round::Active { player_stats, .. }) = state.rounds.last_mut() else {
+        return;
+    };
+
+    let [score, kills, deaths, objective_score] =
+        std::array::from_fn(|i| totals[i] - last_totals[i]);
+
+    match player_stats.iter_mut().find(|stats| stats.player == id) {
+        Some(stats) => {
+            stats.score += score;
+            stats.kills += kills;
+            stats.deaths += deaths;
+            stats.objective_score += objective_score;
+        }
+
+        None => player_stats.push(RoundPlayerStats {
+            player: id,
+            score,
+            kills,
+            deaths,
+            objective_score,
+        }),
+    }
+}
+
+/// Completes the active round, if there is one. Round results can arrive without a round in
+/// progress, e.g. a win right after the match start, so they are ignored then.
+fn end_active_round(state: &mut AnalyzerState, winner: Option<Team>) {
+    if let Some(round) = state.rounds.last_mut()
+        && let Round::Active {
+            allies_kills,
+            axis_kills,
+            start_time,
+            participants,
+            player_stats,
+        } = round
+    {
+        let winner_stats = winner.map(|team| match team {
+            Team::Allies => (team, *allies_kills),
+            _ => (team, *axis_kills),
+        });
+
+        *round = Round::Completed {
+            start_time: start_time.clone(),
+            end_time: state.current_time.clone(),
+            winner_stats,
+            participants: std::mem::take(participants),
+            player_stats: std::mem::take(player_stats),
+        };
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the number of the round, starting at 1, that the [GameTime] falls within, and the
+    /// time since that round started.
+    pub fn round_time(&self, time: &GameTime) -> Option<(usize, Duration)> {
+        let (i, round) = self
+            .rounds
+            .iter()
+            .enumerate()
+            .find(|(_, round)| round.contains(time))?;
+
+        Some((i + 1, time - round.start_time()))
+    }
+}
+
+/// Starts over with a single active round that began at the [GameTime].
+pub(crate) fn reset_rounds(state: &mut AnalyzerState, start_time: GameTime) {
+    state.rounds.clear();
+    state.round_scoreboard.clear();
+    state.rounds.push(Round::Active {
+        allies_kills: 0,
+        axis_kills: 0,
+        start_time,
+        participants: vec![],
+        player_stats: vec![],
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clan_match::use_clan_match_detection_updates, fixtures::state_with_players,
+        scoreboard::use_scoreboard_updates,
+    };
+    use dod::{ClanTimer, DeathMsg, ScoreShort, Weapon};
+    use proptest::prelude::*;
+
+    /// Something that happens on the server between two moments of the demo.
+    #[derive(Clone, Debug)]
+    enum Step {
+        Wait(Duration),
+        RoundState(u8),
+        Death { killer: u8, victim: u8 },
+        Suicide(u8),
+        ClanTimer,
+    }
+
+    fn step() -> impl Strategy<Value = Step> {
+        prop_oneof![
+            (0..30_000u64).prop_map(|millis| Step::Wait(Duration::from_millis(millis))),
+            (0..5u8).prop_map(Step::RoundState),
+            // Client index 0 is the world, and 5 is a slot nobody is in
+            (0..=5u8, 1..=5u8).prop_map(|(killer, victim)| Step::Death { killer, victim }),
+            (1..=4u8).prop_map(Step::Suicide),
+            Just(Step::ClanTimer),
+        ]
+    }
+
+    fn teamkill_policy() -> impl Strategy<Value = TeamkillPolicy> {
+        prop_oneof![
+            Just(TeamkillPolicy::Exclude),
+            Just(TeamkillPolicy::Penalize),
+            Just(TeamkillPolicy::Include),
+        ]
+    }
+
+    /// Runs the round and clan match analyzers, applying the match start like the
+    /// [crate::context::AnalysisContext] does.
+    fn handle(teamkill_policy: &TeamkillPolicy, state: &mut AnalyzerState, event: &AnalyzerEvent) {
+        use_rounds_updates(teamkill_policy, state, event);
+        use_clan_match_detection_updates(Duration::from_secs(10), state, event);
+
+        if let Some(start_time) = state.requested_match_start.take() {
+            reset_rounds(state, start_time);
+        }
+    }
+
+    fn user_message(step: &Step) -> Option<UserMessage> {
+        match step {
+            Step::Wait(_) => None,
+
+            Step::RoundState(round_state) => Some(UserMessage::RoundState(match round_state {
+                0 => RoundState::Reset,
+                1 => RoundState::Start,
+                2 => RoundState::AlliesWin,
+                3 => RoundState::AxisWin,
+                _ => RoundState::Draw,
+            })),
+
+            Step::Death { killer, victim } => Some(UserMessage::DeathMsg(DeathMsg {
+                killer_client_index: *killer,
+                victim_client_index: *victim,
+                weapon: Weapon::Garand,
+            })),
+
+            Step::Suicide(player) => Some(suicide(*player)),
+
+            Step::ClanTimer => Some(UserMessage::ClanTimer(ClanTimer(Duration::from_secs(1200)))),
+        }
+    }
+
+    /// Returns the message of a player who killed themselves with their own grenade.
+    fn suicide(client_index: u8) -> UserMessage {
+        UserMessage::DeathMsg(DeathMsg {
+            killer_client_index: client_index,
+            victim_client_index: client_index,
+            weapon: Weapon::Mk2Grenade,
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn rounds_stay_consistent(
+            teamkill_policy in teamkill_policy(),
+            steps in prop::collection::vec(step(), 0..200),
+        ) {
+            let mut state = state_with_players(&[Team::Allies, Team::Axis, Team::Allies, Team::Axis]);
+
+            handle(&teamkill_policy, &mut state, &AnalyzerEvent::Initialization);
+
+            for step in &steps {
+                if let Step::Wait(duration) = step {
+                    state.current_time.viewdemo_offset += *duration;
+                    state.current_time.real_offset += *duration;
+                }
+
+                if let Some(user_message) = user_message(step) {
+                    handle(&teamkill_policy, &mut state, &AnalyzerEvent::UserMessage(user_message));
+                }
+            }
+
+            handle(&teamkill_policy, &mut state, &AnalyzerEvent::Finalization);
+
+            let team_of = |client_index: u8| {
+                state
+                    .find_player_by_entity_index(client_index)
+                    .and_then(|player| player.team.clone())
+            };
+
+            // Suicides and deaths to the world or in an empty slot are never a round kill
+            let kills = steps
+                .iter()
+                .filter(|step| match step {
+                    Step::Death { killer, victim } if killer != victim => {
+                        match (team_of(*killer), team_of(*victim)) {
+                            (Some(killer_team), Some(victim_team)) => {
+                                killer_team != victim_team
+                                    || teamkill_policy == TeamkillPolicy::Include
+                            }
+                            _ => false,
+                        }
+                    }
+                    _ => false,
+                })
+                .count() as u32;
+
+            let mut winning_kills = 0;
+            let mut previous_end = Duration::ZERO;
+
+            prop_assert!(!state.rounds.is_empty());
+
+            for round in &state.rounds {
+                let Round::Completed {
+                    start_time,
+                    end_time,
+                    winner_stats,
+                    ..
+                } = round
+                else {
+                    return Err(TestCaseError::fail(format!("Round was never closed: {round:?}")));
+                };
+
+                prop_assert!(start_time.viewdemo_offset <= end_time.viewdemo_offset);
+                prop_assert!(previous_end <= start_time.viewdemo_offset);
+
+                previous_end = end_time.viewdemo_offset;
+                winning_kills += winner_stats.as_ref().map_or(0, |(_, kills)| *kills);
+            }
+
+            prop_assert!(winning_kills <= kills);
+        }
+
+        #[test]
+        fn round_gains_add_up_to_the_scoreboard(
+            rows in prop::collection::vec(
+                (1..=4u8, 0..4i16, 0..4i16, 0..4i16, any::<bool>()),
+                0..50,
+            ),
+        ) {
+            let mut state = state_with_players(&[Team::Allies, Team::Axis, Team::Allies, Team::Axis]);
+
+            use_rounds_updates(&TeamkillPolicy::Include, &mut state, &AnalyzerEvent::Initialization);
+
+            for (client_index, score, kills, deaths, killed_themselves) in rows.iter().copied() {
+                // A suicide is sent before the scoreboard row it changed
+                if killed_themselves {
+                    let event = AnalyzerEvent::UserMessage(suicide(client_index));
+
+                    use_rounds_updates(&TeamkillPolicy::Include, &mut state, &event);
+                }
+
+                let event = AnalyzerEvent::UserMessage(UserMessage::ScoreShort(ScoreShort {
+                    client_index,
+                    score,
+                    kills,
+                    deaths,
+                }));
+
+                use_scoreboard_updates(&mut state, &event);
+                use_rounds_updates(&TeamkillPolicy::Include, &mut state, &event);
+            }
+
+            let round = &state.rounds[0];
+
+            prop_assert!(
+                matches!(round, Round::Active { allies_kills: 0, axis_kills: 0, .. }),
+                "Suicides were counted as kills: {:?}",
+                round
+            );
+
+            // The fixture puts the players in client indexes starting at 1
+            for (client_index, player) in (1..).zip(&state.players) {
+                let player_rows = rows
+                    .iter()
+                    .filter(|row| row.0 == client_index)
+                    .map(|(_, score, kills, deaths, _)| (*score as i32, *kills as i32, *deaths as i32))
+                    .collect::<Vec<_>>();
+
+                // The first row of a player is where their gains start from, even if it is not 0
+                let gains = match (player_rows.first(), player_rows.last()) {
+                    (Some(first), Some(last)) => (last.0 - first.0, last.1 - first.1, last.2 - first.2),
+                    _ => (0, 0, 0),
+                };
+
+                let round_gains = round
+                    .stats_of(&player.id)
+                    .map_or((0, 0, 0), |stats| (stats.score, stats.kills, stats.deaths));
+
+                prop_assert_eq!(round_gains, gains);
+            }
+        }
+    }
+}
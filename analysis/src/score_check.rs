@@ -0,0 +1,88 @@
+use crate::{AnalyzerState, KillCause, PlayerGlobalId, ScoreFormula, time::GameTime};
+
+/// Points a player scored in a round that differ from what the league's [ScoreFormula] gives
+/// them, e.g. when the server runs a scoring plugin the league did not agree to.
+#[derive(Debug)]
+pub struct ScoreMismatch {
+    /// Number of the round, starting at 1.
+    pub round: usize,
+
+    pub player: PlayerGlobalId,
+
+    /// Points gained during the round on the scoreboard.
+    pub reported: i32,
+
+    /// Points the formula gives for the captures, kills, teamkills, and suicides of the round.
+    pub expected: i32,
+}
+
+impl ScoreFormula {
+    /// Returns the points given for the counts of each scoring event.
+    pub fn points(&self, captures: usize, kills: usize, teamkills: usize, suicides: usize) -> i32 {
+        self.points_per_capture * captures as i32
+            + self.points_per_kill * kills as i32
+            + self.points_per_teamkill * teamkills as i32
+            + self.points_per_suicide * suicides as i32
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the rounds in which a player's score changed by a different amount than the
+    /// formula gives for what they did in the round, comparing the scoreboards taken at the end
+    /// of each round.
+    pub fn score_mismatches(&self, formula: &ScoreFormula) -> Vec<ScoreMismatch> {
+        let mut mismatches = vec![];
+
+        for (i, snapshot) in self.scoreboard_history.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| &self.scoreboard_history[i]);
+
+            let in_round = |time: &GameTime| {
+                previous.is_none_or(|previous| time.viewdemo_offset > previous.time.viewdemo_offset)
+                    && time.viewdemo_offset <= snapshot.time.viewdemo_offset
+            };
+
+            for (player, (score, _, _)) in &snapshot.stats {
+                let previous_score = previous
+                    .and_then(|previous| previous.get_player_stats(player))
+                    .map(|(score, _, _)| score)
+                    .unwrap_or_default();
+
+                let captures = self
+                    .captures
+                    .iter()
+                    .filter(|capture| capture.player == *player && in_round(&capture.time))
+                    .count();
+
+                let (mut kills, mut teamkills, mut suicides) = (0, 0, 0);
+
+                for kill in self.kills.iter().filter(|kill| in_round(&kill.time)) {
+                    match kill.cause {
+                        KillCause::Suicide if kill.victim == *player => suicides += 1,
+                        KillCause::Player if kill.killer.as_ref() == Some(player) => {
+                            if kill.is_teamkill {
+                                teamkills += 1;
+                            } else {
+                                kills += 1;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                let reported = score - previous_score;
+                let expected = formula.points(captures, kills, teamkills, suicides);
+
+                if reported != expected {
+                    mismatches.push(ScoreMismatch {
+                        round: snapshot.round,
+                        player: player.clone(),
+                        reported,
+                        expected,
+                    });
+                }
+            }
+        }
+
+        mismatches
+    }
+}
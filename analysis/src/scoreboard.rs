@@ -1,5 +1,5 @@
-use crate::{AnalyzerEvent, AnalyzerState, time::GameTime};
-use dod::{Team, UserMessage};
+use crate::{AnalyzerEvent, AnalyzerState, PlayerGlobalId, time::GameTime};
+use dod::{RoundState, Team, UserMessage};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -9,19 +9,58 @@ pub struct TeamScores {
     timeline: Vec<(GameTime, Team, i32)>,
 }
 
+/// A change in a team's score.
+#[derive(Debug)]
+pub struct TeamScoreChange<'a> {
+    pub time: &'a GameTime,
+    pub team: &'a Team,
+
+    /// Score of the team after the change.
+    pub score: i32,
+
+    /// Points gained, or lost if negative, since the team's previous score.
+    pub delta: i32,
+}
+
 impl TeamScores {
     pub fn get_team_score(&self, team: Team) -> i32 {
-        self.timeline
-            .iter()
-            .rfind(|(_, t, _)| *t == team)
-            .map(|(_, _, points)| *points)
-            .unwrap_or(0)
+        self.current_scores.get(&team).copied().unwrap_or(0)
     }
 
     pub fn add_team_score(&mut self, game_time: GameTime, team: Team, points: i32) {
+        self.current_scores.insert(team.clone(), points);
         self.timeline.push((game_time, team, points));
     }
 
+    /// Returns the score of each team as it was at the [GameTime].
+    pub fn scores_at(&self, time: &GameTime) -> HashMap<Team, i32> {
+        self.timeline
+            .iter()
+            .take_while(|(t, _, _)| t.viewdemo_offset <= time.viewdemo_offset)
+            .map(|(_, team, points)| (team.clone(), *points))
+            .collect()
+    }
+
+    /// Returns every change in the team scores, in the order they happened.
+    ///
+    /// Scores that are sent again without changing are left out.
+    pub fn changes(&self) -> impl Iterator<Item = TeamScoreChange<'_>> {
+        let mut previous_scores = HashMap::new();
+
+        self.timeline
+            .iter()
+            .filter_map(move |(time, team, points)| {
+                let previous = previous_scores.insert(team, *points).unwrap_or(0);
+
+                (*points != previous).then_some(TeamScoreChange {
+                    time,
+                    team,
+                    score: *points,
+                    delta: points - previous,
+                })
+            })
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &(GameTime, Team, i32)> {
         self.timeline.iter()
     }
@@ -32,10 +71,43 @@ impl TeamScores {
     }
 }
 
+/// Scoreboard as it looked when a round ended.
+#[derive(Debug)]
+pub struct ScoreboardSnapshot {
+    /// Number of the round that ended, starting at 1.
+    pub round: usize,
+
+    /// Time when the round ended.
+    pub time: GameTime,
+
+    /// Points, kills, and deaths of each player at the time of the snapshot.
+    pub stats: Vec<(PlayerGlobalId, (i32, i32, i32))>,
+}
+
+impl ScoreboardSnapshot {
+    /// Returns the stats of a player at the time of the snapshot.
+    pub fn get_player_stats(&self, id: &PlayerGlobalId) -> Option<(i32, i32, i32)> {
+        self.stats
+            .iter()
+            .find(|(player_id, _)| player_id == id)
+            .map(|(_, stats)| *stats)
+    }
+}
+
+/// Orders two scoreboard rows of points, kills, and deaths the way the game ranks players: most
+/// points first, then most kills, then fewest deaths.
+pub fn rank(left: &(i32, i32, i32), right: &(i32, i32, i32)) -> Ordering {
+    let by_points = left.0.cmp(&right.0).reverse();
+    let by_kills = left.1.cmp(&right.1).reverse();
+    let by_deaths = left.2.cmp(&right.2);
+
+    by_points.then(by_kills).then(by_deaths)
+}
+
 pub fn use_scoreboard_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
     match event {
         AnalyzerEvent::UserMessage(UserMessage::PClass(p_class)) => {
-            let player = state.find_player_by_client_index_mut(p_class.client_index - 1);
+            let player = state.find_player_by_entity_index_mut(p_class.client_index);
 
             if let Some(player) = player {
                 player.class = Some(p_class.class.clone());
@@ -43,7 +115,7 @@ pub fn use_scoreboard_updates(state: &mut AnalyzerState, event: &AnalyzerEvent)
         }
 
         AnalyzerEvent::UserMessage(UserMessage::PTeam(p_team)) => {
-            let player = state.find_player_by_client_index_mut(p_team.client_index - 1);
+            let player = state.find_player_by_entity_index_mut(p_team.client_index);
 
             if let Some(player) = player {
                 player.team = Some(p_team.team.clone());
@@ -51,7 +123,7 @@ pub fn use_scoreboard_updates(state: &mut AnalyzerState, event: &AnalyzerEvent)
         }
 
         AnalyzerEvent::UserMessage(UserMessage::ScoreShort(score_short)) => {
-            let player = state.find_player_by_client_index_mut(score_short.client_index - 1);
+            let player = state.find_player_by_entity_index_mut(score_short.client_index);
 
             if let Some(player) = player {
                 player.stats = (
@@ -62,16 +134,31 @@ pub fn use_scoreboard_updates(state: &mut AnalyzerState, event: &AnalyzerEvent)
             }
         }
 
+        // Legacy versions send the whole scoreboard row instead of ScoreShort
+        AnalyzerEvent::UserMessage(UserMessage::ScoreInfo(score_info)) => {
+            let player = state.find_player_by_entity_index_mut(score_info.client_index);
+
+            if let Some(player) = player {
+                player.stats = (
+                    score_info.points as i32,
+                    score_info.kills as i32,
+                    score_info.deaths as i32,
+                );
+                player.class = Some(score_info.class.clone());
+                player.team = Some(score_info.team.clone());
+            }
+        }
+
         AnalyzerEvent::UserMessage(UserMessage::ObjScore(obj_score)) => {
-            let player = state.find_player_by_client_index_mut(obj_score.client_index - 1);
+            let player = state.find_player_by_entity_index_mut(obj_score.client_index);
 
             if let Some(player) = player {
-                player.stats.0 = obj_score.score as i32;
+                player.objective_score = obj_score.score as i32;
             }
         }
 
         AnalyzerEvent::UserMessage(UserMessage::Frags(frags)) => {
-            let player = state.find_player_by_client_index_mut(frags.client_index - 1);
+            let player = state.find_player_by_entity_index_mut(frags.client_index);
 
             if let Some(player) = player {
                 player.stats.1 = frags.frags as i32;
@@ -83,11 +170,7 @@ pub fn use_scoreboard_updates(state: &mut AnalyzerState, event: &AnalyzerEvent)
                 .players
                 .sort_by(|left, right| match (&left.team, &right.team) {
                     (Some(left_team), Some(right_team)) if left_team == right_team => {
-                        let by_points = left.stats.0.cmp(&right.stats.0).reverse();
-                        let by_kills = left.stats.1.cmp(&right.stats.1).reverse();
-                        let by_deaths = left.stats.2.cmp(&right.stats.2);
-
-                        by_points.then(by_kills).then(by_deaths)
+                        rank(&left.stats, &right.stats)
                     }
 
                     (Some(Team::Allies), _) => Ordering::Less,
@@ -111,3 +194,87 @@ pub fn use_team_score_updates(state: &mut AnalyzerState, event: &AnalyzerEvent)
         );
     }
 }
+
+pub fn use_scoreboard_history_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    if let AnalyzerEvent::UserMessage(UserMessage::RoundState(
+        RoundState::AlliesWin | RoundState::AxisWin,
+    )) = event
+    {
+        let snapshot = ScoreboardSnapshot {
+            round: state.rounds.len(),
+            time: state.current_time.clone(),
+            stats: state
+                .players
+                .iter()
+                .map(|player| (player.id.clone(), player.stats))
+                .collect(),
+        };
+
+        state.scoreboard_history.push(snapshot);
+    }
+}
+
+pub(crate) fn reset_scoreboard(state: &mut AnalyzerState) {
+    state.team_scores.reset();
+    state.scoreboard_history.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn more_points_rank_first() {
+        assert_eq!(rank(&(10, 0, 9), &(5, 9, 0)), Ordering::Less);
+        assert_eq!(rank(&(5, 9, 0), &(10, 0, 9)), Ordering::Greater);
+    }
+
+    #[test]
+    fn more_kills_break_ties_in_points() {
+        assert_eq!(rank(&(5, 3, 9), &(5, 2, 0)), Ordering::Less);
+        assert_eq!(rank(&(5, 2, 0), &(5, 3, 9)), Ordering::Greater);
+    }
+
+    #[test]
+    fn fewer_deaths_break_ties_in_points_and_kills() {
+        assert_eq!(rank(&(5, 3, 1), &(5, 3, 4)), Ordering::Less);
+        assert_eq!(rank(&(5, 3, 4), &(5, 3, 1)), Ordering::Greater);
+    }
+
+    #[test]
+    fn identical_rows_are_equal() {
+        assert_eq!(rank(&(5, 3, 1), &(5, 3, 1)), Ordering::Equal);
+        assert_eq!(rank(&(0, 0, 0), &(0, 0, 0)), Ordering::Equal);
+    }
+
+    #[test]
+    fn negative_points_rank_below_zero() {
+        assert_eq!(rank(&(0, 0, 0), &(-1, 5, 0)), Ordering::Less);
+    }
+
+    #[test]
+    fn sorting_orders_every_tie_breaker() {
+        let mut rows = vec![
+            (5, 3, 4),
+            (5, 3, 1),
+            (0, 0, 0),
+            (5, 4, 9),
+            (10, 0, 0),
+            (5, 3, 1),
+        ];
+
+        rows.sort_by(rank);
+
+        assert_eq!(
+            rows,
+            vec![
+                (10, 0, 0),
+                (5, 4, 9),
+                (5, 3, 1),
+                (5, 3, 1),
+                (5, 3, 4),
+                (0, 0, 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{AnalyzerEvent, AnalyzerState, ClientSlot, Player, mortality::MortalityState};
+use dem::types::EngineMessage;
+
+/// Sound channel that the engine plays weapon sounds on.
+const CHAN_WEAPON: u8 = 1;
+
+/// Records the weapon sounds made by each player as shots fired.
+///
+/// Sounds are sent to every client that can hear them, including HLTV, so this covers players
+/// other than the POV, unlike [dod::CurWeapon]. Other weapon sounds, such as reloads, share the
+/// channel, so the shots are an estimate.
+pub fn use_shot_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    let AnalyzerEvent::EngineMessage(EngineMessage::SvcSound(svc_sound)) = event else {
+        return;
+    };
+
+    if svc_sound.channel != CHAN_WEAPON {
+        return;
+    }
+
+    let Some(slot) = ClientSlot::from_entity_index(svc_sound.entity_index) else {
+        return;
+    };
+
+    let time = state.current_time.clone();
+
+    if let Some(player) = state.find_player_by_slot_mut(slot)
+        && player.is_alive()
+    {
+        player.shots.push(time);
+    }
+}
+
+pub(crate) fn reset_shots(state: &mut AnalyzerState) {
+    for player in state.players.iter_mut() {
+        player.shots.clear();
+    }
+}
+
+impl Player {
+    /// Estimates the fraction of shots that hit, assuming every kill took the fewest body hits
+    /// for its weapon.
+    ///
+    /// Kills with weapons without known stats, such as explosives, are left out. Returns [None]
+    /// if no shots were heard.
+    pub fn estimated_accuracy(&self) -> Option<f32> {
+        if self.shots.is_empty() {
+            return None;
+        }
+
+        let hits = self
+            .weapon_breakdown
+            .iter()
+            .filter_map(|(weapon, (kills, teamkills))| {
+                Some(weapon.stats()?.hits_to_kill() * (kills + teamkills))
+            })
+            .sum::<u32>();
+
+        Some((hits as f32 / self.shots.len() as f32).min(1.))
+    }
+}
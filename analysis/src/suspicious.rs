@@ -0,0 +1,240 @@
+use crate::{AnalyzerState, Player, PlayerGlobalId, time::GameTime, ttk::MULTIKILL_WINDOW};
+use dod::Weapon;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Something a player did that may indicate cheating.
+///
+/// These are heuristics based on limited data and are not proof of cheating.
+#[derive(Debug)]
+pub struct SuspiciousEvent {
+    pub time: GameTime,
+    pub player: PlayerGlobalId,
+    pub kind: SuspiciousEventKind,
+}
+
+#[derive(Debug)]
+pub enum SuspiciousEventKind {
+    /// Player moved horizontally faster than the game normally allows, in world units per second.
+    AbnormalSpeed(f32),
+
+    /// Player's view turned much faster than usual for them right before a kill.
+    ViewSnap {
+        /// Angle the view turned in a single sample, in degrees.
+        degrees: f32,
+
+        /// How unusual the turn was compared to the player's other turns, between 0 and 1.
+        confidence: f32,
+    },
+
+    /// Player killed twice with a weapon faster than it can fire.
+    RapidKills {
+        weapon: Weapon,
+
+        /// Time between the two kills.
+        interval: Duration,
+
+        /// Shortest time between the weapon's shots.
+        fire_interval: Duration,
+    },
+}
+
+impl Display for SuspiciousEventKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuspiciousEventKind::AbnormalSpeed(speed) => {
+                write!(f, "Moved at {speed:.0} units/s")
+            }
+
+            SuspiciousEventKind::ViewSnap {
+                degrees,
+                confidence,
+            } => {
+                write!(
+                    f,
+                    "View snapped {degrees:.0}° right before a kill (confidence {:.0}%)",
+                    confidence * 100.
+                )
+            }
+
+            SuspiciousEventKind::RapidKills {
+                weapon,
+                interval,
+                fire_interval,
+            } => {
+                write!(
+                    f,
+                    "Killed twice with {weapon} {} ms apart, faster than it fires ({} ms)",
+                    interval.as_millis(),
+                    fire_interval.as_millis()
+                )
+            }
+        }
+    }
+}
+
+impl SuspiciousEvent {
+    /// Horizontal speed in world units per second above which movement is considered abnormal.
+    ///
+    /// Sprinting stays well below this, but falling and explosions can briefly push players past
+    /// it.
+    const ABNORMAL_SPEED: f32 = 600.;
+
+    /// Time before a kill in which the view is checked for snaps.
+    const SNAP_WINDOW: Duration = Duration::from_millis(300);
+
+    /// Smallest turn in degrees that can be considered a snap.
+    const MIN_SNAP_DEGREES: f32 = 10.;
+
+    /// Number of standard deviations above the player's average turn speed for a snap.
+    const SNAP_DEVIATIONS: f32 = 4.;
+
+    /// Share of a weapon's fire interval below which two kills are too close together, leaving
+    /// room for latency.
+    const RAPID_KILL_RATIO: f32 = 0.8;
+}
+
+impl AnalyzerState {
+    /// Returns heuristically detected events that may indicate cheating, ordered by time.
+    pub fn suspicious_events(&self) -> Vec<SuspiciousEvent> {
+        let mut events = self
+            .players
+            .iter()
+            .flat_map(|player| {
+                player
+                    .movement_per_life()
+                    .into_iter()
+                    .filter_map(|movement| movement.max_speed)
+                    .filter(|(_, speed)| *speed > SuspiciousEvent::ABNORMAL_SPEED)
+                    .map(|(time, speed)| SuspiciousEvent {
+                        time,
+                        player: player.id.clone(),
+                        kind: SuspiciousEventKind::AbnormalSpeed(speed),
+                    })
+            })
+            .chain(self.players.iter().flat_map(view_snaps))
+            .chain(
+                self.players
+                    .iter()
+                    .flat_map(|player| self.rapid_kills(player)),
+            )
+            .collect::<Vec<_>>();
+
+        events.sort_by_key(|event| event.time.viewdemo_offset);
+
+        events
+    }
+
+    /// Finds consecutive kills with a weapon that are closer together than the weapon can fire.
+    fn rapid_kills(&self, player: &Player) -> Vec<SuspiciousEvent> {
+        let kills = self.kills_by(&player.id).collect::<Vec<_>>();
+
+        kills
+            .iter()
+            .enumerate()
+            .filter_map(|(i, kill)| {
+                let fire_interval = kill.weapon.stats()?.fire_interval;
+
+                // Previous kill with the same weapon
+                let previous = kills[..i]
+                    .iter()
+                    .rev()
+                    .find(|other| other.weapon == kill.weapon)?;
+
+                let interval = &kill.time - &previous.time;
+
+                (interval >= MULTIKILL_WINDOW
+                    && interval < fire_interval.mul_f32(SuspiciousEvent::RAPID_KILL_RATIO))
+                .then(|| SuspiciousEvent {
+                    time: kill.time.clone(),
+                    player: player.id.clone(),
+                    kind: SuspiciousEventKind::RapidKills {
+                        weapon: kill.weapon.clone(),
+                        interval,
+                        fire_interval,
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// Finds kills preceded by a turn that is far faster than the player's usual turn speed.
+///
+/// Only available for the player that recorded the demo.
+fn view_snaps(player: &Player) -> Vec<SuspiciousEvent> {
+    // Degrees turned and turn speed in degrees per second between consecutive samples
+    let turns = player
+        .positions
+        .windows(2)
+        .filter_map(|pair| {
+            let elapsed = (&pair[1].time - &pair[0].time).as_secs_f32();
+
+            if elapsed <= 0. {
+                return None;
+            }
+
+            let degrees = angle_between(pair[0].view_angles, pair[1].view_angles);
+
+            Some((&pair[1].time, degrees, degrees / elapsed))
+        })
+        .collect::<Vec<_>>();
+
+    if turns.len() < 2 {
+        return vec![];
+    }
+
+    let mean = turns.iter().map(|(_, _, speed)| speed).sum::<f32>() / turns.len() as f32;
+    let std_dev = (turns
+        .iter()
+        .map(|(_, _, speed)| (speed - mean).powi(2))
+        .sum::<f32>()
+        / turns.len() as f32)
+        .sqrt();
+
+    if std_dev == 0. {
+        return vec![];
+    }
+
+    player
+        .kill_streaks
+        .iter()
+        .flat_map(|streak| streak.kills.iter())
+        .filter_map(|(kill_time, _)| {
+            let window = kill_time
+                .viewdemo_offset
+                .saturating_sub(SuspiciousEvent::SNAP_WINDOW)
+                ..=kill_time.viewdemo_offset;
+
+            let (time, degrees, speed) = turns
+                .iter()
+                .filter(|(time, degrees, _)| {
+                    window.contains(&time.viewdemo_offset)
+                        && *degrees >= SuspiciousEvent::MIN_SNAP_DEGREES
+                })
+                .max_by(|(_, _, l), (_, _, r)| l.total_cmp(r))?;
+
+            let deviations = (speed - mean) / std_dev;
+
+            (deviations >= SuspiciousEvent::SNAP_DEVIATIONS).then(|| SuspiciousEvent {
+                time: (*time).clone(),
+                player: player.id.clone(),
+                kind: SuspiciousEventKind::ViewSnap {
+                    degrees: *degrees,
+                    confidence: (deviations / (2. * SuspiciousEvent::SNAP_DEVIATIONS)).min(1.),
+                },
+            })
+        })
+        .collect()
+}
+
+/// Returns the angle in degrees between two view directions given as pitch, yaw, and roll.
+fn angle_between(a: [f32; 3], b: [f32; 3]) -> f32 {
+    // Yaw wraps around, so a turn from 359° to 1° is only 2°
+    let wrap = |degrees: f32| (degrees + 180.).rem_euclid(360.) - 180.;
+
+    let pitch = wrap(b[0] - a[0]);
+    let yaw = wrap(b[1] - a[1]);
+
+    (pitch.powi(2) + yaw.powi(2)).sqrt()
+}
@@ -0,0 +1,164 @@
+use crate::{AnalyzerEvent, AnalyzerState, Player, PlayerGlobalId, time::GameTime};
+use dod::{Team, UserMessage};
+use std::time::Duration;
+
+/// Longest time between the first and last player changing sides for them to count as one swap.
+const SWAP_WINDOW: Duration = Duration::from_secs(30);
+
+/// A player moving directly between the Allies and Axis teams.
+#[derive(Debug)]
+pub(crate) struct SideSwitch {
+    time: GameTime,
+    player: PlayerGlobalId,
+}
+
+/// Detects the teams trading sides, e.g. at half-time with `mp_clan` swaps.
+///
+/// A swap is recorded when at least half of the players on both teams move to the other team within
+/// a short window. This must run before the scoreboard updates so the players' previous teams are
+/// still known.
+pub fn use_team_swap_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    let (client_index, team) = match event {
+        AnalyzerEvent::UserMessage(UserMessage::PTeam(p_team)) => {
+            (p_team.client_index, &p_team.team)
+        }
+        AnalyzerEvent::UserMessage(UserMessage::ScoreInfo(score_info)) => {
+            (score_info.client_index, &score_info.team)
+        }
+        _ => return,
+    };
+
+    let Some(player) = state.find_player_by_entity_index(client_index) else {
+        return;
+    };
+
+    let switched_sides = matches!(
+        (&player.team, team),
+        (Some(Team::Allies), Team::Axis) | (Some(Team::Axis), Team::Allies)
+    );
+
+    if !switched_sides {
+        return;
+    }
+
+    let player = player.id.clone();
+    let current_time = state.current_time.clone();
+
+    state
+        .pending_side_switches
+        .retain(|switch| &current_time - &switch.time <= SWAP_WINDOW && switch.player != player);
+
+    state.pending_side_switches.push(SideSwitch {
+        time: current_time,
+        player,
+    });
+
+    let playing = state
+        .players
+        .iter()
+        .filter(|player| matches!(player.team, Some(Team::Allies | Team::Axis)))
+        .count();
+
+    if state.pending_side_switches.len() >= playing.div_ceil(2).max(2) {
+        let swap_time = state.pending_side_switches[0].time.clone();
+
+        state.team_swaps.push(swap_time);
+        state.pending_side_switches.clear();
+    }
+}
+
+pub(crate) fn reset_team_swaps(state: &mut AnalyzerState) {
+    state.team_swaps.clear();
+    state.pending_side_switches.clear();
+}
+
+fn swap_sides(team: &Team, swaps: usize) -> Team {
+    match (team, swaps % 2) {
+        (Team::Allies, 1) => Team::Axis,
+        (Team::Axis, 1) => Team::Allies,
+        (team, _) => team.clone(),
+    }
+}
+
+impl AnalyzerState {
+    /// Returns the side played at the [GameTime] by the players that started on a team.
+    pub fn side_at(&self, starting_team: &Team, time: &GameTime) -> Team {
+        let swaps = self
+            .team_swaps
+            .iter()
+            .filter(|swap| swap.viewdemo_offset <= time.viewdemo_offset)
+            .count();
+
+        swap_sides(starting_team, swaps)
+    }
+
+    /// Returns the side played at the end of the demo by the players that started on a team.
+    pub fn final_side(&self, starting_team: &Team) -> Team {
+        swap_sides(starting_team, self.team_swaps.len())
+    }
+
+    /// Returns the score of the players that started on a team over time, following them across
+    /// [team swaps](AnalyzerState::team_swaps).
+    ///
+    /// Only points gained are added up, so scores that reset at a swap carry over instead.
+    pub fn clan_score_timeline(&self, starting_team: &Team) -> Vec<(GameTime, i32)> {
+        let mut score = 0;
+
+        self.team_scores
+            .changes()
+            .filter(|change| change.delta > 0)
+            .filter(|change| self.side_at(starting_team, change.time) == *change.team)
+            .map(|change| {
+                score += change.delta;
+
+                (change.time.clone(), score)
+            })
+            .collect()
+    }
+
+    /// Returns the number of kills made by the players on a side over time, excluding teamkills
+    /// and suicides.
+    pub fn team_kill_timeline(&self, team: &Team) -> Vec<(GameTime, i32)> {
+        self.kill_timeline(|_| team.clone())
+    }
+
+    /// Returns the number of kills made by the players that started on a team over time, following
+    /// them across [team swaps](AnalyzerState::team_swaps).
+    pub fn clan_kill_timeline(&self, starting_team: &Team) -> Vec<(GameTime, i32)> {
+        self.kill_timeline(|time| self.side_at(starting_team, time))
+    }
+
+    fn kill_timeline(&self, side: impl Fn(&GameTime) -> Team) -> Vec<(GameTime, i32)> {
+        let mut kills = 0;
+
+        self.kills
+            .iter()
+            .filter(|kill| kill.is_enemy_kill())
+            .filter(|kill| {
+                kill.killer
+                    .as_ref()
+                    .and_then(|killer| self.find_player_by_id(killer))
+                    .and_then(|killer| self.side_of(killer, &kill.time))
+                    .is_some_and(|team| team == side(&kill.time))
+            })
+            .map(|kill| {
+                kills += 1;
+
+                (kill.time.clone(), kills)
+            })
+            .collect()
+    }
+
+    /// Returns the side a player played on at the [GameTime], following team swaps.
+    pub(crate) fn side_of(&self, player: &Player, time: &GameTime) -> Option<Team> {
+        let team = player
+            .team
+            .clone()
+            .filter(|team| *team != Team::Spectators)?;
+
+        // Swapping sides twice is a no-op, so this undoes the swaps to find the starting team
+        let starting_team = self.final_side(&team);
+
+        Some(self.side_at(&starting_team, time))
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{AnalyzerEvent, AnalyzerState, PlayerGlobalId, kill::killer_index, time::GameTime};
+use dod::{UserMessage, Weapon};
+
+/// A player killing a teammate, kept so admins can review each one, e.g. to hand out penalties.
+#[derive(Debug)]
+pub struct Teamkill {
+    pub time: GameTime,
+    pub killer: PlayerGlobalId,
+    pub victim: PlayerGlobalId,
+    pub weapon: Weapon,
+}
+
+/// Records every kill of a player by a teammate.
+pub fn use_teamkill_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    if let AnalyzerEvent::UserMessage(UserMessage::DeathMsg(death_msg)) = event {
+        let killer = state.find_player_by_entity_index(killer_index(death_msg));
+        let victim = state.find_player_by_entity_index(death_msg.victim_client_index);
+
+        let (Some(killer), Some(victim)) = (killer, victim) else {
+            return;
+        };
+
+        if killer == victim || killer.team.is_none() || killer.team != victim.team {
+            return;
+        }
+
+        let teamkill = Teamkill {
+            time: state.current_time.clone(),
+            killer: killer.id.clone(),
+            victim: victim.id.clone(),
+            weapon: death_msg.weapon.clone(),
+        };
+
+        state.teamkills.push(teamkill);
+    }
+}
+
+pub(crate) fn reset_teamkills(state: &mut AnalyzerState) {
+    state.teamkills.clear();
+}
+
+impl AnalyzerState {
+    /// Returns the number of teammates the player killed.
+    pub fn teamkills_by(&self, player: &PlayerGlobalId) -> usize {
+        self.teamkills
+            .iter()
+            .filter(|teamkill| teamkill.killer == *player)
+            .count()
+    }
+}
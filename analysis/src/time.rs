@@ -1,17 +1,76 @@
 use crate::{AnalyzerEvent, AnalyzerState};
 use dem::types::EngineMessage;
+use serde::{Deserialize, Serialize};
 use std::{ops::Sub, time::Duration};
 
 /// A moment in time when something happened in game.
+///
+/// Demos keep three clocks that drift apart, e.g. when the recording starts after the map loaded,
+/// when an HLTV client records with a delay, or when the demo was stopped and recorded again.
+/// Reports pick one with [Clock].
 #[derive(Clone, Debug, Default)]
 pub struct GameTime {
-    /// Timestamp that represents the amount opf time relative to 0 (recording start).
+    /// Timestamp that represents the amount of time relative to 0 (recording start), carried over
+    /// from one playback segment to the next.
     pub real_offset: Duration,
 
-    /// Timestamp that represents the value shown in the `viewdemo` window.
+    /// Timestamp of the demo frame, as stamped by the server, which starts over with each playback
+    /// segment.
+    pub server_offset: Duration,
+
+    /// Timestamp that represents the value shown in the `viewdemo` window, as sent in the
+    /// `svc_time` messages of each network frame.
+    ///
+    /// This is the one used for durations and ordering in the analysis.
     pub viewdemo_offset: Duration,
 }
 
+impl GameTime {
+    /// Returns the timestamp on the clock.
+    pub fn offset(&self, clock: &Clock) -> Duration {
+        match clock {
+            Clock::Viewdemo => self.viewdemo_offset,
+            Clock::Recording => self.real_offset,
+            Clock::Server => self.server_offset,
+        }
+    }
+
+    /// Returns the console commands that play the demo and seek to this moment, on the server
+    /// clock, e.g. `viewdemo demo.dem; demo_jump 1234`.
+    pub fn to_viewdemo_command(&self, demo_name: &str) -> String {
+        let demo_name = if demo_name.contains(char::is_whitespace) {
+            format!("\"{demo_name}\"")
+        } else {
+            demo_name.to_string()
+        };
+
+        format!(
+            "viewdemo {demo_name}; demo_jump {}",
+            self.viewdemo_offset.as_secs()
+        )
+    }
+}
+
+/// Clock used to show a [GameTime] in reports.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum Clock {
+    /// Time shown in the `viewdemo` window. Use this to seek to a moment in the demo.
+    #[default]
+    Viewdemo,
+
+    /// Time since the demo started recording, e.g. to line up with a video of the recording.
+    Recording,
+
+    /// Time stamped on each frame of the demo by the server, which starts over when the demo was
+    /// stopped and recorded again.
+    Server,
+}
+
+impl Clock {
+    /// Every clock, in the order reports show them side by side.
+    pub const ALL: [Clock; 3] = [Clock::Viewdemo, Clock::Recording, Clock::Server];
+}
+
 impl Sub<GameTime> for GameTime {
     type Output = Duration;
 
@@ -36,6 +95,19 @@ pub fn use_timing_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
     } else if let AnalyzerEvent::Frame(frame) = event
         && let Ok(offset) = Duration::try_from_secs_f32(frame.time)
     {
-        state.current_time.real_offset = offset;
+        // Frame times may restart with a new segment, so the recording clock carries on from the
+        // end of the previous one instead of going back
+        if std::mem::take(&mut state.segment_started) {
+            let current = state.current_time.real_offset;
+
+            if state.segment_offset + offset < current {
+                state.segment_offset = current - offset;
+            }
+        }
+
+        state.current_time.server_offset = offset;
+        state.current_time.real_offset = state.segment_offset + offset;
+    } else if let AnalyzerEvent::SegmentStart = event {
+        state.segment_started = true;
     }
 }
@@ -0,0 +1,52 @@
+use crate::{AnalyzerEvent, AnalyzerState, Round, time::GameTime};
+use dod::UserMessage;
+use std::time::Duration;
+
+/// Records the time left on the map, as sent when a client asks for it with `timeleft`.
+pub fn use_time_left_updates(state: &mut AnalyzerState, event: &AnalyzerEvent) {
+    if let AnalyzerEvent::UserMessage(UserMessage::TimeLeft(time_left)) = event {
+        state
+            .time_left_samples
+            .push((state.current_time.clone(), time_left.0));
+    }
+}
+
+/// The map timer restarts when the clan match goes live, so earlier times left no longer apply.
+pub(crate) fn reset_time_left(state: &mut AnalyzerState) {
+    state.time_left_samples.clear();
+}
+
+impl AnalyzerState {
+    /// Returns true if the time left on the map was sent at least once.
+    pub fn is_time_left_known(&self) -> bool {
+        !self.time_left_samples.is_empty()
+    }
+
+    /// Returns the time that was left on the map at a moment, counted from the time left sent
+    /// closest to it, or [None] if it was never sent.
+    ///
+    /// The map timer runs on the server's clock, so it keeps running between rounds.
+    pub fn time_left_at(&self, time: &GameTime) -> Option<Duration> {
+        let (sample_time, time_left) = self
+            .time_left_samples
+            .iter()
+            .min_by_key(|(sample, _)| sample.viewdemo_offset.abs_diff(time.viewdemo_offset))?;
+
+        if time.viewdemo_offset >= sample_time.viewdemo_offset {
+            Some(time_left.saturating_sub(time - sample_time))
+        } else {
+            Some(*time_left + (sample_time - time))
+        }
+    }
+
+    /// Returns the time that was left on the map when the round ended, to tell rounds that were
+    /// won with time to spare from those that went down to the wire.
+    ///
+    /// Returns [None] for a round still in progress, or if the time left was never sent.
+    pub fn time_left_at_round_end(&self, round: &Round) -> Option<Duration> {
+        match round {
+            Round::Completed { end_time, .. } => self.time_left_at(end_time),
+            Round::Active { .. } => None,
+        }
+    }
+}
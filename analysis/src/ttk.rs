@@ -0,0 +1,73 @@
+use crate::{AnalyzerState, Kill, PlayerGlobalId};
+use dod::Weapon;
+use std::time::Duration;
+
+/// Expected and observed kill timing of a player with a single weapon.
+#[derive(Debug)]
+pub struct WeaponTiming {
+    pub weapon: Weapon,
+    pub kills: usize,
+
+    /// Shortest time to kill a player with full health using body hits.
+    pub time_to_kill: Duration,
+
+    /// Shortest time between the weapon's shots, and so between two kills.
+    pub fire_interval: Duration,
+
+    /// Shortest time observed between two consecutive kills with the weapon.
+    pub fastest_follow_up: Option<Duration>,
+}
+
+/// Kills closer together than this are treated as a single shot hitting several players.
+pub(crate) const MULTIKILL_WINDOW: Duration = Duration::from_millis(50);
+
+impl AnalyzerState {
+    /// Returns the kill timing of a player for each weapon with known stats, most used first.
+    pub fn weapon_timings(&self, player: &PlayerGlobalId) -> Vec<WeaponTiming> {
+        let mut timings: Vec<WeaponTiming> = vec![];
+
+        for kill in self.kills_by(player) {
+            let Some(stats) = kill.weapon.stats() else {
+                continue;
+            };
+
+            if timings.iter().any(|timing| timing.weapon == kill.weapon) {
+                continue;
+            }
+
+            let kills = self
+                .kills_by(player)
+                .filter(|other| other.weapon == kill.weapon)
+                .collect::<Vec<_>>();
+
+            timings.push(WeaponTiming {
+                weapon: kill.weapon.clone(),
+                kills: kills.len(),
+                time_to_kill: stats.time_to_kill(),
+                fire_interval: stats.fire_interval,
+                fastest_follow_up: follow_ups(&kills).min(),
+            });
+        }
+
+        timings.sort_by(|l, r| l.kills.cmp(&r.kills).reverse());
+
+        timings
+    }
+
+    pub(crate) fn kills_by<'a>(
+        &'a self,
+        player: &'a PlayerGlobalId,
+    ) -> impl Iterator<Item = &'a Kill> + 'a {
+        self.kills
+            .iter()
+            .filter(move |kill| kill.killer.as_ref() == Some(player) && kill.victim != *player)
+    }
+}
+
+/// Returns the time between each pair of consecutive kills, leaving out multikills.
+fn follow_ups<'a>(kills: &'a [&'a Kill]) -> impl Iterator<Item = Duration> + 'a {
+    kills
+        .windows(2)
+        .map(|pair| &pair[1].time - &pair[0].time)
+        .filter(|interval| *interval >= MULTIKILL_WINDOW)
+}
@@ -0,0 +1,45 @@
+use crate::{AnalyzerState, Capture, Kill, PlayerGlobalId, Round};
+use std::mem;
+
+/// Results gathered before the clan match went live, such as warmup frags and practice rounds.
+#[derive(Debug, Default)]
+pub struct WarmupStats {
+    pub kills: Vec<Kill>,
+    pub captures: Vec<Capture>,
+    pub rounds: Vec<Round>,
+}
+
+impl WarmupStats {
+    /// Returns the number of kills the player made during warmup, excluding teamkills and
+    /// suicides.
+    pub fn kills_by(&self, player: &PlayerGlobalId) -> usize {
+        self.kills
+            .iter()
+            .filter(|kill| kill.is_enemy_kill() && kill.killer.as_ref() == Some(player))
+            .count()
+    }
+
+    /// Returns the number of times the player died during warmup.
+    pub fn deaths_of(&self, player: &PlayerGlobalId) -> usize {
+        self.kills
+            .iter()
+            .filter(|kill| kill.victim == *player)
+            .count()
+    }
+}
+
+/// Moves the results gathered so far into the warmup, before they are reset for the match.
+///
+/// Results from earlier restarts are kept as well.
+pub(crate) fn stash_warmup(state: &mut AnalyzerState) {
+    state.warmup.kills.append(&mut state.kills);
+    state.warmup.captures.append(&mut state.captures);
+    state.warmup.rounds.extend(mem::take(&mut state.rounds));
+}
+
+impl AnalyzerState {
+    /// Returns the results gathered before the clan match went live, if one was detected.
+    pub fn warmup_stats(&self) -> Option<&WarmupStats> {
+        self.match_start_time.as_ref().map(|_| &self.warmup)
+    }
+}
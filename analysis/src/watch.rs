@@ -0,0 +1,146 @@
+use crate::{AnalyzerState, Kill, Player, time::GameTime};
+use std::fmt::{Display, Formatter};
+
+/// A moment worth watching from a player's point of view.
+#[derive(Debug)]
+pub struct Highlight {
+    pub time: GameTime,
+    pub kind: HighlightKind,
+}
+
+#[derive(Debug)]
+pub enum HighlightKind {
+    /// Several kills without dying, with the number of kills.
+    KillStreak(usize),
+
+    /// Capture of the named objective.
+    Capture(String),
+
+    /// First kill of a round.
+    FirstBlood,
+
+    /// Last kill of a round that was won by a team.
+    RoundEnder,
+
+    /// Kill from far away, in meters.
+    LongRangeKill(f32),
+}
+
+impl HighlightKind {
+    /// Returns how much the moment adds to the player's rank.
+    pub fn weight(&self) -> u32 {
+        match self {
+            HighlightKind::KillStreak(kills) => 2 * *kills as u32,
+            HighlightKind::Capture(_) => 3,
+            HighlightKind::FirstBlood | HighlightKind::RoundEnder => 2,
+            HighlightKind::LongRangeKill(_) => 1,
+        }
+    }
+}
+
+impl Display for HighlightKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HighlightKind::KillStreak(kills) => write!(f, "{kills} kill streak"),
+            HighlightKind::Capture(point_name) => write!(f, "Captured {point_name}"),
+            HighlightKind::FirstBlood => write!(f, "First blood"),
+            HighlightKind::RoundEnder => write!(f, "Round ender"),
+            HighlightKind::LongRangeKill(meters) => write!(f, "{meters:.0} m kill"),
+        }
+    }
+}
+
+/// A player whose point of view is worth watching, with their highlights.
+#[derive(Debug)]
+pub struct PlayerToWatch<'a> {
+    pub player: &'a Player,
+
+    /// Sum of the weights of the highlights, used to rank players.
+    pub score: u32,
+
+    /// Highlights ordered by time.
+    pub highlights: Vec<Highlight>,
+}
+
+/// Fewest kills in a streak for it to be a highlight.
+const MIN_HIGHLIGHT_STREAK: usize = 3;
+
+impl AnalyzerState {
+    /// Returns the players with highlights, ranked by how much there is to watch.
+    ///
+    /// Useful for picking points of view to follow in HLTV demos.
+    pub fn players_to_watch(&self) -> Vec<PlayerToWatch<'_>> {
+        let first_bloods = self.first_bloods();
+        let round_enders = self.round_enders();
+
+        let mut players = self
+            .players
+            .iter()
+            .map(|player| {
+                let streaks = player
+                    .notable_kill_streaks(MIN_HIGHLIGHT_STREAK)
+                    .filter_map(|(_, streak)| {
+                        let (time, _) = streak.kills.first()?;
+
+                        Some(Highlight {
+                            time: time.clone(),
+                            kind: HighlightKind::KillStreak(streak.kills.len()),
+                        })
+                    });
+
+                let captures = self
+                    .captures
+                    .iter()
+                    .filter(|capture| capture.player == player.id)
+                    .map(|capture| Highlight {
+                        time: capture.time.clone(),
+                        kind: HighlightKind::Capture(capture.point_name.clone()),
+                    });
+
+                let kill_moments = |kills: &[&Kill], kind: fn() -> HighlightKind| {
+                    kills
+                        .iter()
+                        .filter(|kill| kill.killer.as_ref() == Some(&player.id))
+                        .map(|kill| Highlight {
+                            time: kill.time.clone(),
+                            kind: kind(),
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                let long_range_kills = self
+                    .kill_distances
+                    .iter()
+                    .filter(|kill| kill.killer == player.id && kill.is_long_range())
+                    .map(|kill| Highlight {
+                        time: kill.time.clone(),
+                        kind: HighlightKind::LongRangeKill(kill.meters()),
+                    });
+
+                let mut highlights = streaks
+                    .chain(captures)
+                    .chain(kill_moments(&first_bloods, || HighlightKind::FirstBlood))
+                    .chain(kill_moments(&round_enders, || HighlightKind::RoundEnder))
+                    .chain(long_range_kills)
+                    .collect::<Vec<_>>();
+
+                highlights.sort_by_key(|highlight| highlight.time.viewdemo_offset);
+
+                PlayerToWatch {
+                    player,
+                    score: highlights
+                        .iter()
+                        .map(|highlight| highlight.kind.weight())
+                        .sum(),
+                    highlights,
+                }
+            })
+            .filter(|player| !player.highlights.is_empty())
+            .collect::<Vec<_>>();
+
+        // Stable, so ties keep the scoreboard order
+        players.sort_by(|l, r| l.score.cmp(&r.score).reverse());
+
+        players
+    }
+}
@@ -0,0 +1,168 @@
+//! Ids used by the mod to identify teams, classes, weapons, and ammo in messages.
+
+use crate::{Ammo, Class, Team, Weapon};
+
+/// Id of each [Team].
+pub const TEAM_IDS: [(u8, Team); 3] = [(1, Team::Allies), (2, Team::Axis), (3, Team::Spectators)];
+
+// FIXME Inaccurate!
+/// Id of each [Class].
+pub const CLASS_IDS: [(u8, Class); 27] = [
+    (1, Class::Rifleman),
+    (2, Class::StaffSergeant),
+    (3, Class::MasterSergeant),
+    (4, Class::Sergeant),
+    (5, Class::Sniper),
+    (6, Class::SupportInfantry),
+    (7, Class::MachineGunner),
+    (8, Class::Bazooka),
+    (9, Class::Mortar),
+    (10, Class::Grenadier),
+    (11, Class::Stosstruppe),
+    (12, Class::Unteroffizer),
+    (13, Class::Sturmtruppe),
+    (14, Class::Scharfschutze),
+    (15, Class::Fg42Zweibein),
+    (16, Class::Fg42Zielfernrohr),
+    (17, Class::MG34Schutze),
+    (18, Class::MG42Schutze),
+    (19, Class::Panzerschreck),
+    (20, Class::AxisMortar),
+    (21, Class::BritishRifleman),
+    (22, Class::SergeantMajor),
+    (23, Class::Marksman),
+    (24, Class::Gunner),
+    (25, Class::RocketInfantry),
+    (26, Class::BritishMortar),
+    (27, Class::Random),
+];
+
+/// Id of each [Weapon], except for [Weapon::Unknown].
+pub const WEAPON_IDS: [(u8, Weapon); 38] = [
+    (1, Weapon::Kabar),
+    (2, Weapon::GermanKnife),
+    (3, Weapon::M1911),
+    (4, Weapon::Luger),
+    (5, Weapon::Garand),
+    (6, Weapon::ScopedK98),
+    (7, Weapon::Thompson),
+    (8, Weapon::Stg44),
+    (9, Weapon::Springfield),
+    (10, Weapon::K98),
+    (11, Weapon::Bar),
+    (12, Weapon::Mp40),
+    (13, Weapon::Mk2Grenade),
+    (14, Weapon::StickGrenade),
+    (17, Weapon::Mg42),
+    (18, Weapon::Browning30Cal),
+    (19, Weapon::Spade),
+    (20, Weapon::M1Carbine),
+    (21, Weapon::Mg34),
+    (22, Weapon::GreaseGun),
+    (23, Weapon::Fg42),
+    (24, Weapon::K43),
+    (25, Weapon::LeeEnfield),
+    (26, Weapon::Sten),
+    (27, Weapon::Bren),
+    (28, Weapon::Webley),
+    (29, Weapon::Bazooka),
+    (30, Weapon::Panzerschreck),
+    (31, Weapon::Piat),
+    (32, Weapon::Mortar),
+    (35, Weapon::ScopedFg42),
+    (36, Weapon::M1A1Carbine),
+    (37, Weapon::K98Bayonet),
+    (38, Weapon::ScopedLeeEnfield),
+    (39, Weapon::MillsBomb),
+    (40, Weapon::BritishKnife),
+    (42, Weapon::ButtStock),
+    (43, Weapon::EnfieldBayonet),
+];
+
+/// Id of each kind of [Ammo], except for [Ammo::Unknown].
+pub const AMMO_IDS: [(u8, Ammo); 10] = [
+    (1, Ammo::Smg),
+    (2, Ammo::AltRifle),
+    (3, Ammo::Rifle),
+    (4, Ammo::Pistol),
+    (5, Ammo::Springfield),
+    (6, Ammo::Heavy),
+    (7, Ammo::Mg42),
+    (8, Ammo::Browning30Cal),
+    (9, Ammo::Rocket),
+    (u8::MAX, Ammo::Infinite),
+];
+
+fn find_by_id<T: Clone>(table: &[(u8, T)], value: u8) -> Option<T> {
+    table
+        .iter()
+        .find(|(id, _)| *id == value)
+        .map(|(_, item)| item.clone())
+}
+
+fn find_id<T: PartialEq>(table: &[(u8, T)], value: &T) -> Option<u8> {
+    table
+        .iter()
+        .find(|(_, item)| item == value)
+        .map(|(id, _)| *id)
+}
+
+impl TryFrom<u8> for Team {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        find_by_id(&TEAM_IDS, value).ok_or(())
+    }
+}
+
+impl From<&Team> for u8 {
+    fn from(value: &Team) -> Self {
+        find_id(&TEAM_IDS, value).expect("every team has an id")
+    }
+}
+
+impl TryFrom<u8> for Class {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        find_by_id(&CLASS_IDS, value).ok_or(())
+    }
+}
+
+impl From<&Class> for u8 {
+    fn from(value: &Class) -> Self {
+        find_id(&CLASS_IDS, value).expect("every class has an id")
+    }
+}
+
+/// Ids without a known weapon are [Weapon::Unknown].
+impl From<u8> for Weapon {
+    fn from(value: u8) -> Self {
+        find_by_id(&WEAPON_IDS, value).unwrap_or(Weapon::Unknown(value))
+    }
+}
+
+impl From<&Weapon> for u8 {
+    fn from(value: &Weapon) -> Self {
+        match value {
+            Weapon::Unknown(id) => *id,
+            _ => find_id(&WEAPON_IDS, value).expect("every known weapon has an id"),
+        }
+    }
+}
+
+/// Ids without a known kind of ammo are [Ammo::Unknown].
+impl From<u8> for Ammo {
+    fn from(value: u8) -> Self {
+        find_by_id(&AMMO_IDS, value).unwrap_or(Ammo::Unknown)
+    }
+}
+
+/// Fails for [Ammo::Unknown], since the id it was read from is not kept.
+impl TryFrom<&Ammo> for u8 {
+    type Error = ();
+
+    fn try_from(value: &Ammo) -> Result<Self, Self::Error> {
+        find_id(&AMMO_IDS, value).ok_or(())
+    }
+}
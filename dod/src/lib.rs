@@ -2,6 +2,18 @@
 
 #![allow(dead_code)]
 
+mod ids;
+mod registry;
+
+#[cfg(feature = "weapon-stats")]
+mod weapon_stats;
+
+pub use ids::{AMMO_IDS, CLASS_IDS, TEAM_IDS, WEAPON_IDS};
+pub use registry::{MESSAGES, MessageInfo, MessageKind, MessageSize};
+
+#[cfg(feature = "weapon-stats")]
+pub use weapon_stats::WeaponStats;
+
 use nom::{
     IResult, Parser,
     branch::alt,
@@ -12,16 +24,67 @@ use nom::{
     number::complete::{le_i8, le_i16, le_i32, le_u8, le_u16},
     sequence::terminated,
 };
-use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
 use std::str::from_utf8;
 use std::time::Duration;
 
 pub enum Error {
+    /// The message is known, but its payload could not be parsed.
     ParserError,
+
+    /// The message is not one this crate can parse, such as those registered by server plugins.
+    UnknownMessage,
+}
+
+/// Single-byte character encoding used to decode strings that are not valid UTF-8.
+///
+/// Clients send names and chat in the system codepage, so extended characters common in European
+/// clan tags (`ö`, `ß`, `é`) are usually not valid UTF-8.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Codepage {
+    /// ISO-8859-1, which maps every byte to the code point with the same value.
+    Latin1 = 0,
+
+    /// Windows-1252, a superset of [Codepage::Latin1] used by most Western European clients.
+    #[default]
+    Windows1252 = 1,
+}
+
+/// Characters for the 0x80 - 0x9F range of [Codepage::Windows1252]. Undefined bytes are mapped to
+/// the same code point, as in [Codepage::Latin1].
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+impl Codepage {
+    /// Decodes every byte as a single character of the codepage.
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|byte| match (self, byte) {
+                (Self::Windows1252, 0x80..=0x9F) => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+                _ => *byte as char,
+            })
+            .collect()
+    }
+}
+
+/// Decodes bytes as UTF-8, or with the [Codepage] if they are not valid UTF-8.
+pub fn decode_string(bytes: &[u8], codepage: Codepage) -> String {
+    match from_utf8(bytes) {
+        Ok(str) => str.to_string(),
+        Err(_) => codepage.decode(bytes),
+    }
 }
 
 /// Version of Day of Defeat that was used to record the demo.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Version {
+    /// Last public beta of the mod before the retail release.
+    Beta3_1,
     V1_0,
     V1_1,
     V1_1b,
@@ -30,6 +93,47 @@ pub enum Version {
     V1_3,
 }
 
+impl Version {
+    /// Estimates the version of the mod from the network protocol used by the engine.
+    ///
+    /// This is coarse: the engine protocol changed far less often than the mod did, so multiple
+    /// versions share a protocol number. The earliest version for each protocol is returned, which
+    /// is enough to pick a compatible set of message parsers.
+    ///
+    /// - 46 and below: beta releases on the original engine
+    /// - 47: retail releases before Steam
+    /// - 48 and above: Steam releases
+    pub fn from_network_protocol(network_protocol: i32) -> Self {
+        match network_protocol {
+            ..=46 => Self::Beta3_1,
+            47 => Self::V1_0,
+            _ => Self::V1_3,
+        }
+    }
+
+    /// Returns true if the version uses the message set from before [ScoreShort] was introduced.
+    pub fn is_legacy(&self) -> bool {
+        matches!(
+            self,
+            Self::Beta3_1 | Self::V1_0 | Self::V1_1 | Self::V1_1b | Self::V1_1c
+        )
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Beta3_1 => "beta 3.1",
+            Self::V1_0 => "1.0",
+            Self::V1_1 => "1.1",
+            Self::V1_1b => "1.1b",
+            Self::V1_1c => "1.1c",
+            Self::V1_2 => "1.2",
+            Self::V1_3 => "1.3",
+        })
+    }
+}
+
 /// Kinds of messages registered by the mod.
 #[derive(Debug)]
 pub enum UserMessage {
@@ -75,6 +179,7 @@ pub enum UserMessage {
     RoundState(RoundState),
     SayText(SayText),
     Scope(Scope),
+    ScoreInfo(ScoreInfo),
     ScoreShort(ScoreShort),
     ScreenFade(ScreenFade),
     ScreenShake(ScreenShake),
@@ -112,7 +217,7 @@ pub enum Team {
     Spectators,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Class {
     AxisMortar,
     Bazooka,
@@ -143,7 +248,8 @@ pub enum Class {
     Unteroffizer,
 }
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
 pub enum Weapon {
     Kabar = 1,
     GermanKnife = 2,
@@ -188,6 +294,10 @@ pub enum Weapon {
     // 41 ?
     ButtStock = 42, // Same id for Garand/K43
     EnfieldBayonet = 43,
+
+    /// Weapon with an id that is not in [WEAPON_IDS], like the ones marked above, so kills made
+    /// with it are still counted.
+    Unknown(u8),
 }
 
 impl Weapon {
@@ -197,10 +307,101 @@ impl Weapon {
             Self::MillsBomb | Self::Mk2Grenade | Self::StickGrenade
         )
     }
+
+    /// Returns the kind of weapon, to group weapons that play alike.
+    pub fn category(&self) -> WeaponCategory {
+        match self {
+            Self::Garand
+            | Self::K43
+            | Self::K98
+            | Self::ScopedK98
+            | Self::Springfield
+            | Self::LeeEnfield
+            | Self::ScopedLeeEnfield
+            | Self::ScopedFg42
+            | Self::M1Carbine
+            | Self::M1A1Carbine => WeaponCategory::Rifle,
+
+            Self::Thompson | Self::Mp40 | Self::Sten | Self::GreaseGun | Self::Stg44 => {
+                WeaponCategory::Smg
+            }
+
+            Self::Bar | Self::Bren | Self::Fg42 | Self::Mg34 | Self::Mg42 | Self::Browning30Cal => {
+                WeaponCategory::Mg
+            }
+
+            Self::Mk2Grenade
+            | Self::StickGrenade
+            | Self::MillsBomb
+            | Self::Bazooka
+            | Self::Panzerschreck
+            | Self::Piat
+            | Self::Mortar => WeaponCategory::Explosive,
+
+            Self::Kabar
+            | Self::GermanKnife
+            | Self::BritishKnife
+            | Self::Spade
+            | Self::K98Bayonet
+            | Self::EnfieldBayonet
+            | Self::ButtStock => WeaponCategory::Melee,
+
+            Self::M1911 | Self::Luger | Self::Webley => WeaponCategory::Pistol,
+
+            Self::Unknown(_) => WeaponCategory::Other,
+        }
+    }
+}
+
+/// Kind of [Weapon], in the order they are usually listed.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum WeaponCategory {
+    /// Bolt-action and semi-automatic rifles, including scoped ones and carbines.
+    Rifle,
+
+    /// Submachine guns and assault rifles.
+    Smg,
+
+    /// Machine guns and automatic rifles.
+    Mg,
+
+    /// Grenades, rockets, and mortars.
+    Explosive,
+
+    /// Knives, spades, bayonets, and rifle butts.
+    Melee,
+
+    Pistol,
+
+    /// Weapons that are not known.
+    Other,
+}
+
+impl Display for WeaponCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rifle => write!(f, "Rifles"),
+            Self::Smg => write!(f, "SMGs"),
+            Self::Mg => write!(f, "MGs"),
+            Self::Explosive => write!(f, "Explosives"),
+            Self::Melee => write!(f, "Melee"),
+            Self::Pistol => write!(f, "Pistols"),
+            Self::Other => write!(f, "Other"),
+        }
+    }
+}
+
+impl Display for Weapon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown(id) => write!(f, "Unknown ({id})"),
+            _ => std::fmt::Debug::fmt(self, f),
+        }
+    }
 }
 
 /// Ammunition used by a [Weapon].
-#[derive(Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Ammo {
     Unknown,
 
@@ -681,6 +882,10 @@ pub struct Scope {}
 
 /// Possibly deprecated and replaced by [ScoreShort].
 ///
+/// Only parsed for [Version::is_legacy] demos, which do not send [ScoreShort].
+///
+/// - Length: 6
+///
 /// CDoDTeamPlay::InitHUD
 /// CDoDTeamPlay::UpdateData
 #[derive(Debug)]
@@ -717,18 +922,30 @@ pub struct ScoreShort {
 /// Sent when the screen should fade to a color.
 #[derive(Debug)]
 pub struct ScreenFade {
-    duration: u16,  // Sometimes in 1<<12, usually not
-    hold_time: u16, // Sometimes in 1<<12, usually not
-    flags: u16,
-    color: (u8, u8, u8, u8),
+    /// Time taken to fade.
+    pub duration: Duration, // u16 in 1<<12
+
+    /// Time the color is held after fading.
+    pub hold_time: Duration, // u16 in 1<<12
+
+    /// `0x1` fades out instead of in, `0x2` modulates the color instead of blending it, and `0x4`
+    /// holds the color until the next fade.
+    pub flags: u16,
+
+    /// RGBA color faded to.
+    pub color: (u8, u8, u8, u8),
 }
 
 /// Sent when the POV should render a screen shake animation, such as after a grenade explosion.
 #[derive(Debug)]
 pub struct ScreenShake {
-    amplitude: u16,
-    duration: Duration,
-    frequency: u16,
+    /// Maximum distance the view moves, in units.
+    pub amplitude: f32, // u16 in 1<<12
+
+    pub duration: Duration, // u16 in 1<<12
+
+    /// Number of times the view moves each second.
+    pub frequency: f32, // u16 in 1<<8
 }
 
 /// Sent when the client connects to a server.
@@ -790,8 +1007,10 @@ pub struct TeamScore {
     pub score: u16,
 }
 
+/// Sent when the client should print a message, usually a localization token with arguments.
 #[derive(Debug)]
 pub struct TextMsg {
+    /// Where the client shows the message: 1 notify, 2 console, 3 chat, 4 center of the screen.
     pub destination: u8,
     pub text: String,
     pub arg1: Option<String>,
@@ -800,6 +1019,66 @@ pub struct TextMsg {
     pub arg4: Option<String>,
 }
 
+impl TextMsg {
+    /// Destination for messages that are only printed to the console.
+    pub const DESTINATION_CONSOLE: u8 = 2;
+}
+
+impl Display for TextMsg {
+    /// Formats the message as a readable sentence, resolving localization tokens in the text and
+    /// arguments.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let resolve = |str: &str| localize(str).unwrap_or(str).to_string();
+
+        let args = [&self.arg1, &self.arg2, &self.arg3, &self.arg4];
+
+        let sentence = args
+            .iter()
+            .enumerate()
+            .fold(resolve(&self.text), |sentence, (i, arg)| {
+                let placeholder = format!("%s{}", i + 1);
+                let value = arg.as_deref().map(resolve).unwrap_or_default();
+
+                sentence.replace(&placeholder, &value)
+            });
+
+        f.write_str(sentence.trim_end())
+    }
+}
+
+/// English strings for common localization tokens sent in [TextMsg].
+///
+/// Placeholders `%s1` to `%s4` are substituted with the arguments of the message.
+pub const LOCALIZED_STRINGS: [(&str, &str); 16] = [
+    ("#Game_connected", "%s1 connected"),
+    ("#Game_disconnected", "%s1 has left the game"),
+    ("#Game_joined_team", "%s1 is joining the %s2"),
+    ("#Game_join_allies", "%s1 is joining the Allied forces"),
+    ("#Game_join_axis", "%s1 is joining the Axis forces"),
+    ("#Game_join_spectators", "%s1 is joining the spectators"),
+    ("#Game_captured_point", "%s1 captured %s2"),
+    ("#Game_will_restart_in", "The game will restart in %s1 %s2"),
+    ("#Game_teammate_attack", "%s1 attacked a teammate"),
+    (
+        "#Game_idle_kick",
+        "%s1 has been idle for too long and has been kicked",
+    ),
+    ("#Game_kicked", "Kicked %s1"),
+    ("#Game_unknown_command", "Unknown command: %s1"),
+    ("#Game_Second", "second"),
+    ("#Game_Seconds", "seconds"),
+    ("#Game_Allies", "Allies"),
+    ("#Game_Axis", "Axis"),
+];
+
+/// Returns the English string for a localization token, if it is known.
+pub fn localize(token: &str) -> Option<&'static str> {
+    LOCALIZED_STRINGS
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(token.trim_end()))
+        .map(|(_, value)| *value)
+}
+
 /// Sent in response to a `timeleft` command from the client.
 #[derive(Debug)]
 pub struct TimeLeft(pub Duration);
@@ -868,197 +1147,147 @@ pub struct Weather {}
 #[derive(Debug)]
 pub struct YouDied {}
 
-fn wrapped_string<T>(i: &[u8], f: fn(String) -> T) -> IResult<&[u8], T> {
-    all_consuming(many0(le_u8))
-        .map_res(String::from_utf8)
-        .map(f)
-        .parse(i)
+fn wrapped_string<T>(
+    codepage: Codepage,
+    f: fn(String) -> T,
+) -> impl Fn(&[u8]) -> IResult<&[u8], T> {
+    move |i| {
+        all_consuming(many0(le_u8))
+            .map(|bytes| decode_string(&bytes, codepage))
+            .map(f)
+            .parse(i)
+    }
 }
 
-fn null_string(i: &[u8]) -> IResult<&[u8], String> {
-    alt((
-        tag("\x00").map(|_| vec![]),
-        terminated(take_until("\x00"), tag("\x00")).map(Vec::from),
-    ))
-    .map_res(String::from_utf8)
-    .parse(i)
+fn null_string(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], String> {
+    move |i| {
+        alt((
+            tag("\x00").map(|_| vec![]),
+            terminated(take_until("\x00"), tag("\x00")).map(Vec::from),
+        ))
+        .map(|bytes| decode_string(&bytes, codepage))
+        .parse(i)
+    }
 }
 
 fn class(i: &[u8]) -> IResult<&[u8], Class> {
-    le_u8
-        .map_res(|value| match value {
-            // FIXME Inaccurate!
-            1 => Ok(Class::Rifleman),
-            2 => Ok(Class::StaffSergeant),
-            3 => Ok(Class::MasterSergeant),
-            4 => Ok(Class::Sergeant),
-            5 => Ok(Class::Sniper),
-            6 => Ok(Class::SupportInfantry),
-            7 => Ok(Class::MachineGunner),
-            8 => Ok(Class::Bazooka),
-            9 => Ok(Class::Mortar),
-            10 => Ok(Class::Grenadier),
-            11 => Ok(Class::Stosstruppe),
-            12 => Ok(Class::Unteroffizer),
-            13 => Ok(Class::Sturmtruppe),
-            14 => Ok(Class::Scharfschutze),
-            15 => Ok(Class::Fg42Zweibein),
-            16 => Ok(Class::Fg42Zielfernrohr),
-            17 => Ok(Class::MG34Schutze),
-            18 => Ok(Class::MG42Schutze),
-            19 => Ok(Class::Panzerschreck),
-            20 => Ok(Class::AxisMortar),
-            21 => Ok(Class::BritishRifleman),
-            22 => Ok(Class::SergeantMajor),
-            23 => Ok(Class::Marksman),
-            24 => Ok(Class::Gunner),
-            25 => Ok(Class::RocketInfantry),
-            26 => Ok(Class::BritishMortar),
-            27 => Ok(Class::Random),
-            _ => Err(()),
-        })
-        .parse(i)
+    le_u8.map_res(Class::try_from).parse(i)
 }
 
 fn team(i: &[u8]) -> IResult<&[u8], Team> {
-    le_u8
-        .map_res(|value| match value {
-            1 => Ok(Team::Allies),
-            2 => Ok(Team::Axis),
-            3 => Ok(Team::Spectators),
-            _ => Err(()),
-        })
-        .parse(i)
+    le_u8.map_res(Team::try_from).parse(i)
 }
 
 fn weapon(i: &[u8]) -> IResult<&[u8], Weapon> {
-    le_u8
-        .map_res(|value| match value {
-            1 => Ok(Weapon::Kabar),
-            2 => Ok(Weapon::GermanKnife),
-            3 => Ok(Weapon::M1911),
-            4 => Ok(Weapon::Luger),
-            5 => Ok(Weapon::Garand),
-            6 => Ok(Weapon::ScopedK98),
-            7 => Ok(Weapon::Thompson),
-            8 => Ok(Weapon::Stg44),
-            9 => Ok(Weapon::Springfield),
-            10 => Ok(Weapon::K98),
-            11 => Ok(Weapon::Bar),
-            12 => Ok(Weapon::Mp40),
-            13 => Ok(Weapon::Mk2Grenade),
-            14 => Ok(Weapon::StickGrenade),
-            17 => Ok(Weapon::Mg42),
-            18 => Ok(Weapon::Browning30Cal),
-            19 => Ok(Weapon::Spade),
-            20 => Ok(Weapon::M1Carbine),
-            21 => Ok(Weapon::Mg34),
-            22 => Ok(Weapon::GreaseGun),
-            23 => Ok(Weapon::Fg42),
-            24 => Ok(Weapon::K43),
-            25 => Ok(Weapon::LeeEnfield),
-            26 => Ok(Weapon::Sten),
-            27 => Ok(Weapon::Bren),
-            28 => Ok(Weapon::Webley),
-            29 => Ok(Weapon::Bazooka),
-            30 => Ok(Weapon::Panzerschreck),
-            31 => Ok(Weapon::Piat),
-            32 => Ok(Weapon::Mortar),
-            35 => Ok(Weapon::ScopedFg42),
-            36 => Ok(Weapon::M1A1Carbine),
-            37 => Ok(Weapon::K98Bayonet),
-            38 => Ok(Weapon::ScopedLeeEnfield),
-            39 => Ok(Weapon::MillsBomb),
-            40 => Ok(Weapon::BritishKnife),
-            42 => Ok(Weapon::ButtStock),
-            43 => Ok(Weapon::EnfieldBayonet),
-            _ => Err(()),
-        })
-        .parse(i)
+    le_u8.map(Weapon::from).parse(i)
 }
 
 fn ammo(i: &[u8]) -> IResult<&[u8], Ammo> {
-    le_u8
-        .map_res(|value| match value {
-            1 => Ok::<Ammo, Infallible>(Ammo::Smg),
-            2 => Ok(Ammo::AltRifle),
-            3 => Ok(Ammo::Rifle),
-            4 => Ok(Ammo::Pistol),
-            5 => Ok(Ammo::Springfield),
-            6 => Ok(Ammo::Heavy),
-            7 => Ok(Ammo::Mg42),
-            8 => Ok(Ammo::Browning30Cal),
-            9 => Ok(Ammo::Rocket),
-            u8::MAX => Ok(Ammo::Infinite),
-            _ => Ok(Ammo::Unknown),
-        })
-        .parse(i)
+    le_u8.map(Ammo::from).parse(i)
 }
 
 impl UserMessage {
-    pub fn new<'a>(msg_name: &'a [u8], msg_data: &'a [u8]) -> Result<UserMessage, Error> {
+    /// Parses a message, decoding its strings with the [Codepage] when they are not valid UTF-8.
+    pub fn new<'a>(
+        msg_name: &'a [u8],
+        msg_data: &'a [u8],
+        codepage: Codepage,
+    ) -> Result<UserMessage, Error> {
         let msg_name = from_utf8(msg_name).map_err(|_| Error::ParserError)?;
         let msg_name = msg_name.trim_end_matches('\x00');
         let i = msg_data;
 
-        let (_, message) = match msg_name {
-            "AmmoShort" => ammo_short.map(Self::AmmoShort).parse(i),
-            "AmmoX" => ammox.map(Self::AmmoX).parse(i),
-            "BloodPuff" => blood_puff.map(Self::BloodPuff).parse(i),
-            "CancelProg" => cancel_prog.map(Self::CancelProg).parse(i),
-            "CapMsg" => cap_msg.map(Self::CapMsg).parse(i),
-            "ClCorpse" => cl_corpse.map(Self::ClCorpse).parse(i),
-            "ClanTimer" => clan_timer.map(Self::ClanTimer).parse(i),
-            "ClientAreas" => client_areas.map(Self::ClientAreas).parse(i),
-            "CurWeapon" => cur_weapon.map(Self::CurWeapon).parse(i),
-            "DeathMsg" => death_msg.map(Self::DeathMsg).parse(i),
-            "Frags" => frags.map(Self::Frags).parse(i),
-            "GameRules" => game_rules.map(Self::GameRules).parse(i),
-            "HandSignal" => hand_signal.map(Self::HandSignal).parse(i),
-            "Health" => health.map(Self::Health).parse(i),
-            "HideWeapon" => hide_weapon.map(Self::HideWeapon).parse(i),
-            "HLTV" => hltv.map(Self::Hltv).parse(i),
-            "HudText" => hud_text.map(Self::HudText).parse(i),
-            "InitHUD" => init_hud.map(Self::InitHUD).parse(i),
-            "InitObj" => init_obj.map(Self::InitObj).parse(i),
-            "MOTD" => motd.map(Self::Motd).parse(i),
-            "ObjScore" => obj_score.map(Self::ObjScore).parse(i),
-            "PClass" => p_class.map(Self::PClass).parse(i),
-            "PStatus" => p_status.map(Self::PStatus).parse(i),
-            "PTeam" => p_team.map(Self::PTeam).parse(i),
-            "PlayersIn" => players_in.map(Self::PlayersIn).parse(i),
-            "ReloadDone" => reload_done.map(Self::ReloadDone).parse(i),
-            "ReqState" => req_state.map(Self::ReqState).parse(i),
-            "ResetHUD" => reset_hud.map(Self::ResetHUD).parse(i),
-            "ResetSens" => reset_sens.map(Self::ResetSens).parse(i),
-            "RoundState" => round_state.map(Self::RoundState).parse(i),
-            "SayText" => say_text.map(Self::SayText).parse(i),
-            "Scope" => scope.map(Self::Scope).parse(i),
-            "ScoreShort" => score_short.map(Self::ScoreShort).parse(i),
-            "ScreenFade" => screen_fade.map(Self::ScreenFade).parse(i),
-            "ScreenShake" => screen_shake.map(Self::ScreenShake).parse(i),
-            "ServerName" => server_name.map(Self::ServerName).parse(i),
-            "SetFOV" => set_fov.map(Self::SetFOV).parse(i),
-            "SetObj" => set_obj.map(Self::SetObj).parse(i),
-            "Spectator" => spectator.map(Self::Spectator).parse(i),
-            "StartProg" => start_prog.map(Self::StartProg).parse(i),
-            "StatusValue" => status_value.map(Self::StatusValue).parse(i),
-            "TeamScore" => team_score.map(Self::TeamScore).parse(i),
-            "TextMsg" => text_msg.map(Self::TextMsg).parse(i),
-            "TimeLeft" => time_left.map(Self::TimeLeft).parse(i),
-            "UseSound" => use_sound.map(Self::UseSound).parse(i),
-            "VGUIMenu" => vgui_menu.map(Self::VGUIMenu).parse(i),
-            "VoiceMask" => voice_mask.map(Self::VoiceMask).parse(i),
-            "WaveStatus" => wave_status.map(Self::WaveStatus).parse(i),
-            "WaveTime" => wave_time.map(Self::WaveTime).parse(i),
-            "WeaponList" => weapon_list.map(Self::WeaponList).parse(i),
-            "YouDied" => you_died.map(Self::YouDied).parse(i),
-            _ => context("Unknown message", fail::<&[u8], UserMessage, _>()).parse(i),
+        let (_, message) = match MessageKind::from_name(msg_name) {
+            Some(MessageKind::AmmoShort) => ammo_short.map(Self::AmmoShort).parse(i),
+            Some(MessageKind::AmmoX) => ammox.map(Self::AmmoX).parse(i),
+            Some(MessageKind::BloodPuff) => blood_puff.map(Self::BloodPuff).parse(i),
+            Some(MessageKind::CancelProg) => cancel_prog.map(Self::CancelProg).parse(i),
+            Some(MessageKind::CapMsg) => cap_msg(codepage).map(Self::CapMsg).parse(i),
+            Some(MessageKind::ClCorpse) => cl_corpse(codepage).map(Self::ClCorpse).parse(i),
+            Some(MessageKind::ClanTimer) => clan_timer.map(Self::ClanTimer).parse(i),
+            Some(MessageKind::ClientAreas) => {
+                client_areas(codepage).map(Self::ClientAreas).parse(i)
+            }
+            Some(MessageKind::CurWeapon) => cur_weapon.map(Self::CurWeapon).parse(i),
+            Some(MessageKind::DeathMsg) => death_msg.map(Self::DeathMsg).parse(i),
+            Some(MessageKind::Frags) => frags.map(Self::Frags).parse(i),
+            Some(MessageKind::GameRules) => game_rules.map(Self::GameRules).parse(i),
+            Some(MessageKind::HandSignal) => hand_signal.map(Self::HandSignal).parse(i),
+            Some(MessageKind::Health) => health.map(Self::Health).parse(i),
+            Some(MessageKind::HideWeapon) => hide_weapon.map(Self::HideWeapon).parse(i),
+            Some(MessageKind::Hltv) => hltv.map(Self::Hltv).parse(i),
+            Some(MessageKind::HudText) => hud_text(codepage).map(Self::HudText).parse(i),
+            Some(MessageKind::InitHUD) => init_hud.map(Self::InitHUD).parse(i),
+            Some(MessageKind::InitObj) => init_obj.map(Self::InitObj).parse(i),
+            Some(MessageKind::Motd) => motd(codepage).map(Self::Motd).parse(i),
+            Some(MessageKind::ObjScore) => obj_score.map(Self::ObjScore).parse(i),
+            Some(MessageKind::PClass) => p_class.map(Self::PClass).parse(i),
+            Some(MessageKind::PStatus) => p_status.map(Self::PStatus).parse(i),
+            Some(MessageKind::PTeam) => p_team.map(Self::PTeam).parse(i),
+            Some(MessageKind::PlayersIn) => players_in.map(Self::PlayersIn).parse(i),
+            Some(MessageKind::ReloadDone) => reload_done.map(Self::ReloadDone).parse(i),
+            Some(MessageKind::ReqState) => req_state.map(Self::ReqState).parse(i),
+            Some(MessageKind::ResetHUD) => reset_hud.map(Self::ResetHUD).parse(i),
+            Some(MessageKind::ResetSens) => reset_sens.map(Self::ResetSens).parse(i),
+            Some(MessageKind::RoundState) => round_state.map(Self::RoundState).parse(i),
+            Some(MessageKind::SayText) => say_text(codepage).map(Self::SayText).parse(i),
+            Some(MessageKind::Scope) => scope.map(Self::Scope).parse(i),
+            Some(MessageKind::ScoreShort) => score_short.map(Self::ScoreShort).parse(i),
+            Some(MessageKind::ScreenFade) => screen_fade.map(Self::ScreenFade).parse(i),
+            Some(MessageKind::ScreenShake) => screen_shake.map(Self::ScreenShake).parse(i),
+            Some(MessageKind::ServerName) => server_name(codepage).map(Self::ServerName).parse(i),
+            Some(MessageKind::SetFOV) => set_fov.map(Self::SetFOV).parse(i),
+            Some(MessageKind::SetObj) => set_obj.map(Self::SetObj).parse(i),
+            Some(MessageKind::Spectator) => spectator.map(Self::Spectator).parse(i),
+            Some(MessageKind::StartProg) => start_prog.map(Self::StartProg).parse(i),
+            Some(MessageKind::StatusValue) => status_value.map(Self::StatusValue).parse(i),
+            Some(MessageKind::TeamScore) => team_score.map(Self::TeamScore).parse(i),
+            Some(MessageKind::TextMsg) => text_msg(codepage).map(Self::TextMsg).parse(i),
+            Some(MessageKind::TimeLeft) => time_left.map(Self::TimeLeft).parse(i),
+            Some(MessageKind::UseSound) => use_sound.map(Self::UseSound).parse(i),
+            Some(MessageKind::VGUIMenu) => vgui_menu.map(Self::VGUIMenu).parse(i),
+            Some(MessageKind::VoiceMask) => voice_mask.map(Self::VoiceMask).parse(i),
+            Some(MessageKind::WaveStatus) => wave_status.map(Self::WaveStatus).parse(i),
+            Some(MessageKind::WaveTime) => wave_time.map(Self::WaveTime).parse(i),
+            Some(MessageKind::WeaponList) => weapon_list.map(Self::WeaponList).parse(i),
+            Some(MessageKind::YouDied) => you_died.map(Self::YouDied).parse(i),
+            _ => return Err(Error::UnknownMessage),
         }
         .map_err(|_| Error::ParserError)?;
 
         Ok(message)
     }
+
+    /// Parses a message using the layouts that were sent by a specific [Version] of the mod.
+    ///
+    /// Legacy versions registered some messages that were later removed or changed, so those are
+    /// tried first before falling back to the current layouts of [UserMessage::new].
+    pub fn new_for_version<'a>(
+        version: &Version,
+        msg_name: &'a [u8],
+        msg_data: &'a [u8],
+        codepage: Codepage,
+    ) -> Result<UserMessage, Error> {
+        if version.is_legacy() {
+            let name = from_utf8(msg_name).map_err(|_| Error::ParserError)?;
+            let i = msg_data;
+
+            let legacy_message = match MessageKind::from_name(name.trim_end_matches('\x00')) {
+                Some(MessageKind::ScoreInfo) => score_info.map(Self::ScoreInfo).parse(i).ok(),
+                Some(MessageKind::TeamScore) => legacy_team_score(codepage)
+                    .map(Self::TeamScore)
+                    .parse(i)
+                    .ok(),
+                _ => None,
+            };
+
+            if let Some((_, message)) = legacy_message {
+                return Ok(message);
+            }
+        }
+
+        Self::new(msg_name, msg_data, codepage)
+    }
 }
 
 impl TryFrom<&str> for Team {
@@ -1098,36 +1327,40 @@ fn cancel_prog(i: &[u8]) -> IResult<&[u8], CancelProg> {
         .parse(i)
 }
 
-fn cap_msg(i: &[u8]) -> IResult<&[u8], CapMsg> {
-    all_consuming((le_u8, null_string, team))
-        .map(|(client_index, point_name, team)| CapMsg {
-            client_index,
-            point_name,
-            team,
-        })
-        .parse(i)
+fn cap_msg(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], CapMsg> {
+    move |i| {
+        all_consuming((le_u8, null_string(codepage), team))
+            .map(|(client_index, point_name, team)| CapMsg {
+                client_index,
+                point_name,
+                team,
+            })
+            .parse(i)
+    }
 }
 
-fn cl_corpse(i: &[u8]) -> IResult<&[u8], ClCorpse> {
-    all_consuming((
-        null_string,
-        (le_i16, le_i16, le_i16),
-        (le_i8, le_i8, le_i8),
-        le_u8,
-        le_u16,
-        team,
-    ))
-    .map(
-        |(model_name, origin, angle, animation_sequence, body, team)| ClCorpse {
-            model_name,
-            origin,
-            angle,
-            animation_sequence,
-            body,
+fn cl_corpse(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], ClCorpse> {
+    move |i| {
+        all_consuming((
+            null_string(codepage),
+            (le_i16, le_i16, le_i16),
+            (le_i8, le_i8, le_i8),
+            le_u8,
+            le_u16,
             team,
-        },
-    )
-    .parse(i)
+        ))
+        .map(
+            |(model_name, origin, angle, animation_sequence, body, team)| ClCorpse {
+                model_name,
+                origin,
+                angle,
+                animation_sequence,
+                body,
+                team,
+            },
+        )
+        .parse(i)
+    }
 }
 
 fn clan_timer(i: &[u8]) -> IResult<&[u8], ClanTimer> {
@@ -1140,22 +1373,24 @@ fn clan_timer(i: &[u8]) -> IResult<&[u8], ClanTimer> {
         .parse(i)
 }
 
-fn client_areas(i: &[u8]) -> IResult<&[u8], ClientAreas> {
-    let (i, icon_index) = le_u8(i)?;
-    let (i, flags) = le_u8(i)?;
+fn client_areas(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], ClientAreas> {
+    move |i| {
+        let (i, icon_index) = le_u8(i)?;
+        let (i, flags) = le_u8(i)?;
 
-    let (i, hud_icon) = match flags {
-        255 => null_string.map(Some).parse(i)?,
-        _ => success(None).parse(i)?,
-    };
+        let (i, hud_icon) = match flags {
+            255 => null_string(codepage).map(Some).parse(i)?,
+            _ => success(None).parse(i)?,
+        };
 
-    Ok((
-        i,
-        ClientAreas {
-            icon_index,
-            hud_icon,
-        },
-    ))
+        Ok((
+            i,
+            ClientAreas {
+                icon_index,
+                hud_icon,
+            },
+        ))
+    }
 }
 
 fn cur_weapon(i: &[u8]) -> IResult<&[u8], CurWeapon> {
@@ -1223,13 +1458,15 @@ fn hltv(i: &[u8]) -> IResult<&[u8], Hltv> {
         .parse(i)
 }
 
-fn hud_text(i: &[u8]) -> IResult<&[u8], HudText> {
-    all_consuming((null_string, le_u8))
-        .map(|(text, init_hud_style)| HudText {
-            text,
-            init_hud_style,
-        })
-        .parse(i)
+fn hud_text(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], HudText> {
+    move |i| {
+        all_consuming((null_string(codepage), le_u8))
+            .map(|(text, init_hud_style)| HudText {
+                text,
+                init_hud_style,
+            })
+            .parse(i)
+    }
 }
 
 fn init_hud(i: &[u8]) -> IResult<&[u8], InitHUD> {
@@ -1278,13 +1515,15 @@ fn init_obj(i: &[u8]) -> IResult<&[u8], InitObj> {
         .parse(i)
 }
 
-fn motd(i: &[u8]) -> IResult<&[u8], Motd> {
-    all_consuming((
-        le_u8.map(|v| v != 0),
-        many0(le_u8).map_res(String::from_utf8),
-    ))
-    .map(|(is_terminal, text)| Motd { is_terminal, text })
-    .parse(i)
+fn motd(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], Motd> {
+    move |i| {
+        all_consuming((
+            le_u8.map(|v| v != 0),
+            many0(le_u8).map(|bytes| decode_string(&bytes, codepage)),
+        ))
+        .map(|(is_terminal, text)| Motd { is_terminal, text })
+        .parse(i)
+    }
 }
 
 fn obj_score(i: &[u8]) -> IResult<&[u8], ObjScore> {
@@ -1362,20 +1601,37 @@ fn round_state(i: &[u8]) -> IResult<&[u8], RoundState> {
         .parse(i)
 }
 
-fn say_text(i: &[u8]) -> IResult<&[u8], SayText> {
-    all_consuming((
-        le_u8,
-        le_u8, // unk
-        null_string,
-    ))
-    .map(|(client_index, _, text)| SayText { client_index, text })
-    .parse(i)
+fn say_text(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], SayText> {
+    move |i| {
+        all_consuming((
+            le_u8,
+            le_u8, // unk
+            null_string(codepage),
+        ))
+        .map(|(client_index, _, text)| SayText { client_index, text })
+        .parse(i)
+    }
 }
 
 fn scope(i: &[u8]) -> IResult<&[u8], Scope> {
     all_consuming(le_u8).map(|_| Scope {}).parse(i)
 }
 
+fn score_info(i: &[u8]) -> IResult<&[u8], ScoreInfo> {
+    all_consuming((le_u8, le_i8, le_i8, le_i8, class, team))
+        .map(
+            |(client_index, points, kills, deaths, class, team)| ScoreInfo {
+                client_index,
+                points,
+                kills,
+                deaths,
+                class,
+                team,
+            },
+        )
+        .parse(i)
+}
+
 fn score_short(i: &[u8]) -> IResult<&[u8], ScoreShort> {
     all_consuming((le_u8, le_i16, le_i16, le_i16, le_u8))
         .map(|(client_index, score, kills, deaths, _)| ScoreShort {
@@ -1387,24 +1643,37 @@ fn score_short(i: &[u8]) -> IResult<&[u8], ScoreShort> {
         .parse(i)
 }
 
+/// Parses an unsigned fixed-point number with the given number of fractional bits, as written by
+/// the SDK's `FixedUnsigned16`.
+fn fixed_u16(fractional_bits: u32) -> impl Fn(&[u8]) -> IResult<&[u8], f32> {
+    move |i| {
+        le_u16
+            .map(|x| x as f32 / (1 << fractional_bits) as f32)
+            .parse(i)
+    }
+}
+
 fn screen_fade(i: &[u8]) -> IResult<&[u8], ScreenFade> {
-    all_consuming((le_u16, le_u16, le_u16, (le_u8, le_u8, le_u8, le_u8)))
-        .map(|(duration, hold_time, flags, (r, g, b, a))| ScreenFade {
-            duration,
-            hold_time,
-            flags,
-            color: (r, g, b, a),
-        })
-        .parse(i)
+    all_consuming((
+        fixed_u16(12).map(Duration::from_secs_f32),
+        fixed_u16(12).map(Duration::from_secs_f32),
+        le_u16,
+        (le_u8, le_u8, le_u8, le_u8),
+    ))
+    .map(|(duration, hold_time, flags, (r, g, b, a))| ScreenFade {
+        duration,
+        hold_time,
+        flags,
+        color: (r, g, b, a),
+    })
+    .parse(i)
 }
 
 fn screen_shake(i: &[u8]) -> IResult<&[u8], ScreenShake> {
     all_consuming((
-        le_u16.map(|a| a / 4096),
-        le_u16
-            .map(|a| a as f32 / 4096.)
-            .map(Duration::from_secs_f32),
-        le_u16.map(|a| a / 4096),
+        fixed_u16(12),
+        fixed_u16(12).map(Duration::from_secs_f32),
+        fixed_u16(8),
     ))
     .map(|(amplitude, duration, frequency)| ScreenShake {
         amplitude,
@@ -1414,8 +1683,8 @@ fn screen_shake(i: &[u8]) -> IResult<&[u8], ScreenShake> {
     .parse(i)
 }
 
-fn server_name(i: &[u8]) -> IResult<&[u8], ServerName> {
-    wrapped_string(i, ServerName)
+fn server_name(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], ServerName> {
+    move |i| wrapped_string(codepage, ServerName).parse(i)
 }
 
 fn set_fov(i: &[u8]) -> IResult<&[u8], SetFOV> {
@@ -1461,26 +1730,40 @@ fn team_score(i: &[u8]) -> IResult<&[u8], TeamScore> {
         .parse(i)
 }
 
-fn text_msg(i: &[u8]) -> IResult<&[u8], TextMsg> {
-    let (i, text_msg) = all_consuming((
-        le_u8,
-        null_string,
-        opt(null_string),
-        opt(null_string),
-        opt(null_string),
-        opt(null_string),
-    ))
-    .map(|(destination, text, arg1, arg2, arg3, arg4)| TextMsg {
-        destination,
-        text,
-        arg1,
-        arg2,
-        arg3,
-        arg4,
-    })
-    .parse(i)?;
+/// Legacy versions identify the team by name, like other mods of the era.
+fn legacy_team_score(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], TeamScore> {
+    move |i| {
+        all_consuming((
+            null_string(codepage).map_res(|name| Team::try_from(name.to_lowercase().as_str())),
+            le_u16,
+        ))
+        .map(|(team, score)| TeamScore { team, score })
+        .parse(i)
+    }
+}
 
-    Ok((i, text_msg))
+fn text_msg(codepage: Codepage) -> impl Fn(&[u8]) -> IResult<&[u8], TextMsg> {
+    move |i| {
+        let (i, text_msg) = all_consuming((
+            le_u8,
+            null_string(codepage),
+            opt(null_string(codepage)),
+            opt(null_string(codepage)),
+            opt(null_string(codepage)),
+            opt(null_string(codepage)),
+        ))
+        .map(|(destination, text, arg1, arg2, arg3, arg4)| TextMsg {
+            destination,
+            text,
+            arg1,
+            arg2,
+            arg3,
+            arg4,
+        })
+        .parse(i)?;
+
+        Ok((i, text_msg))
+    }
 }
 
 fn time_left(i: &[u8]) -> IResult<&[u8], TimeLeft> {
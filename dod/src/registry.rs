@@ -0,0 +1,519 @@
+//! Names and sizes of the messages registered by the mod.
+
+use crate::UserMessage;
+
+/// Size of a message's data, as registered by the mod.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageSize {
+    /// Always this many bytes.
+    Fixed(u8),
+
+    /// Length depends on the contents, such as strings or lists, and is sent with each message.
+    Variable,
+}
+
+/// Kinds of messages registered by the mod, without their data.
+///
+/// Each kind matches a variant of [UserMessage].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MessageKind {
+    AmmoPickup,
+    AmmoShort,
+    AmmoX,
+    BloodPuff,
+    CameraView,
+    CancelProg,
+    CapMsg,
+    ClanTimer,
+    ClCorpse,
+    ClientAreas,
+    CurMarker,
+    CurWeapon,
+    DeathMsg,
+    Frags,
+    GameRules,
+    HandSignal,
+    Health,
+    HideWeapon,
+    Hltv,
+    HudText,
+    InitHUD,
+    InitObj,
+    MapMarker,
+    Motd,
+    ObjScore,
+    Object,
+    PClass,
+    PShoot,
+    PStatus,
+    PTeam,
+    PlayersIn,
+    ReloadDone,
+    ReqState,
+    ResetHUD,
+    ResetSens,
+    RoundState,
+    SayText,
+    Scope,
+    ScoreInfo,
+    ScoreShort,
+    ScreenFade,
+    ScreenShake,
+    ServerName,
+    SetFOV,
+    SetObj,
+    ShowMenu,
+    Spectator,
+    StartProg,
+    StartProgF,
+    StatusValue,
+    TeamScore,
+    TextMsg,
+    TimeLeft,
+    TimerStatus,
+    UseSound,
+    VGUIMenu,
+    VoiceMask,
+    WaveStatus,
+    WaveTime,
+    WeaponList,
+    WeapPickup,
+    Weather,
+    YouDied,
+}
+
+/// Name and size of a kind of message.
+#[derive(Clone, Copy, Debug)]
+pub struct MessageInfo {
+    pub kind: MessageKind,
+
+    /// Name that the mod registers the message with.
+    pub name: &'static str,
+
+    /// Size of the message data sent by the current version of the mod.
+    pub size: MessageSize,
+}
+
+/// Every message registered by the mod.
+pub const MESSAGES: [MessageInfo; 63] = [
+    MessageInfo {
+        kind: MessageKind::AmmoPickup,
+        name: "AmmoPickup",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::AmmoShort,
+        name: "AmmoShort",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::AmmoX,
+        name: "AmmoX",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::BloodPuff,
+        name: "BloodPuff",
+        size: MessageSize::Fixed(6),
+    },
+    MessageInfo {
+        kind: MessageKind::CameraView,
+        name: "CameraView",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::CancelProg,
+        name: "CancelProg",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::CapMsg,
+        name: "CapMsg",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::ClanTimer,
+        name: "ClanTimer",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::ClCorpse,
+        name: "ClCorpse",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::ClientAreas,
+        name: "ClientAreas",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::CurMarker,
+        name: "CurMarker",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::CurWeapon,
+        name: "CurWeapon",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::DeathMsg,
+        name: "DeathMsg",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::Frags,
+        name: "Frags",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::GameRules,
+        name: "GameRules",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::HandSignal,
+        name: "HandSignal",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::Health,
+        name: "Health",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::HideWeapon,
+        name: "HideWeapon",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::Hltv,
+        name: "HLTV",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::HudText,
+        name: "HudText",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::InitHUD,
+        name: "InitHUD",
+        size: MessageSize::Fixed(0),
+    },
+    MessageInfo {
+        kind: MessageKind::InitObj,
+        name: "InitObj",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::MapMarker,
+        name: "MapMarker",
+        size: MessageSize::Fixed(6),
+    },
+    MessageInfo {
+        kind: MessageKind::Motd,
+        name: "MOTD",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::ObjScore,
+        name: "ObjScore",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::Object,
+        name: "Object",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::PClass,
+        name: "PClass",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::PShoot,
+        name: "PShoot",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::PStatus,
+        name: "PStatus",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::PTeam,
+        name: "PTeam",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::PlayersIn,
+        name: "PlayersIn",
+        size: MessageSize::Fixed(4),
+    },
+    MessageInfo {
+        kind: MessageKind::ReloadDone,
+        name: "ReloadDone",
+        size: MessageSize::Fixed(0),
+    },
+    MessageInfo {
+        kind: MessageKind::ReqState,
+        name: "ReqState",
+        size: MessageSize::Fixed(0),
+    },
+    MessageInfo {
+        kind: MessageKind::ResetHUD,
+        name: "ResetHUD",
+        size: MessageSize::Fixed(0),
+    },
+    MessageInfo {
+        kind: MessageKind::ResetSens,
+        name: "ResetSens",
+        size: MessageSize::Fixed(0),
+    },
+    MessageInfo {
+        kind: MessageKind::RoundState,
+        name: "RoundState",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::SayText,
+        name: "SayText",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::Scope,
+        name: "Scope",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::ScoreInfo,
+        name: "ScoreInfo",
+        size: MessageSize::Fixed(6),
+    },
+    MessageInfo {
+        kind: MessageKind::ScoreShort,
+        name: "ScoreShort",
+        size: MessageSize::Fixed(8),
+    },
+    MessageInfo {
+        kind: MessageKind::ScreenFade,
+        name: "ScreenFade",
+        size: MessageSize::Fixed(10),
+    },
+    MessageInfo {
+        kind: MessageKind::ScreenShake,
+        name: "ScreenShake",
+        size: MessageSize::Fixed(6),
+    },
+    MessageInfo {
+        kind: MessageKind::ServerName,
+        name: "ServerName",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::SetFOV,
+        name: "SetFOV",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::SetObj,
+        name: "SetObj",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::ShowMenu,
+        name: "ShowMenu",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::Spectator,
+        name: "Spectator",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::StartProg,
+        name: "StartProg",
+        size: MessageSize::Fixed(4),
+    },
+    MessageInfo {
+        kind: MessageKind::StartProgF,
+        name: "StartProgF",
+        size: MessageSize::Fixed(4),
+    },
+    MessageInfo {
+        kind: MessageKind::StatusValue,
+        name: "StatusValue",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::TeamScore,
+        name: "TeamScore",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::TextMsg,
+        name: "TextMsg",
+        size: MessageSize::Variable,
+    },
+    MessageInfo {
+        kind: MessageKind::TimeLeft,
+        name: "TimeLeft",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::TimerStatus,
+        name: "TimerStatus",
+        size: MessageSize::Fixed(3),
+    },
+    MessageInfo {
+        kind: MessageKind::UseSound,
+        name: "UseSound",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::VGUIMenu,
+        name: "VGUIMenu",
+        size: MessageSize::Fixed(5),
+    },
+    MessageInfo {
+        kind: MessageKind::VoiceMask,
+        name: "VoiceMask",
+        size: MessageSize::Fixed(8),
+    },
+    MessageInfo {
+        kind: MessageKind::WaveStatus,
+        name: "WaveStatus",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::WaveTime,
+        name: "WaveTime",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::WeaponList,
+        name: "WeaponList",
+        size: MessageSize::Fixed(10),
+    },
+    MessageInfo {
+        kind: MessageKind::WeapPickup,
+        name: "WeapPickup",
+        size: MessageSize::Fixed(1),
+    },
+    MessageInfo {
+        kind: MessageKind::Weather,
+        name: "Weather",
+        size: MessageSize::Fixed(2),
+    },
+    MessageInfo {
+        kind: MessageKind::YouDied,
+        name: "YouDied",
+        size: MessageSize::Fixed(1),
+    },
+];
+
+impl MessageKind {
+    /// Returns the kind of message registered with a name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        MESSAGES
+            .iter()
+            .find(|info| info.name == name)
+            .map(|info| info.kind)
+    }
+
+    /// Returns the name and size of the message.
+    pub fn info(&self) -> &'static MessageInfo {
+        MESSAGES
+            .iter()
+            .find(|info| info.kind == *self)
+            .expect("every kind of message is registered")
+    }
+
+    /// Returns the name that the mod registers the message with.
+    pub fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Returns the size of the message data.
+    pub fn size(&self) -> MessageSize {
+        self.info().size
+    }
+}
+
+impl UserMessage {
+    /// Returns the kind of the message.
+    #[allow(deprecated)]
+    pub fn kind(&self) -> MessageKind {
+        match self {
+            Self::AmmoPickup(_) => MessageKind::AmmoPickup,
+            Self::AmmoShort(_) => MessageKind::AmmoShort,
+            Self::AmmoX(_) => MessageKind::AmmoX,
+            Self::BloodPuff(_) => MessageKind::BloodPuff,
+            Self::CameraView(_) => MessageKind::CameraView,
+            Self::CancelProg(_) => MessageKind::CancelProg,
+            Self::CapMsg(_) => MessageKind::CapMsg,
+            Self::ClanTimer(_) => MessageKind::ClanTimer,
+            Self::ClCorpse(_) => MessageKind::ClCorpse,
+            Self::ClientAreas(_) => MessageKind::ClientAreas,
+            Self::CurMarker(_) => MessageKind::CurMarker,
+            Self::CurWeapon(_) => MessageKind::CurWeapon,
+            Self::DeathMsg(_) => MessageKind::DeathMsg,
+            Self::Frags(_) => MessageKind::Frags,
+            Self::GameRules(_) => MessageKind::GameRules,
+            Self::HandSignal(_) => MessageKind::HandSignal,
+            Self::Health(_) => MessageKind::Health,
+            Self::HideWeapon(_) => MessageKind::HideWeapon,
+            Self::Hltv(_) => MessageKind::Hltv,
+            Self::HudText(_) => MessageKind::HudText,
+            Self::InitHUD(_) => MessageKind::InitHUD,
+            Self::InitObj(_) => MessageKind::InitObj,
+            Self::MapMarker(_) => MessageKind::MapMarker,
+            Self::Motd(_) => MessageKind::Motd,
+            Self::ObjScore(_) => MessageKind::ObjScore,
+            Self::Object(_) => MessageKind::Object,
+            Self::PClass(_) => MessageKind::PClass,
+            Self::PShoot(_) => MessageKind::PShoot,
+            Self::PStatus(_) => MessageKind::PStatus,
+            Self::PTeam(_) => MessageKind::PTeam,
+            Self::PlayersIn(_) => MessageKind::PlayersIn,
+            Self::ReloadDone(_) => MessageKind::ReloadDone,
+            Self::ReqState(_) => MessageKind::ReqState,
+            Self::ResetHUD(_) => MessageKind::ResetHUD,
+            Self::ResetSens(_) => MessageKind::ResetSens,
+            Self::RoundState(_) => MessageKind::RoundState,
+            Self::SayText(_) => MessageKind::SayText,
+            Self::Scope(_) => MessageKind::Scope,
+            Self::ScoreInfo(_) => MessageKind::ScoreInfo,
+            Self::ScoreShort(_) => MessageKind::ScoreShort,
+            Self::ScreenFade(_) => MessageKind::ScreenFade,
+            Self::ScreenShake(_) => MessageKind::ScreenShake,
+            Self::ServerName(_) => MessageKind::ServerName,
+            Self::SetFOV(_) => MessageKind::SetFOV,
+            Self::SetObj(_) => MessageKind::SetObj,
+            Self::ShowMenu(_) => MessageKind::ShowMenu,
+            Self::Spectator(_) => MessageKind::Spectator,
+            Self::StartProg(_) => MessageKind::StartProg,
+            Self::StartProgF(_) => MessageKind::StartProgF,
+            Self::StatusValue(_) => MessageKind::StatusValue,
+            Self::TeamScore(_) => MessageKind::TeamScore,
+            Self::TextMsg(_) => MessageKind::TextMsg,
+            Self::TimeLeft(_) => MessageKind::TimeLeft,
+            Self::TimerStatus(_) => MessageKind::TimerStatus,
+            Self::UseSound(_) => MessageKind::UseSound,
+            Self::VGUIMenu(_) => MessageKind::VGUIMenu,
+            Self::VoiceMask(_) => MessageKind::VoiceMask,
+            Self::WaveStatus(_) => MessageKind::WaveStatus,
+            Self::WaveTime(_) => MessageKind::WaveTime,
+            Self::WeaponList(_) => MessageKind::WeaponList,
+            Self::WeapPickup(_) => MessageKind::WeapPickup,
+            Self::Weather(_) => MessageKind::Weather,
+            Self::YouDied(_) => MessageKind::YouDied,
+        }
+    }
+
+    /// Returns the name that the mod registers the message with.
+    pub fn name(&self) -> &'static str {
+        self.kind().name()
+    }
+}
@@ -0,0 +1,86 @@
+use crate::Weapon;
+use std::time::Duration;
+
+/// Damage and rate of fire of a [Weapon].
+///
+/// Values are approximate, taken from the stock weapon scripts, and ignore hit location, range,
+/// and armor.
+#[derive(Clone, Copy, Debug)]
+pub struct WeaponStats {
+    /// Damage dealt by a single hit to the body.
+    pub damage: u32,
+
+    /// Shortest time between two shots or swings.
+    pub fire_interval: Duration,
+}
+
+/// Health of a player when they spawn.
+const PLAYER_HEALTH: u32 = 100;
+
+impl WeaponStats {
+    const fn new(damage: u32, fire_interval_millis: u64) -> Self {
+        Self {
+            damage,
+            fire_interval: Duration::from_millis(fire_interval_millis),
+        }
+    }
+
+    /// Returns the number of body hits needed to kill a player with full health.
+    pub fn hits_to_kill(&self) -> u32 {
+        PLAYER_HEALTH.div_ceil(self.damage.max(1))
+    }
+
+    /// Returns the shortest time to kill a player with full health, from the first hit to the last.
+    pub fn time_to_kill(&self) -> Duration {
+        self.fire_interval * (self.hits_to_kill() - 1)
+    }
+}
+
+impl Weapon {
+    /// Returns the damage and rate of fire of the weapon.
+    ///
+    /// Explosives deal splash damage and have no meaningful rate of fire, so they return [None],
+    /// as do weapons that are not known.
+    pub fn stats(&self) -> Option<WeaponStats> {
+        let stats = match self {
+            Self::Kabar
+            | Self::GermanKnife
+            | Self::BritishKnife
+            | Self::Spade
+            | Self::K98Bayonet
+            | Self::EnfieldBayonet
+            | Self::ButtStock => WeaponStats::new(60, 400),
+
+            Self::M1911 | Self::Luger => WeaponStats::new(40, 150),
+            Self::Webley => WeaponStats::new(50, 350),
+
+            Self::M1Carbine | Self::M1A1Carbine => WeaponStats::new(40, 100),
+            Self::Garand | Self::K43 => WeaponStats::new(115, 200),
+
+            Self::K98 | Self::ScopedK98 | Self::Springfield => WeaponStats::new(115, 1600),
+            Self::LeeEnfield | Self::ScopedLeeEnfield => WeaponStats::new(115, 1300),
+
+            Self::Thompson => WeaponStats::new(40, 85),
+            Self::Mp40 | Self::Sten | Self::Stg44 => WeaponStats::new(40, 90),
+            Self::GreaseGun => WeaponStats::new(40, 125),
+
+            Self::Bar | Self::Bren => WeaponStats::new(50, 120),
+            Self::Fg42 | Self::ScopedFg42 => WeaponStats::new(50, 75),
+            Self::Mg34 => WeaponStats::new(50, 75),
+            Self::Mg42 => WeaponStats::new(50, 50),
+            Self::Browning30Cal => WeaponStats::new(50, 80),
+
+            Self::Mk2Grenade
+            | Self::StickGrenade
+            | Self::MillsBomb
+            | Self::Bazooka
+            | Self::Panzerschreck
+            | Self::Piat
+            | Self::Mortar => return None,
+
+            Self::Unknown(_) => return None,
+        };
+
+        Some(stats)
+    }
+}
@@ -0,0 +1,59 @@
+//! Registration of the GUI as the program that opens demo files, so double-clicking a demo opens
+//! its report.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Identifier of the file type registered for demos.
+const PROG_ID: &str = "dod-tools.demo";
+
+/// Registers the program to open `.dem` files for the current user, passing the path of the demo
+/// to its `gui` command.
+///
+/// Only supported on Windows, where the association is written to the classes of the user's
+/// registry, so it needs no administrator rights.
+pub fn register_demo_association(exe: &Path) -> io::Result<()> {
+    if !cfg!(windows) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "file associations can only be registered on Windows",
+        ));
+    }
+
+    let command = format!("\"{}\" gui \"%1\"", exe.display());
+
+    for (key, value) in [
+        (
+            r"HKCU\Software\Classes\.dem".to_string(),
+            PROG_ID.to_string(),
+        ),
+        (
+            format!(r"HKCU\Software\Classes\{PROG_ID}"),
+            "Day of Defeat demo".to_string(),
+        ),
+        (
+            format!(r"HKCU\Software\Classes\{PROG_ID}\shell\open\command"),
+            command,
+        ),
+    ] {
+        set_default_value(&key, &value)?;
+    }
+
+    Ok(())
+}
+
+/// Sets the default value of a registry key with `reg.exe`, creating the key if needed.
+fn set_default_value(key: &str, value: &str) -> io::Result<()> {
+    let status = Command::new("reg")
+        .args(["add", key, "/ve", "/d", value, "/f"])
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "reg add {key} failed with {status}"
+        )))
+    }
+}
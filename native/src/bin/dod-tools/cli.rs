@@ -0,0 +1,573 @@
+//! Commands that run in a terminal and produce text output.
+
+mod analyze;
+mod compare;
+mod highlights;
+mod html;
+mod index;
+mod info;
+mod maps;
+mod markdown;
+mod markers;
+mod merge;
+mod round_report;
+mod search;
+mod serve;
+mod train_win_model;
+
+use crate::cli::analyze::analyze;
+use crate::cli::compare::print_comparison;
+use crate::cli::highlights::print_highlights;
+use crate::cli::index::update_index;
+use crate::cli::info::print_info;
+use crate::cli::maps::print_maps;
+use crate::cli::markers::print_markers;
+use crate::cli::merge::print_aggregation;
+use crate::cli::search::print_search;
+use crate::cli::serve::serve;
+use crate::cli::train_win_model::print_win_model;
+use analysis::{Analysis, AnalysisConfig, Clock, Codepage, ScoreFormula};
+use clap::{Subcommand, ValueEnum};
+use native::{
+    FileInfo, Segments,
+    index::{DEFAULT_INDEX_KEY, DemoFilter},
+    report::{self, Report},
+    run_analyzer, settings, win_model,
+};
+use serde_json::{Value, json};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Runs a command that prints its output in the terminal.
+pub fn run(command: Command) {
+    let saved_settings = settings::load_settings();
+    let display = saved_settings.display;
+    let mut config = saved_settings.analysis;
+
+    if let Some(config_args) = command.config_args() {
+        config_args.apply_to(&mut config);
+    }
+
+    match command {
+        Command::Analyze(args) => analyze(&args, &config, &display),
+
+        Command::Info { demo_paths, .. } => print_info(&demo_paths, &config, &display),
+
+        Command::Merge {
+            demo_paths,
+            output_format,
+            ..
+        } => {
+            let analyses = demo_paths
+                .iter()
+                .map(|demo_path| run_analyzer(demo_path, &config))
+                .collect::<Vec<_>>();
+
+            print_aggregation(&analyses, &output_format);
+        }
+
+        Command::Highlights {
+            demo_paths, top, ..
+        } => print_highlights(&demo_paths, top, &config),
+
+        Command::Markers {
+            demo_path,
+            player,
+            format,
+            frame_rate,
+            ..
+        } => print_markers(&demo_path, player.as_deref(), &format, frame_rate, &config),
+
+        Command::Maps { demo_paths, .. } => print_maps(&demo_paths, &config),
+
+        Command::Compare {
+            players,
+            demo_paths,
+            ..
+        } => print_comparison(&players, &demo_paths, &config),
+
+        Command::TrainWinModel { demo_paths, .. } => print_win_model(&demo_paths, &config),
+
+        Command::Index { dir, index, .. } => update_index(&dir, &index, &config),
+
+        Command::Search {
+            player,
+            map,
+            clan,
+            index,
+        } => {
+            let filter = DemoFilter {
+                player,
+                map_name: map,
+                clan,
+                ..DemoFilter::default()
+            };
+
+            print_search(&filter, &index, &display);
+        }
+
+        Command::Serve { dir, address } => serve(dir, &address),
+
+        Command::Schema => println!("{}", Json::from(report::schema())),
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct AnalyzeArgs {
+    /// List of paths to demo files
+    demo_paths: Vec<PathBuf>,
+
+    /// TOML file listing more demos to analyze, each with an optional match name, half number, and
+    /// expected teams
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+
+    /// JSON file written by the index command, to list the players whose Steam ID never appeared
+    /// in the earlier demos of their clan; read from the shared storage when DOD_TOOLS_STORAGE_URL
+    /// is set
+    #[arg(long)]
+    index: Option<PathBuf>,
+
+    /// The kind of string output to produce from an analysis
+    #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+    output_format: OutputFormat,
+
+    /// Directory to write the scoreboard, weapon, and kill streak CSV files into, instead of
+    /// printing the scoreboard CSV, when the output format is csv
+    #[arg(long)]
+    csv_dir: Option<PathBuf>,
+
+    /// How to analyze demos that were stopped and recorded again
+    #[arg(long, value_enum, default_value_t = Segments::Joined)]
+    segments: Segments,
+
+    /// Write a separate report file for each round next to its demo
+    #[arg(long)]
+    split_rounds: bool,
+
+    /// URL that receives a JSON POST request when an analysis starts, finishes, and has a result
+    #[arg(long)]
+    notify_url: Option<String>,
+
+    /// Write the raw userinfo strings received for each player slot into the current directory
+    #[arg(long)]
+    export_user_info: bool,
+
+    /// Write a PNG stats card for each player into the current directory
+    #[arg(long)]
+    stats_cards: bool,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+}
+
+/// Options that change how demos are analyzed, overriding the saved settings.
+#[derive(Debug, clap::Args)]
+pub struct ConfigArgs {
+    /// Character encoding used for player names and chat that are not valid UTF-8 [default: from
+    /// the saved settings, or windows-1252]
+    #[arg(long, value_enum)]
+    fallback_encoding: Option<FallbackEncoding>,
+
+    /// Leave kill streaks with fewer kills than this out of the report [default: from the saved
+    /// settings, or 1]
+    #[arg(long)]
+    min_streak: Option<usize>,
+
+    /// Report the team score and kill timelines per clan, following players when the teams swap
+    /// sides, instead of per side
+    #[arg(long)]
+    scores_by_clan: bool,
+
+    /// Add what players said around the end of each round to the rounds table
+    #[arg(long)]
+    round_chat: bool,
+
+    /// Credit probable assists, inferred from shots heard and blood seen before each kill, in an
+    /// Assists* column of the scoreboard
+    #[arg(long)]
+    assists: bool,
+
+    /// Treat the whole demo as one session instead of detecting when a clan match went live, e.g.
+    /// for public server demos
+    #[arg(long)]
+    no_clan_detection: bool,
+
+    /// Time in the demo, as shown by viewdemo, when the clan match went live, e.g. 12:30; use when
+    /// the match start is detected at the wrong moment
+    #[arg(long, value_name = "MM:SS", value_parser = parse_match_start)]
+    match_start: Option<u64>,
+
+    /// TOML file with the round win probability model used to rate the impact of kills, as
+    /// written by the train-win-model command [default: the model shipped with the program]
+    #[arg(long)]
+    win_model: Option<PathBuf>,
+
+    /// Clock used for times in the report [default: from the saved settings, or viewdemo]
+    #[arg(long, value_enum)]
+    clock: Option<TimeClock>,
+
+    /// Check the score each player gained in every round against the league's scoring formula,
+    /// e.g. capture=2,kill=1,teamkill=-1; points left out are 1 for captures and kills, and 0
+    /// for teamkills and suicides [default: from the saved settings, or no check]
+    #[arg(long, value_name = "FORMULA", value_parser = parse_score_formula)]
+    score_formula: Option<ScoreFormula>,
+}
+
+impl ConfigArgs {
+    /// Overrides the settings with the options that were given.
+    fn apply_to(&self, config: &mut AnalysisConfig) {
+        if let Some(min_streak) = self.min_streak {
+            config.min_streak = min_streak;
+        }
+
+        if self.scores_by_clan {
+            config.scores_by_clan = true;
+        }
+
+        if self.round_chat {
+            config.round_chat = true;
+        }
+
+        if self.assists {
+            config.infer_assists = true;
+        }
+
+        if self.no_clan_detection {
+            config.clan_match_detection = false;
+        }
+
+        config.match_start_secs = self.match_start;
+
+        if let Some(score_formula) = &self.score_formula {
+            config.score_formula = Some(score_formula.clone());
+        }
+
+        if let Some(win_model_path) = &self.win_model {
+            config.win_model = Some(
+                win_model::load_win_model(win_model_path).expect("Could not read the win model"),
+            );
+        }
+
+        if let Some(fallback_encoding) = &self.fallback_encoding {
+            config.codepage = match fallback_encoding {
+                FallbackEncoding::Latin1 => Codepage::Latin1,
+                FallbackEncoding::Windows1252 => Codepage::Windows1252,
+            };
+        }
+
+        if let Some(clock) = &self.clock {
+            config.clock = match clock {
+                TimeClock::Viewdemo => Clock::Viewdemo,
+                TimeClock::Recording => Clock::Recording,
+                TimeClock::Server => Clock::Server,
+            };
+        }
+    }
+}
+
+/// Parses a `mm:ss` time into seconds.
+fn parse_match_start(value: &str) -> Result<u64, String> {
+    let (minutes, seconds) = value.split_once(':').ok_or("expected a time like 12:30")?;
+
+    let minutes = minutes.parse::<u64>().map_err(|err| err.to_string())?;
+    let seconds = seconds.parse::<u64>().map_err(|err| err.to_string())?;
+
+    if seconds >= 60 {
+        return Err("seconds must be less than 60".to_string());
+    }
+
+    Ok(minutes * 60 + seconds)
+}
+
+/// Parses a scoring formula such as `capture=2,kill=1`.
+fn parse_score_formula(value: &str) -> Result<ScoreFormula, String> {
+    let mut formula = ScoreFormula::default();
+
+    for term in value
+        .split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+    {
+        let (event, points) = term
+            .split_once('=')
+            .ok_or("expected terms like capture=2")?;
+
+        let points = points
+            .trim()
+            .parse::<i32>()
+            .map_err(|err| err.to_string())?;
+
+        match event.trim() {
+            "capture" => formula.points_per_capture = points,
+            "kill" => formula.points_per_kill = points,
+            "teamkill" => formula.points_per_teamkill = points,
+            "suicide" => formula.points_per_suicide = points,
+            event => {
+                return Err(format!(
+                    "unknown event {event}, expected capture, kill, teamkill, or suicide"
+                ));
+            }
+        }
+    }
+
+    Ok(formula)
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print a report of each demo, such as the scoreboard, rounds, and kill streaks
+    Analyze(AnalyzeArgs),
+
+    /// Print what is known about each demo without its report, such as the map, the versions of
+    /// the game, and the server
+    Info {
+        /// List of paths to demo files
+        demo_paths: Vec<PathBuf>,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Print one scoreboard for a match recorded in several demos, e.g. both halves of a clan
+    /// match, with the results of each player summed across the demos
+    Merge {
+        /// List of paths to demo files
+        demo_paths: Vec<PathBuf>,
+
+        /// The kind of string output to produce from the scoreboard
+        #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+        output_format: OutputFormat,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Print the best moments across a set of demos, such as kill streaks, captures, and round
+    /// enders
+    Highlights {
+        /// List of paths to demo files
+        demo_paths: Vec<PathBuf>,
+
+        /// Number of highlights to print
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Print the highlights of a demo as markers to import into a video editor alongside a
+    /// recording of its playback, on the clock set with --clock
+    Markers {
+        /// Path to the demo file
+        demo_path: PathBuf,
+
+        /// Steam ID or name of a player, to only mark their highlights, e.g. STEAM_0:0:1679
+        #[arg(long)]
+        player: Option<String>,
+
+        /// Format of the markers
+        #[arg(long, value_enum, default_value_t = MarkerFormat::Edl)]
+        format: MarkerFormat,
+
+        /// Frame rate of the recording, used for the timecodes of the EDL format
+        #[arg(long, default_value_t = 60)]
+        frame_rate: u32,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Print statistics for each map across a set of demos, such as round lengths, win rates, and
+    /// the most contested flags
+    Maps {
+        /// List of paths to demo files
+        demo_paths: Vec<PathBuf>,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Print the stats of players side by side across a set of demos, including their kills on
+    /// each other
+    Compare {
+        /// Steam ID or name of a player to compare, e.g. STEAM_0:0:1679; repeat for each player
+        #[arg(long = "player", required = true)]
+        players: Vec<String>,
+
+        /// List of paths to demo files
+        demo_paths: Vec<PathBuf>,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Fit the round win probability model used to rate the impact of kills to a set of demos,
+    /// and print it as TOML for the --win-model option
+    TrainWinModel {
+        /// List of paths to demo files
+        demo_paths: Vec<PathBuf>,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// Add the demos in a directory and its subdirectories to an index, to search them later
+    /// without analyzing them again
+    Index {
+        /// Directory with the demo files
+        dir: PathBuf,
+
+        /// JSON file to write the index to, updating it if it exists. Kept in the shared storage
+        /// instead when DOD_TOOLS_STORAGE_URL is set
+        #[arg(long, default_value = DEFAULT_INDEX_KEY)]
+        index: PathBuf,
+
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+
+    /// List the indexed demos that contain a player, were recorded on a map, or were played by a
+    /// clan
+    Search {
+        /// Steam ID or name of a player, e.g. STEAM_0:0:1679
+        #[arg(long)]
+        player: Option<String>,
+
+        /// Name of a map, e.g. dod_anzio
+        #[arg(long)]
+        map: Option<String>,
+
+        /// Part of the name of either clan
+        #[arg(long)]
+        clan: Option<String>,
+
+        /// JSON file written by the index command, read from the shared storage when
+        /// DOD_TOOLS_STORAGE_URL is set
+        #[arg(long, default_value = DEFAULT_INDEX_KEY)]
+        index: PathBuf,
+    },
+
+    /// Share the files in a directory, such as the identities, the notes, and the index, with the
+    /// reviewers whose DOD_TOOLS_STORAGE_URL points to this server, and who set the same
+    /// DOD_TOOLS_STORAGE_TOKEN
+    Serve {
+        /// Directory with the shared files
+        dir: PathBuf,
+
+        /// Address and port to listen on; the server speaks plain HTTP, so only listen on a
+        /// network that nobody but the reviewers can reach
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+
+    /// Print the JSON Schema of the JSON output, to validate the tools that read it
+    Schema,
+}
+
+impl Command {
+    /// Returns the options that change how the demos are analyzed, if the command analyzes any.
+    fn config_args(&self) -> Option<&ConfigArgs> {
+        match self {
+            Self::Analyze(AnalyzeArgs { config, .. })
+            | Self::Info { config, .. }
+            | Self::Merge { config, .. }
+            | Self::Highlights { config, .. }
+            | Self::Markers { config, .. }
+            | Self::Maps { config, .. }
+            | Self::Compare { config, .. }
+            | Self::TrainWinModel { config, .. }
+            | Self::Index { config, .. } => Some(config),
+            Self::Search { .. } | Self::Serve { .. } | Self::Schema => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum OutputFormat {
+    /// Markdown document best used in combination with a Markdown renderer
+    Markdown,
+
+    /// JSON string for automated tools or custom visualization
+    Json,
+
+    /// CSV scoreboard for spreadsheets, with a row for each player of each demo
+    Csv,
+
+    /// Single HTML file with sortable tables and charts, to share with people who do not have
+    /// the program
+    Html,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+enum FallbackEncoding {
+    /// ISO-8859-1
+    Latin1,
+
+    /// Windows-1252, used by most Western European clients
+    #[value(name = "windows-1252")]
+    Windows1252,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum MarkerFormat {
+    /// Comma-separated values with a row for each highlight
+    Csv,
+
+    /// Edit decision list with a marker for each highlight, as imported by DaVinci Resolve
+    Edl,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+enum TimeClock {
+    /// Time shown by viewdemo
+    Viewdemo,
+
+    /// Time since the demo started recording
+    Recording,
+
+    /// Time stamped on each frame by the server, which starts over with each playback segment
+    Server,
+}
+
+type AnalyzerOutput = (FileInfo, Analysis);
+
+struct Json(Value);
+
+impl FromIterator<AnalyzerOutput> for Json {
+    fn from_iter<T: IntoIterator<Item = AnalyzerOutput>>(iter: T) -> Self {
+        let reports = iter
+            .into_iter()
+            .map(|(file, analysis)| Report::new(&file, &analysis))
+            .collect::<Vec<_>>();
+
+        json!(reports).into()
+    }
+}
+
+impl From<Value> for Json {
+    fn from(value: Value) -> Self {
+        Self(value)
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = serde_json::to_string_pretty(&self.0).map_err(|_| std::fmt::Error)?;
+
+        f.write_str(&str)
+    }
+}
+
+/// Returns the time reports are created at.
+///
+/// `SOURCE_DATE_EPOCH` overrides the current time so that reports of the same demos are identical.
+fn report_time() -> SystemTime {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+        .unwrap_or_else(SystemTime::now)
+}
@@ -0,0 +1,330 @@
+//! The `analyze` command, which prints or writes the report of each demo.
+
+use crate::cli::html::Html;
+use crate::cli::markdown::Markdown;
+use crate::cli::round_report::write_round_reports;
+use crate::cli::{AnalyzeArgs, AnalyzerOutput, Json, OutputFormat};
+use analysis::{AnalysisConfig, Team};
+use native::{
+    card::StatsCard,
+    escape,
+    index::DemoIndex,
+    manifest::Manifest,
+    notify::Webhook,
+    run_analyzer_by_segment, run_analyzer_with_notifier,
+    settings::{self, DisplaySettings},
+    storage,
+};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Analyzes the demos and prints or writes their reports.
+pub fn analyze(args: &AnalyzeArgs, config: &AnalysisConfig, display: &DisplaySettings) {
+    let webhook = args.notify_url.clone().map(Webhook::new);
+
+    let manifest_entries = match &args.manifest {
+        Some(manifest_path) => {
+            Manifest::load(manifest_path)
+                .expect("Could not read the manifest")
+                .demos
+        }
+        None => vec![],
+    };
+
+    let demo_index = args.index.as_ref().map(|index| {
+        DemoIndex::load_from(
+            storage::shared_or_local_storage().as_ref(),
+            &index.to_string_lossy(),
+        )
+        .expect("Could not read the index")
+    });
+
+    let demos = args
+        .demo_paths
+        .iter()
+        .map(|demo_path| (demo_path.clone(), None))
+        .chain(
+            manifest_entries
+                .into_iter()
+                .map(|entry| (entry.path.clone(), Some(entry))),
+        );
+
+    let analyses = demos
+        .flat_map(|(demo_path, manifest_entry)| {
+            let outputs = match &webhook {
+                Some(webhook) => {
+                    run_analyzer_with_notifier(&demo_path, config, &args.segments, webhook)
+                }
+                None => run_analyzer_by_segment(&demo_path, config, &args.segments),
+            };
+
+            let demo_index = demo_index.as_ref();
+
+            outputs.into_iter().map(move |(mut file_info, analysis)| {
+                file_info.manifest_entry = manifest_entry.clone();
+
+                if let Some(demo_index) = demo_index {
+                    file_info.roster_newcomers = demo_index.roster_newcomers(&file_info, &analysis);
+                }
+
+                (file_info, analysis)
+            })
+        })
+        .inspect(|output| {
+            if args.export_user_info {
+                write_user_info_history(output).expect("Could not write userinfo history");
+            }
+
+            if args.stats_cards {
+                write_stats_cards(output, display).expect("Could not write stats cards");
+            }
+        });
+
+    if args.split_rounds {
+        for output in analyses {
+            write_round_reports(&output, &args.output_format)
+                .expect("Could not write round reports");
+        }
+
+        return;
+    }
+
+    match args.output_format {
+        OutputFormat::Json => println!("{}", Json::from_iter(analyses)),
+
+        OutputFormat::Markdown => analyses
+            .map(|(file, analysis)| Markdown(file, analysis, display.clone()))
+            .for_each(|output| {
+                println!("{output}");
+            }),
+
+        OutputFormat::Html => print!("{}", Html(analyses.collect(), display.clone())),
+
+        OutputFormat::Csv => {
+            let analyses = analyses.collect::<Vec<_>>();
+
+            match &args.csv_dir {
+                Some(csv_dir) => write_csvs(&analyses, csv_dir).expect("Could not write CSV files"),
+                None => print!("{}", scoreboard_csv(&analyses)),
+            }
+        }
+    };
+}
+
+/// Formats the scoreboards of the analyses as CSV, with a row for each player of each demo.
+fn scoreboard_csv(analyses: &[AnalyzerOutput]) -> String {
+    let mut csv = String::from(
+        "file,id,name,team,class,score,kills,deaths,environment_deaths,suicides,assists,flags,\
+        objective_score,rounds_played\n",
+    );
+
+    for (file, analysis) in analyses {
+        let state = &analysis.state;
+
+        for player in &state.players {
+            let fields = [
+                escape::csv(&file.name),
+                escape::csv(&player.id.to_string()),
+                escape::csv(&player.name),
+                player
+                    .team
+                    .as_ref()
+                    .map(|team| format!("{team:?}"))
+                    .unwrap_or_default(),
+                player
+                    .class
+                    .as_ref()
+                    .map(|class| format!("{class:?}"))
+                    .unwrap_or_default(),
+                player.stats.0.to_string(),
+                player.stats.1.to_string(),
+                player.stats.2.to_string(),
+                state.environment_deaths(&player.id).to_string(),
+                state.suicides(&player.id).to_string(),
+                match analysis.config.infer_assists {
+                    true => state.assists_by(&player.id).to_string(),
+                    false => String::new(),
+                },
+                state.captures_by(&player.id).to_string(),
+                player.objective_score.to_string(),
+                player.rounds_played(&state.rounds).to_string(),
+            ];
+
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+    }
+
+    csv
+}
+
+/// Formats the kills of each player with each weapon as CSV, grouped by weapon category.
+fn weapons_csv(analyses: &[AnalyzerOutput]) -> String {
+    let mut csv = String::from("file,id,name,category,weapon,kills,teamkills\n");
+
+    for (file, analysis) in analyses {
+        for player in &analysis.state.players {
+            for category in player.weapon_breakdown_by_category() {
+                for (weapon, kills, teamkills) in category.weapons {
+                    let fields = [
+                        escape::csv(&file.name),
+                        escape::csv(&player.id.to_string()),
+                        escape::csv(&player.name),
+                        category.category.to_string(),
+                        weapon.to_string(),
+                        kills.to_string(),
+                        teamkills.to_string(),
+                    ];
+
+                    csv.push_str(&fields.join(","));
+                    csv.push('\n');
+                }
+            }
+        }
+    }
+
+    csv
+}
+
+/// Formats the notable kill streaks of each player as CSV, with the weapons separated by spaces.
+fn kill_streaks_csv(analyses: &[AnalyzerOutput]) -> String {
+    let mut csv = String::from("file,id,name,wave,kills,start_time,weapons,seek\n");
+
+    for (file, analysis) in analyses {
+        for player in &analysis.state.players {
+            for (wave, streak) in player.notable_kill_streaks(analysis.config.min_streak) {
+                let start_time = streak
+                    .kills
+                    .first()
+                    .map(|(time, _)| settings::format_game_time(time, &analysis.config))
+                    .unwrap_or_default();
+
+                let weapons = streak
+                    .kills
+                    .iter()
+                    .map(|(_, weapon)| weapon.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let fields = [
+                    escape::csv(&file.name),
+                    escape::csv(&player.id.to_string()),
+                    escape::csv(&player.name),
+                    (wave + 1).to_string(),
+                    streak.kills.len().to_string(),
+                    start_time,
+                    escape::csv(&weapons),
+                    streak
+                        .kills
+                        .first()
+                        .map(|(time, _)| escape::csv(&file.seek_command(time)))
+                        .unwrap_or_default(),
+                ];
+
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+        }
+    }
+
+    csv
+}
+
+/// Writes the scoreboard, weapon, and kill streak CSV files of the analyses into a directory.
+fn write_csvs(analyses: &[AnalyzerOutput], csv_dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(csv_dir)?;
+
+    for (name, contents) in [
+        ("scoreboard.csv", scoreboard_csv(analyses)),
+        ("weapons.csv", weapons_csv(analyses)),
+        ("kill-streaks.csv", kill_streaks_csv(analyses)),
+    ] {
+        let csv_path = csv_dir.join(name);
+
+        fs::write(&csv_path, contents)?;
+
+        eprintln!("Wrote {}", csv_path.display());
+    }
+
+    Ok(())
+}
+
+/// Writes the userinfo history of an analysis as JSON, grouped by player slot and named after the
+/// demo file.
+fn write_user_info_history((file, analysis): &AnalyzerOutput) -> std::io::Result<()> {
+    let file_stem = Path::new(&file.name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&file.name);
+
+    let slots = analysis.state.user_info_history.iter().fold(
+        BTreeMap::<u8, Vec<Value>>::new(),
+        |mut acc, update| {
+            acc.entry(update.slot).or_default().push(json!({
+                "time": settings::format_game_time(&update.time, &analysis.config),
+                "connection_id": update.connection_id,
+                "user_info": update.user_info,
+            }));
+
+            acc
+        },
+    );
+
+    let contents = json!({
+        "file": file.path,
+        "slots": slots,
+    });
+
+    let export_path = PathBuf::from(format!("{file_stem}.userinfo.json"));
+
+    fs::write(&export_path, contents.to_string())?;
+
+    eprintln!("Wrote {}", export_path.display());
+
+    Ok(())
+}
+
+/// Writes a stats card image for every player that was on a team, named after the demo file, the
+/// player's position in the scoreboard, and the player.
+fn write_stats_cards(
+    (file, analysis): &AnalyzerOutput,
+    display: &DisplaySettings,
+) -> image::ImageResult<()> {
+    let file_stem = Path::new(&file.name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&file.name);
+
+    // The position keeps the file names apart when the names of players sanitize to the same one
+    let players = analysis
+        .state
+        .players
+        .iter()
+        .enumerate()
+        .filter(|(_, player)| matches!(player.team, Some(Team::Allies | Team::Axis)));
+
+    for (i, player) in players {
+        // Names can contain anything, so only keep characters that are safe in file names
+        let player_name = player
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+
+        let card_path = PathBuf::from(format!("{file_stem}.{}-{player_name}.png", i + 1));
+
+        StatsCard::new(analysis, player, display).save_png(&card_path)?;
+
+        eprintln!("Wrote {}", card_path.display());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//! The `compare` command, which prints the stats of players side by side.
+
+use analysis::{AnalysisConfig, SteamId};
+use native::{escape, run_analyzer, win_model};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tabled::{builder::Builder, settings::Style};
+
+/// Totals of a player across the demos they played in.
+#[derive(Default)]
+struct PlayerTotals {
+    demos: usize,
+    rounds: usize,
+    score: i32,
+    kills: i32,
+    deaths: i32,
+    impact_rating: f32,
+
+    /// Kills made on each of the other compared players, by their position in the comparison.
+    kills_on: BTreeMap<usize, u32>,
+}
+
+/// Prints a Markdown table with the totals of the players side by side across the demos.
+///
+/// Players are matched in each demo by Steam ID, global ID, or name.
+pub fn print_comparison(players: &[String], demo_paths: &[PathBuf], config: &AnalysisConfig) {
+    let mut totals = players
+        .iter()
+        .map(|_| PlayerTotals::default())
+        .collect::<Vec<_>>();
+
+    for demo_path in demo_paths {
+        let (_, analysis) = run_analyzer(demo_path, config);
+        let state = &analysis.state;
+        let impact_ratings = state.impact_ratings(&win_model::win_model(&analysis.config));
+        let matchups = state.matchups();
+
+        let found = players
+            .iter()
+            .map(|query| {
+                state.players.iter().find(|player| {
+                    let steam_id =
+                        SteamId::try_from(&player.id).map(|steam_id| steam_id.to_string());
+
+                    steam_id.as_ref() == Ok(query)
+                        || player.id.to_string() == *query
+                        || player.name.eq_ignore_ascii_case(query)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for (totals, player) in totals.iter_mut().zip(&found) {
+            let Some(player) = player else {
+                continue;
+            };
+
+            totals.demos += 1;
+            totals.rounds += player.rounds_played(&state.rounds);
+            totals.score += player.stats.0;
+            totals.kills += player.stats.1;
+            totals.deaths += player.stats.2;
+            totals.impact_rating += impact_ratings.get(&player.id).copied().unwrap_or_default();
+
+            for (other, victim) in found.iter().enumerate() {
+                let Some(victim) = victim else {
+                    continue;
+                };
+
+                let kills = matchups
+                    .iter()
+                    .find(|matchup| matchup.killer == player.id && matchup.victim == victim.id)
+                    .map(|matchup| matchup.kills())
+                    .unwrap_or(0);
+
+                *totals.kills_on.entry(other).or_default() += kills;
+            }
+        }
+    }
+
+    let per = |value: f32, count: usize| match count {
+        0 => "-".to_string(),
+        count => format!("{:.2}", value / count as f32),
+    };
+
+    let row = |label: &str, value: &dyn Fn(&PlayerTotals) -> String| {
+        (
+            label.to_string(),
+            totals.iter().map(value).collect::<Vec<_>>(),
+        )
+    };
+
+    let mut rows = vec![
+        row("Demos", &|t| t.demos.to_string()),
+        row("Rounds", &|t| t.rounds.to_string()),
+        row("Score", &|t| t.score.to_string()),
+        row("Kills", &|t| t.kills.to_string()),
+        row("Deaths", &|t| t.deaths.to_string()),
+        row("K/D", &|t| per(t.kills as f32, t.deaths.max(0) as usize)),
+        row("Kills per Round", &|t| per(t.kills as f32, t.rounds)),
+        row("Score per Demo", &|t| per(t.score as f32, t.demos)),
+        row("Impact Rating", &|t| format!("{:+.2}", t.impact_rating)),
+    ];
+
+    // Head-to-head record, as the kills each player made on each other player
+    for (other, name) in players.iter().enumerate() {
+        rows.push((
+            format!("Kills on {}", escape::markdown(name)),
+            totals
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    if i == other {
+                        "-".to_string()
+                    } else {
+                        t.kills_on.get(&other).copied().unwrap_or(0).to_string()
+                    }
+                })
+                .collect(),
+        ));
+    }
+
+    let mut table_builder = Builder::default();
+    table_builder.push_record(
+        std::iter::once(String::new()).chain(players.iter().map(|name| escape::markdown(name))),
+    );
+
+    for (label, values) in rows {
+        table_builder.push_record(std::iter::once(label).chain(values));
+    }
+
+    let mut table = table_builder.build();
+    table.with(Style::markdown());
+
+    println!("## Comparison\n");
+    println!("{table}");
+}
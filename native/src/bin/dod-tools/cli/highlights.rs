@@ -0,0 +1,53 @@
+//! The `highlights` command, which prints the best moments across a set of demos.
+
+use analysis::AnalysisConfig;
+use native::{run_analyzer, settings};
+use std::path::PathBuf;
+use tabled::{builder::Builder, settings::Style};
+
+/// Prints a table of the highest ranked highlights of all players across the demos.
+pub fn print_highlights(demo_paths: &[PathBuf], top: usize, config: &AnalysisConfig) {
+    let analyses = demo_paths
+        .iter()
+        .map(|demo_path| run_analyzer(demo_path, config))
+        .collect::<Vec<_>>();
+
+    let players_to_watch = analyses
+        .iter()
+        .map(|(file, analysis)| (file, analysis.state.players_to_watch()))
+        .collect::<Vec<_>>();
+
+    let mut highlights = players_to_watch
+        .iter()
+        .flat_map(|(file, players)| {
+            players.iter().flat_map(move |watch| {
+                watch
+                    .highlights
+                    .iter()
+                    .map(move |highlight| (file, watch.player, highlight))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Stable, so ties keep the order of the demos
+    highlights.sort_by(|(_, _, l), (_, _, r)| l.kind.weight().cmp(&r.kind.weight()).reverse());
+
+    let mut table_builder = Builder::default();
+    table_builder.push_record(["Rank", "Demo", "Time", "Player", "Highlight", "Seek"]);
+
+    for (rank, (file, player, highlight)) in highlights.into_iter().take(top).enumerate() {
+        table_builder.push_record([
+            (rank + 1).to_string(),
+            file.path.clone(),
+            settings::format_game_time(&highlight.time, config),
+            player.name.clone(),
+            highlight.kind.to_string(),
+            file.seek_command(&highlight.time),
+        ]);
+    }
+
+    let mut table = table_builder.build();
+    table.with(Style::rounded());
+
+    println!("{table}");
+}
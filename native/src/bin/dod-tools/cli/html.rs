@@ -0,0 +1,345 @@
+//! HTML report of a set of demos, to share without the program.
+
+use crate::cli::{AnalyzerOutput, report_time};
+use analysis::{Clan, KillCause, MortalityState, PlayerGlobalId, Round, Team};
+use humantime::format_duration;
+use native::{
+    escape,
+    html::{self, Cell},
+    icons,
+    settings::{self, DisplaySettings},
+    timeline,
+};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Standalone HTML document with a report of each analysis, to share without the program.
+pub struct Html(pub Vec<AnalyzerOutput>, pub DisplaySettings);
+
+impl Html {
+    fn report(&self, (file, analysis): &AnalyzerOutput) -> String {
+        let state = &analysis.state;
+        let config = &analysis.config;
+        let display = &self.1;
+        let mut html = String::from("<section class=\"report\">\n");
+
+        let player_name = |id: &PlayerGlobalId| {
+            state
+                .players
+                .iter()
+                .find(|player| player.id == *id)
+                .map(|player| player.name.clone())
+                .unwrap_or_default()
+        };
+
+        let (allies, axis) = (
+            Clan::new(state, Team::Allies, file.rosters()),
+            Clan::new(state, Team::Axis, file.rosters()),
+        );
+        let (allies_score, axis_score) = (
+            state.team_scores.get_team_score(Team::Allies),
+            state.team_scores.get_team_score(Team::Axis),
+        );
+
+        // Header section
+        {
+            html.push_str(&format!(
+                "<h1>{} on {}</h1>\n<ul>\n",
+                escape::html(&file.name),
+                escape::html(&analysis.demo_info.map_name)
+            ));
+
+            let match_start = match state.match_start_time() {
+                Some(time) => settings::format_game_time(time, config),
+                None if !config.clan_match_detection => "Detection disabled".to_string(),
+                None => "Not detected".to_string(),
+            };
+
+            let mut items = vec![
+                (
+                    "Teams",
+                    format!(
+                        "{} (Allies) {allies_score} - {axis_score} {} (Axis)",
+                        allies.name(),
+                        axis.name()
+                    ),
+                ),
+                (
+                    "File created at",
+                    settings::format_local_time(file.created_at, display),
+                ),
+                ("Match went live at", match_start),
+                (
+                    "Game version (estimated)",
+                    analysis.demo_info.game_version.to_string(),
+                ),
+            ];
+
+            if let Some(label) = file.manifest_entry.as_ref().and_then(|entry| entry.label()) {
+                items.push(("Match", label));
+            }
+
+            items.push((
+                "Report created at",
+                settings::format_local_time(report_time(), display),
+            ));
+
+            for (name, value) in items {
+                html.push_str(&format!("<li>{name}: {}</li>\n", escape::html(&value)));
+            }
+
+            html.push_str("</ul>\n");
+        }
+
+        // Player scoreboard section
+        {
+            let show_assists = config.infer_assists;
+            let mut header = vec!["Name", "Team", "Class", "Score", "Kills", "Deaths"];
+
+            if show_assists {
+                header.push("Assists");
+            }
+
+            header.extend(["Flags", "Caps/Obj Points", "Rounds", "Avg. Life"]);
+
+            let rows = state
+                .players
+                .iter()
+                .map(|player| {
+                    let avg_lifespan = player.avg_lifespan();
+                    let mut row = vec![
+                        Cell::from(player.name.as_str()),
+                        Cell::from(
+                            player
+                                .team
+                                .as_ref()
+                                .map(|team| format!("{team:?}"))
+                                .unwrap_or("Unknown".to_string()),
+                        ),
+                        Cell::from(
+                            player
+                                .class
+                                .as_ref()
+                                .map(|class| format!("{class:?}"))
+                                .unwrap_or("Unknown".to_string()),
+                        ),
+                        Cell::number(player.stats.0, player.stats.0 as f64),
+                        Cell::number(player.stats.1, player.stats.1 as f64),
+                        Cell::number(player.stats.2, player.stats.2 as f64),
+                    ];
+
+                    if show_assists {
+                        let assists = state.assists_by(&player.id);
+                        row.push(Cell::number(assists, assists as f64));
+                    }
+
+                    let captures = state.captures_by(&player.id);
+                    row.extend([
+                        Cell::number(captures, captures as f64),
+                        Cell::number(player.objective_score, player.objective_score as f64),
+                        Cell::number(
+                            player.rounds_played(&state.rounds),
+                            player.rounds_played(&state.rounds) as f64,
+                        ),
+                        Cell::number(format_duration(avg_lifespan), avg_lifespan.as_secs_f64()),
+                    ]);
+
+                    row
+                })
+                .collect();
+
+            html.push_str("<h2>Scoreboard</h2>\n");
+            html.push_str(&html::table(&header, rows));
+        }
+
+        // Team score timeline section
+        {
+            let series = timeline::team_score_timeline(analysis, file.rosters());
+
+            html.push_str("<h2>Team Scores</h2>\n");
+            html.push_str(&html::timeline_chart(&series, display));
+        }
+
+        // Rounds section
+        {
+            let show_time_left = state.is_time_left_known();
+            let mut header = vec![
+                "Round",
+                "Start Time",
+                "Duration",
+                "Winner",
+                "Kills by Winner",
+            ];
+
+            if show_time_left {
+                header.push("Time Left");
+            }
+
+            let rows = state
+                .rounds
+                .iter()
+                .enumerate()
+                .filter_map(|(i, round)| {
+                    let Round::Completed {
+                        start_time,
+                        end_time,
+                        winner_stats,
+                        ..
+                    } = round
+                    else {
+                        return None;
+                    };
+
+                    let duration = Duration::new((end_time - start_time).as_secs(), 0);
+                    let mut row = vec![
+                        Cell::number(i + 1, (i + 1) as f64),
+                        Cell::number(
+                            settings::format_game_time(start_time, config),
+                            start_time.offset(&config.clock).as_secs_f64(),
+                        ),
+                        Cell::number(format_duration(duration), duration.as_secs_f64()),
+                        Cell::from(
+                            winner_stats
+                                .as_ref()
+                                .map(|(winner, _)| format!("{winner:?}"))
+                                .unwrap_or_default(),
+                        ),
+                        Cell::from(
+                            winner_stats
+                                .as_ref()
+                                .map(|(_, kills)| kills.to_string())
+                                .unwrap_or_default(),
+                        ),
+                    ];
+
+                    if show_time_left {
+                        row.push(match state.time_left_at(end_time) {
+                            Some(time_left) => Cell::number(
+                                format_duration(Duration::new(time_left.as_secs(), 0)),
+                                time_left.as_secs_f64(),
+                            ),
+                            None => Cell::number("", 0.),
+                        });
+                    }
+
+                    Some(row)
+                })
+                .collect();
+
+            html.push_str("<h2>Rounds</h2>\n");
+            html.push_str(&html::table(&header, rows));
+        }
+
+        // Players to watch section
+        {
+            html.push_str("<h2>Players to Watch</h2>\n<ol>\n");
+
+            for watch in state.players_to_watch() {
+                let highlights = watch
+                    .highlights
+                    .iter()
+                    .map(|highlight| {
+                        format!(
+                            "{}: {}",
+                            settings::format_game_time(&highlight.time, config),
+                            highlight.kind
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+
+                html.push_str(&format!(
+                    "<li>{} ({} points): {}</li>\n",
+                    escape::html(&watch.player.name),
+                    watch.score,
+                    escape::html(&highlights)
+                ));
+            }
+
+            html.push_str("</ol>\n");
+        }
+
+        // Kill feed section
+        {
+            let rows = state
+                .kill_feed(None)
+                .into_iter()
+                .map(|entry| {
+                    let kill = entry.kill;
+
+                    vec![
+                        Cell::number(
+                            settings::format_game_time(&kill.time, config),
+                            kill.time.offset(&config.clock).as_secs_f64(),
+                        ),
+                        Cell::from(match kill.cause {
+                            KillCause::Player | KillCause::Suicide => {
+                                kill.killer.as_ref().map(player_name).unwrap_or_default()
+                            }
+                            KillCause::World => "World".to_string(),
+                        }),
+                        Cell::from(player_name(&kill.victim)),
+                        Cell::from(icons::weapon_label(&kill.weapon)),
+                        Cell::from(entry.notes().join(", ")),
+                    ]
+                })
+                .collect();
+
+            html.push_str("<h2>Kill Feed</h2>\n");
+            html.push_str(&html::table(
+                &["Time", "Killer", "Victim", "Weapon", "Note"],
+                rows,
+            ));
+        }
+
+        // Team kills section
+        {
+            let rows = state
+                .teamkills
+                .iter()
+                .map(|teamkill| {
+                    vec![
+                        Cell::number(
+                            settings::format_game_time(&teamkill.time, config),
+                            teamkill.time.offset(&config.clock).as_secs_f64(),
+                        ),
+                        state
+                            .round_time(&teamkill.time)
+                            .map(|(round, _)| Cell::number(round, round as f64))
+                            .unwrap_or_else(|| Cell::number("", 0.)),
+                        Cell::from(player_name(&teamkill.killer)),
+                        Cell::from(player_name(&teamkill.victim)),
+                        Cell::from(icons::weapon_label(&teamkill.weapon)),
+                    ]
+                })
+                .collect();
+
+            html.push_str("<h2>Team Kills</h2>\n");
+            html.push_str(&html::table(
+                &["Time", "Round", "Killer", "Victim", "Weapon"],
+                rows,
+            ));
+        }
+
+        html.push_str("</section>\n");
+
+        html
+    }
+}
+
+impl Display for Html {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let title = match self.0.as_slice() {
+            [(file, analysis)] => format!("{} on {}", file.name, analysis.demo_info.map_name),
+            outputs => format!("{} demos", outputs.len()),
+        };
+
+        let body = self
+            .0
+            .iter()
+            .map(|output| self.report(output))
+            .collect::<String>();
+
+        write!(f, "{}", html::document(&title, &body))
+    }
+}
@@ -0,0 +1,27 @@
+//! The `index` command, which adds the demos in a directory to a searchable index.
+
+use analysis::AnalysisConfig;
+use native::index::DemoIndex;
+use native::storage;
+use std::io;
+use std::path::Path;
+
+/// Adds the demos in the directory to the index stored under the key, creating it if there is
+/// none yet.
+pub fn update_index(dir: &Path, index: &Path, config: &AnalysisConfig) {
+    let storage = storage::shared_or_local_storage();
+    let key = index.to_string_lossy();
+    let mut demo_index = match DemoIndex::load_from(storage.as_ref(), &key) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => DemoIndex::default(),
+        demo_index => demo_index.expect("Could not read the index"),
+    };
+
+    demo_index
+        .update(dir, config)
+        .expect("Could not read the demos directory");
+    demo_index
+        .save_to(storage.as_ref(), &key)
+        .expect("Could not write the index");
+
+    println!("Indexed {} demos", demo_index.demos.len());
+}
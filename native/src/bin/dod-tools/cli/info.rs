@@ -0,0 +1,57 @@
+//! The `info` command, which prints what is known about each demo without its report.
+
+use analysis::AnalysisConfig;
+use native::{
+    run_analyzer,
+    settings::{self, DisplaySettings},
+};
+use std::path::PathBuf;
+use tabled::{builder::Builder, settings::Style};
+
+/// Prints a table of the details of each demo that are known before its report, such as the
+/// versions of the game and whether the server was modified.
+pub fn print_info(demo_paths: &[PathBuf], config: &AnalysisConfig, display: &DisplaySettings) {
+    let mut table_builder = Builder::default();
+    table_builder.push_record([
+        "Demo",
+        "Created at",
+        "Map",
+        "Demo protocol",
+        "Network protocol",
+        "Game version",
+        "Server",
+        "Segments",
+        "Players",
+    ]);
+
+    for demo_path in demo_paths {
+        let (file, analysis) = run_analyzer(demo_path, config);
+        let demo_info = &analysis.demo_info;
+
+        let server = if demo_info.is_modified_server() {
+            format!(
+                "modified, registers {}",
+                demo_info.extra_messages.join(", ")
+            )
+        } else {
+            "stock".to_string()
+        };
+
+        table_builder.push_record([
+            file.path.clone(),
+            settings::format_local_time(file.created_at, display),
+            demo_info.map_name.clone(),
+            demo_info.demo_protocol.to_string(),
+            demo_info.network_protocol.to_string(),
+            demo_info.game_version.to_string(),
+            server,
+            demo_info.playback_segments().count().to_string(),
+            analysis.state.players.len().to_string(),
+        ]);
+    }
+
+    let mut table = table_builder.build();
+    table.with(Style::rounded());
+
+    println!("{table}");
+}
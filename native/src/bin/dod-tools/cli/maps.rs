@@ -0,0 +1,103 @@
+//! The `maps` command, which prints statistics for each map across a set of demos.
+
+use analysis::{AnalysisConfig, Round, Team};
+use humantime::format_duration;
+use native::{escape, run_analyzer};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tabled::{builder::Builder, settings::Style};
+
+/// Totals of the demos recorded on a map.
+#[derive(Default)]
+struct MapStats {
+    demos: usize,
+    round_durations: Vec<Duration>,
+    allies_wins: usize,
+    axis_wins: usize,
+
+    /// Number of captures of each flag.
+    captures: BTreeMap<String, usize>,
+}
+
+/// Prints a Markdown section with the totals of each map across the demos.
+pub fn print_maps(demo_paths: &[PathBuf], config: &AnalysisConfig) {
+    let mut maps = BTreeMap::<String, MapStats>::new();
+
+    for demo_path in demo_paths {
+        let (_, analysis) = run_analyzer(demo_path, config);
+        let map = maps.entry(analysis.demo_info.map_name.clone()).or_default();
+
+        map.demos += 1;
+
+        for round in &analysis.state.rounds {
+            if let Round::Completed {
+                start_time,
+                end_time,
+                winner_stats,
+                ..
+            } = round
+            {
+                map.round_durations.push(end_time - start_time);
+
+                match winner_stats {
+                    Some((Team::Allies, _)) => map.allies_wins += 1,
+                    Some((Team::Axis, _)) => map.axis_wins += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        for capture in &analysis.state.captures {
+            *map.captures.entry(capture.point_name.clone()).or_default() += 1;
+        }
+    }
+
+    let mut table_builder = Builder::default();
+    table_builder.push_record([
+        "Map",
+        "Demos",
+        "Rounds",
+        "Avg. Round",
+        "Allies Wins",
+        "Axis Wins",
+        "Most Contested Flag",
+    ]);
+
+    for (map_name, map) in maps {
+        let avg_round = match map.round_durations.len() {
+            0 => Duration::ZERO,
+            len => map.round_durations.iter().sum::<Duration>() / len as u32,
+        };
+
+        let win_rate = |wins: usize| match map.allies_wins + map.axis_wins {
+            0 => "-".to_string(),
+            won_rounds => format!("{:.0}%", wins as f32 / won_rounds as f32 * 100.),
+        };
+
+        let most_contested_flag = map
+            .captures
+            .iter()
+            .max_by_key(|(_, captures)| **captures)
+            .map(|(point_name, captures)| {
+                format!("{} ({captures} caps)", escape::markdown(point_name))
+            })
+            .unwrap_or_default();
+
+        table_builder.push_record([
+            escape::markdown(&map_name),
+            map.demos.to_string(),
+            map.round_durations.len().to_string(),
+            format_duration(Duration::new(avg_round.as_secs(), 0)).to_string(),
+            win_rate(map.allies_wins),
+            win_rate(map.axis_wins),
+            most_contested_flag,
+        ]);
+    }
+
+    let mut table = table_builder.build();
+    table.with(Style::markdown());
+
+    println!("## Maps\n");
+    println!("{table}");
+}
@@ -0,0 +1,1170 @@
+//! Markdown report of a demo.
+
+use crate::cli::report_time;
+use analysis::{
+    ASSIST_WINDOW, Analysis, ChatMessage, Clan, Clock, Consistency, DeathEconomy, KillCause,
+    KillLatency, KillParticipation, MatchPace, MortalityState, Narrative, PlayerGlobalId, Round,
+    TRADE_WINDOW, Team,
+};
+use humantime::format_duration;
+use native::{
+    ANALYZER_VERSION, FileInfo, escape,
+    settings::{self, DisplaySettings},
+    win_model,
+};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use tabled::{builder::Builder, settings::Style};
+
+/// Number of areas listed in the danger zones section of a Markdown report.
+const DANGER_ZONES_SIZE: usize = 10;
+
+pub struct Markdown(pub FileInfo, pub Analysis, pub DisplaySettings);
+
+impl Display for Markdown {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Header section
+        {
+            let file_name = escape::markdown(&self.0.name);
+            let map_name = escape::markdown(&self.1.demo_info.map_name);
+            writeln!(f, "# Summary: {file_name} on {map_name}\n")?;
+
+            let file_path = escape::printable(&self.0.path);
+            writeln!(f, "- File path: `{file_path}`")?;
+            let file_created_at = settings::format_local_time(self.0.created_at, &self.2);
+            writeln!(f, "- File created at: {file_created_at}")?;
+            let demo_protocol = &self.1.demo_info.demo_protocol;
+            writeln!(f, "- Demo protocol: {demo_protocol}")?;
+            let network_protocol = &self.1.demo_info.network_protocol;
+            writeln!(f, "- Network protocol: {network_protocol}")?;
+            let game_version = &self.1.demo_info.game_version;
+            writeln!(f, "- Game version (estimated): {game_version}")?;
+            let server = if self.1.demo_info.is_modified_server() {
+                let extra_messages = self.1.demo_info.extra_messages.join(", ");
+                format!("modified, registers {}", escape::markdown(&extra_messages))
+            } else {
+                "stock".to_string()
+            };
+            writeln!(f, "- Server: {server}")?;
+            let directory_entries = self
+                .1
+                .demo_info
+                .directory_entries
+                .iter()
+                .map(|entry| escape::markdown(&entry.to_string()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(f, "- Directory: {directory_entries}")?;
+            if self.1.demo_info.has_multiple_segments() {
+                let segments = self.1.demo_info.playback_segments().count();
+                writeln!(
+                    f,
+                    "- Playback segments: {segments}; the demo was stopped and recorded again, so \
+                    events between the segments are missing"
+                )?;
+            }
+            let match_start = match self.1.state.match_start_time() {
+                Some(time) => {
+                    let time = settings::format_game_time(time, &self.1.config);
+                    let source = match self.1.config.match_start_secs {
+                        Some(_) => "set manually",
+                        None => "detected",
+                    };
+                    format!("{time} ({source})")
+                }
+                None if !self.1.config.clan_match_detection => "Detection disabled".to_string(),
+                None => "Not detected".to_string(),
+            };
+            writeln!(f, "- Match went live at: {match_start}")?;
+            let clock = match self.1.config.clock {
+                Clock::Viewdemo => "time shown by viewdemo",
+                Clock::Recording => "time since the demo started recording",
+                Clock::Server => "time stamped on each frame by the server",
+            };
+            writeln!(f, "- Times shown as: {clock}")?;
+            if self.1.state.match_start_candidates().len() > 1 {
+                let candidates = self
+                    .1
+                    .state
+                    .match_start_candidates()
+                    .iter()
+                    .map(|time| settings::format_game_time(time, &self.1.config))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(f, "- Restarts before going live: {candidates}")?;
+            }
+            if let Some(label) = self
+                .0
+                .manifest_entry
+                .as_ref()
+                .and_then(|entry| entry.label())
+            {
+                let label = escape::markdown(&label);
+                writeln!(f, "- Match: {label}")?;
+            }
+            let allies = Clan::new(&self.1.state, Team::Allies, self.0.rosters());
+            let axis = Clan::new(&self.1.state, Team::Axis, self.0.rosters());
+            let (allies_name, axis_name) = (
+                escape::markdown(&allies.name()),
+                escape::markdown(&axis.name()),
+            );
+            writeln!(f, "- Teams: {allies_name} (Allies) vs {axis_name} (Axis)")?;
+            let app_version = ANALYZER_VERSION;
+            writeln!(f, "- Analyzer version: {app_version}")?;
+            let report_created_at = settings::format_local_time(report_time(), &self.2);
+            writeln!(f, "- Report created at: {report_created_at}")?;
+        }
+
+        writeln!(f)?;
+
+        // Player scoreboard section
+        {
+            let show_assists = self.1.config.infer_assists;
+            let mut table_builder = Builder::default();
+            let mut header = vec!["ID", "Name", "Team", "Class", "Score", "Kills", "Deaths"];
+
+            if show_assists {
+                header.push("Assists*");
+            }
+
+            header.extend([
+                "Env. Deaths",
+                "Suicides",
+                "Flags",
+                "Caps/Obj Points",
+                "Rounds",
+                "Avg. Life",
+                "Min. Life",
+                "Max. Life",
+                "Consistency",
+                "Kill Participation",
+            ]);
+
+            table_builder.push_record(header);
+
+            for player in &self.1.state.players {
+                let consistency = Consistency::new(player, &self.1.state.rounds);
+                let participation = KillParticipation::new(player, &self.1.state);
+
+                let mut record = vec![
+                    player.id.to_string(),
+                    escape::markdown(&player.name),
+                    match &player.team {
+                        None => "Unknown",
+                        Some(Team::Allies) => "Allies",
+                        Some(Team::Axis) => "Axis",
+                        Some(Team::Spectators) => "Spectators",
+                    }
+                    .to_string(),
+                    match &player.class {
+                        None => "Unknown".to_string(),
+                        Some(x) => format!("{x:?}"),
+                    },
+                    player.stats.0.to_string(),
+                    player.stats.1.to_string(),
+                    player.stats.2.to_string(),
+                ];
+
+                if show_assists {
+                    record.push(self.1.state.assists_by(&player.id).to_string());
+                }
+
+                record.extend([
+                    self.1.state.environment_deaths(&player.id).to_string(),
+                    self.1.state.suicides(&player.id).to_string(),
+                    self.1.state.captures_by(&player.id).to_string(),
+                    player.objective_score.to_string(),
+                    player.rounds_played(&self.1.state.rounds).to_string(),
+                    format_duration(player.avg_lifespan()).to_string(),
+                    format_duration(player.min_lifespan()).to_string(),
+                    format_duration(player.max_lifespan()).to_string(),
+                    format!(
+                        "{:.2} ({})",
+                        consistency.index,
+                        if consistency.is_steady() {
+                            "Steady"
+                        } else {
+                            "Streaky"
+                        }
+                    ),
+                    format!("{:.0}%", participation.average * 100.),
+                ]);
+
+                table_builder.push_record(record);
+            }
+
+            let (allies_score, axis_score) = (
+                self.1.state.team_scores.get_team_score(Team::Allies),
+                self.1.state.team_scores.get_team_score(Team::Axis),
+            );
+
+            let (allies, axis) = (
+                Clan::new(&self.1.state, Team::Allies, self.0.rosters()),
+                Clan::new(&self.1.state, Team::Axis, self.0.rosters()),
+            );
+
+            let match_result_fragment = format!(
+                ": {} ({}) {} {} ({})",
+                allies.name(),
+                allies_score,
+                if allies_score > axis_score { ">" } else { "<" },
+                axis.name(),
+                axis_score
+            );
+
+            writeln!(f, "## Scoreboard{match_result_fragment}\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+
+            if show_assists {
+                writeln!(
+                    f,
+                    "\n\\* Probable assists: a teammate of the killer was heard firing, and blood \
+                    was seen near the victim, in the {} s before the kill.",
+                    ASSIST_WINDOW.as_secs()
+                )?;
+            }
+        }
+
+        writeln!(f)?;
+
+        // Rounds section
+        {
+            let show_chat = self.1.config.round_chat;
+            let show_time_left = self.1.state.is_time_left_known();
+            let mut table_builder = Builder::default();
+            let mut header = vec![
+                "Round",
+                "Start Time",
+                "Duration",
+                "Winner",
+                "Kills by Winner",
+            ];
+
+            if show_time_left {
+                header.push("Time Left");
+            }
+
+            if show_chat {
+                header.push("Chat");
+            }
+
+            table_builder.push_record(header);
+
+            let format_chat = |messages: &[&ChatMessage]| {
+                messages
+                    .iter()
+                    .map(|message| {
+                        let sender = message
+                            .sender
+                            .as_ref()
+                            .and_then(|id| self.1.state.players.iter().find(|p| p.id == *id))
+                            .map(|player| escape::markdown(&player.name))
+                            .unwrap_or_default();
+
+                        format!("{sender}: {}", escape::markdown(&message.text))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            };
+
+            let mut rounds = self.1.state.rounds.iter().enumerate();
+
+            while let Some((
+                i,
+                Round::Completed {
+                    start_time,
+                    end_time,
+                    winner_stats,
+                    ..
+                },
+            )) = rounds.next()
+            {
+                let duration = Duration::new((end_time - start_time).as_secs(), 0);
+                let mut record = vec![
+                    (i + 1).to_string(),
+                    settings::format_game_time(start_time, &self.1.config),
+                    format_duration(duration).to_string(),
+                    if let Some((winner, _)) = winner_stats {
+                        format!("{winner:?}")
+                    } else {
+                        String::new()
+                    },
+                    if let Some((_, kills)) = winner_stats {
+                        kills.to_string()
+                    } else {
+                        String::new()
+                    },
+                ];
+
+                if show_time_left {
+                    let time_left = self.1.state.time_left_at(end_time).map(|time_left| {
+                        format_duration(Duration::new(time_left.as_secs(), 0)).to_string()
+                    });
+
+                    record.push(time_left.unwrap_or_default());
+                }
+
+                if show_chat {
+                    let chat = self.1.state.round_chat(i).map(|chat| {
+                        match (chat.before.is_empty(), chat.after.is_empty()) {
+                            (_, true) => format_chat(&chat.before),
+                            (true, false) => format!("After: {}", format_chat(&chat.after)),
+                            (false, false) => format!(
+                                "{}; after: {}",
+                                format_chat(&chat.before),
+                                format_chat(&chat.after)
+                            ),
+                        }
+                    });
+
+                    record.push(chat.unwrap_or_default());
+                }
+
+                table_builder.push_record(record);
+            }
+
+            writeln!(f, "## Rounds\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+        }
+
+        writeln!(f)?;
+
+        // Match pace section
+        {
+            let pace = MatchPace::new(&self.1.state);
+            let format_secs = |duration: Duration| {
+                format_duration(Duration::new(duration.as_secs(), 0)).to_string()
+            };
+
+            writeln!(f, "## Match Pace\n")?;
+
+            writeln!(
+                f,
+                "- Average round duration: {}",
+                format_secs(pace.avg_duration)
+            )?;
+            writeln!(
+                f,
+                "- Median round duration: {}",
+                format_secs(pace.median_duration)
+            )?;
+
+            for (label, round) in [("Fastest", pace.fastest()), ("Slowest", pace.slowest())] {
+                if let Some(round) = round {
+                    writeln!(
+                        f,
+                        "- {label} round: {} ({}, started at {})",
+                        round.number,
+                        format_secs(round.duration),
+                        format_secs(round.start_time.offset(&self.1.config.clock))
+                    )?;
+                }
+            }
+
+            writeln!(f)?;
+
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Round", "Duration", "Kills", "Kills per Minute"]);
+
+            for round in &pace.rounds {
+                table_builder.push_record([
+                    round.number.to_string(),
+                    format_secs(round.duration),
+                    round.kills.to_string(),
+                    format!("{:.1}", round.kills_per_minute),
+                ]);
+            }
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+        }
+
+        writeln!(f)?;
+
+        // Economy of deaths section
+        {
+            writeln!(f, "## Economy of Deaths\n")?;
+
+            let mut table_builder = Builder::default();
+            table_builder.push_record([
+                "Team",
+                "Deaths",
+                "Flags Captured",
+                "Deaths per Flag",
+                "Traded Deaths",
+                "Trade Kills",
+            ]);
+
+            for team in [Team::Allies, Team::Axis] {
+                let economy = DeathEconomy::new(&self.1.state, team);
+
+                table_builder.push_record([
+                    format!("{:?}", economy.team),
+                    economy.deaths.to_string(),
+                    economy.captures.to_string(),
+                    economy
+                        .deaths_per_capture()
+                        .map(|deaths| format!("{deaths:.1}"))
+                        .unwrap_or("-".to_string()),
+                    format!(
+                        "{} ({:.0}%)",
+                        economy.traded_deaths,
+                        economy.trade_rate() * 100.
+                    ),
+                    economy.trade_kills.to_string(),
+                ]);
+            }
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+            writeln!(
+                f,
+                "\nA death is traded when a teammate makes a kill within {} s of it.",
+                TRADE_WINDOW.as_secs()
+            )?;
+        }
+
+        writeln!(f)?;
+
+        // Kill feed section
+        {
+            let player_name = |id: &PlayerGlobalId| {
+                self.1
+                    .state
+                    .players
+                    .iter()
+                    .find(|player| player.id == *id)
+                    .map(|player| escape::markdown(&player.name))
+                    .unwrap_or_default()
+            };
+
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Time", "Killer", "Victim", "Weapon", "Note"]);
+
+            for entry in self.1.state.kill_feed(None) {
+                let kill = entry.kill;
+                let time = settings::format_game_time(&kill.time, &self.1.config);
+
+                table_builder.push_record([
+                    time,
+                    match kill.cause {
+                        KillCause::Player | KillCause::Suicide => {
+                            kill.killer.as_ref().map(player_name).unwrap_or_default()
+                        }
+                        KillCause::World => "World".to_string(),
+                    },
+                    player_name(&kill.victim),
+                    kill.weapon.to_string(),
+                    entry.notes().join(", "),
+                ]);
+            }
+
+            writeln!(f, "## Kill Feed\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+        }
+
+        writeln!(f)?;
+
+        // Team kills section
+        {
+            let player_name = |id: &PlayerGlobalId| {
+                self.1
+                    .state
+                    .players
+                    .iter()
+                    .find(|player| player.id == *id)
+                    .map(|player| escape::markdown(&player.name))
+                    .unwrap_or_default()
+            };
+
+            writeln!(f, "## Team Kills\n")?;
+
+            if self.1.state.teamkills.is_empty() {
+                writeln!(f, "No player killed a teammate.")?;
+            } else {
+                let mut table_builder = Builder::default();
+                table_builder.push_record(["Time", "Round", "Killer", "Victim", "Weapon"]);
+
+                for teamkill in &self.1.state.teamkills {
+                    table_builder.push_record([
+                        settings::format_game_time(&teamkill.time, &self.1.config),
+                        self.1
+                            .state
+                            .round_time(&teamkill.time)
+                            .map(|(round, _)| round.to_string())
+                            .unwrap_or_default(),
+                        player_name(&teamkill.killer),
+                        player_name(&teamkill.victim),
+                        teamkill.weapon.to_string(),
+                    ]);
+                }
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                writeln!(f, "{table}")?;
+            }
+        }
+
+        writeln!(f)?;
+
+        // Score check section, only when the league's scoring formula is set
+        if let Some(formula) = &self.1.config.score_formula {
+            let player_name = |id: &PlayerGlobalId| {
+                self.1
+                    .state
+                    .players
+                    .iter()
+                    .find(|player| player.id == *id)
+                    .map(|player| escape::markdown(&player.name))
+                    .unwrap_or_default()
+            };
+
+            writeln!(f, "## Score Check\n")?;
+
+            let mismatches = self.1.state.score_mismatches(formula);
+
+            if mismatches.is_empty() {
+                writeln!(f, "Every score matches the scoring formula.")?;
+            } else {
+                let mut table_builder = Builder::default();
+                table_builder.push_record(["Round", "Player", "Reported", "Expected"]);
+
+                for mismatch in &mismatches {
+                    table_builder.push_record([
+                        mismatch.round.to_string(),
+                        player_name(&mismatch.player),
+                        format!("{:+}", mismatch.reported),
+                        format!("{:+}", mismatch.expected),
+                    ]);
+                }
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                writeln!(f, "{table}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        // Players to watch section
+        {
+            writeln!(f, "## Players to Watch\n")?;
+
+            for (rank, watch) in self.1.state.players_to_watch().iter().enumerate() {
+                let name = escape::markdown(&watch.player.name);
+                writeln!(f, "{}. {name} ({} points)", rank + 1, watch.score)?;
+
+                for highlight in &watch.highlights {
+                    let time = settings::format_game_time(&highlight.time, &self.1.config);
+                    let kind = escape::markdown(&highlight.kind.to_string());
+                    let seek = escape::markdown(&self.0.seek_command(&highlight.time));
+
+                    writeln!(f, "    - {time}: {kind} ({seek})")?;
+                }
+            }
+        }
+
+        writeln!(f)?;
+
+        // Late joins section
+        {
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Connected", "ID", "Name", "Team"]);
+
+            for player in self.1.state.late_joins() {
+                table_builder.push_record([
+                    settings::format_game_time(&player.first_connected, &self.1.config),
+                    player.id.to_string(),
+                    escape::markdown(&player.name),
+                    match &player.team {
+                        None => "Unknown",
+                        Some(Team::Allies) => "Allies",
+                        Some(Team::Axis) => "Axis",
+                        Some(Team::Spectators) => "Spectators",
+                    }
+                    .to_string(),
+                ]);
+            }
+
+            writeln!(f, "## Late Joins\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+
+            if !self.0.roster_newcomers.is_empty() {
+                let mut table_builder = Builder::default();
+                table_builder.push_record(["ID", "Name", "Clan", "Other Clans"]);
+
+                for newcomer in &self.0.roster_newcomers {
+                    table_builder.push_record([
+                        newcomer.id.clone(),
+                        escape::markdown(&newcomer.name),
+                        escape::markdown(&newcomer.clan),
+                        escape::markdown(&newcomer.other_clans.join(", ")),
+                    ]);
+                }
+
+                writeln!(f, "\nNew to their clan's roster in the indexed demos:\n")?;
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                writeln!(f, "{table}")?;
+            }
+        }
+
+        writeln!(f)?;
+
+        // Warmup section, with the results from before the match went live
+        if let Some(warmup) = self.1.state.warmup_stats() {
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Name", "Kills", "Deaths"]);
+
+            for player in &self.1.state.players {
+                let (kills, deaths) = (warmup.kills_by(&player.id), warmup.deaths_of(&player.id));
+
+                if kills > 0 || deaths > 0 {
+                    table_builder.push_record([
+                        escape::markdown(&player.name),
+                        kills.to_string(),
+                        deaths.to_string(),
+                    ]);
+                }
+            }
+
+            writeln!(f, "## Warmup\n")?;
+
+            let rounds = warmup.rounds.len();
+            let captures = warmup.captures.len();
+            writeln!(f, "- Rounds: {rounds}")?;
+            writeln!(f, "- Captures: {captures}\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+
+            writeln!(f)?;
+        }
+
+        // Danger zones section
+        {
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Area", "Allies Deaths", "Axis Deaths", "Total"]);
+
+            for zone in self.1.state.danger_zones().iter().take(DANGER_ZONES_SIZE) {
+                table_builder.push_record([
+                    zone.name.clone(),
+                    zone.allies_deaths.to_string(),
+                    zone.axis_deaths.to_string(),
+                    zone.deaths().to_string(),
+                ]);
+            }
+
+            writeln!(f, "## Danger Zones\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+        }
+
+        writeln!(f)?;
+
+        // Suspicious events section
+        {
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Time", "Player", "Event"]);
+
+            for event in self.1.state.suspicious_events() {
+                let time = settings::format_game_time(&event.time, &self.1.config);
+                let player = self
+                    .1
+                    .state
+                    .players
+                    .iter()
+                    .find(|player| player.id == event.player);
+
+                table_builder.push_record([
+                    time,
+                    player
+                        .map(|player| escape::markdown(&player.name))
+                        .unwrap_or_default(),
+                    event.kind.to_string(),
+                ]);
+            }
+
+            writeln!(f, "## Suspicious Events\n")?;
+            writeln!(
+                f,
+                "These are detected with heuristics and are not proof of cheating.\n"
+            )?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+        }
+
+        writeln!(f)?;
+
+        // Data quality section
+        {
+            writeln!(f, "## Data Quality\n")?;
+
+            match KillLatency::new(&self.1.state) {
+                Some(latency) => {
+                    let recorded_by = if latency.recorded_by_hltv {
+                        "HLTV"
+                    } else {
+                        "player (POV)"
+                    };
+                    writeln!(f, "- Recorded by: {recorded_by}")?;
+                    writeln!(
+                        f,
+                        "- Kill message delay: {:.1} s median, up to {:.1} s, across {} kills",
+                        latency.median_delay.as_secs_f32(),
+                        latency.max_delay.as_secs_f32(),
+                        latency.delays.len()
+                    )?;
+                }
+                None => writeln!(f, "No kills to measure the delay of kill messages.")?,
+            }
+
+            let malformed_counts = self.1.state.malformed_message_counts();
+
+            if malformed_counts.is_empty() {
+                writeln!(f, "- Malformed messages: none")?;
+            } else {
+                let counts = malformed_counts
+                    .iter()
+                    .map(|(description, count)| format!("{description} × {count}"))
+                    .collect::<Vec<_>>();
+
+                writeln!(
+                    f,
+                    "- Malformed messages: {} ({}); the events in them are missing from the report",
+                    self.1.state.malformed_messages.len(),
+                    counts.join(", ")
+                )?;
+            }
+        }
+
+        writeln!(f)?;
+
+        // Server performance section
+        {
+            writeln!(f, "## Server Performance\n")?;
+
+            if self.1.state.server_anomalies.is_empty() {
+                writeln!(f, "No lag was found in the recording.")?;
+            } else {
+                writeln!(
+                    f,
+                    "Kills and times near these moments may be missing or off because the server lagged.\n"
+                )?;
+
+                let mut table_builder = Builder::default();
+                table_builder.push_record(["Time", "Anomaly", "Duration"]);
+
+                for anomaly in &self.1.state.server_anomalies {
+                    table_builder.push_record([
+                        settings::format_game_time(&anomaly.start, &self.1.config),
+                        anomaly.kind.to_string(),
+                        format!("{:.1} s", anomaly.duration.as_secs_f32()),
+                    ]);
+                }
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                writeln!(f, "{table}")?;
+            }
+        }
+
+        writeln!(f)?;
+
+        // Chat section
+        {
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Time", "Player", "Message"]);
+
+            for message in &self.1.state.chat {
+                let time = settings::format_game_time(&message.time, &self.1.config);
+                let sender = message
+                    .sender
+                    .as_ref()
+                    .and_then(|id| self.1.state.players.iter().find(|player| player.id == *id));
+
+                table_builder.push_record([
+                    time,
+                    sender
+                        .map(|player| escape::markdown(&player.name))
+                        .unwrap_or_default(),
+                    escape::markdown(&message.text),
+                ]);
+            }
+
+            writeln!(f, "## Chat\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+        }
+
+        writeln!(f)?;
+
+        // Individual player summaries
+        {
+            writeln!(f, "## Player Summaries\n")?;
+
+            let impact_ratings = self
+                .1
+                .state
+                .impact_ratings(&win_model::win_model(&self.1.config));
+
+            for player in &self.1.state.players {
+                writeln!(f, "### {}\n", escape::markdown(&player.name))?;
+
+                let first_bloods = self.1.state.first_bloods_by(&player.id);
+                writeln!(f, "- First bloods: {first_bloods}")?;
+                let round_enders = self.1.state.round_enders_by(&player.id);
+                writeln!(f, "- Round enders: {round_enders}")?;
+                let shots = player.shots.len();
+                writeln!(f, "- Shots heard: {shots}")?;
+                let accuracy = player
+                    .estimated_accuracy()
+                    .map(|accuracy| format!("{:.0}%", accuracy * 100.))
+                    .unwrap_or("Unknown".to_string());
+                writeln!(f, "- Estimated accuracy: {accuracy}")?;
+                let impact_rating = impact_ratings.get(&player.id).copied().unwrap_or_default();
+                writeln!(f, "- Impact rating: {:+.2}\n", impact_rating)?;
+
+                // Kills per weapon section
+                writeln!(f, "#### Weapon Breakdown\n")?;
+
+                let mut table_builder = Builder::default();
+                table_builder.push_record(["Weapon", "Kills", "Team Kills"]);
+
+                // Each category starts with a subtotal row, followed by its weapons
+                for category in player.weapon_breakdown_by_category() {
+                    table_builder.push_record([
+                        format!("**{}**", category.category),
+                        format!("**{}**", category.kills),
+                        format!("**{}**", category.teamkills),
+                    ]);
+
+                    for (weapon, kills, teamkills) in category.weapons {
+                        table_builder.push_record([
+                            format!("{weapon}"),
+                            kills.to_string(),
+                            teamkills.to_string(),
+                        ]);
+                    }
+                }
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                writeln!(f, "{table}\n")?;
+
+                // Deaths per enemy weapon section
+                if !player.deaths_by_weapon.is_empty() {
+                    writeln!(f, "#### Killed By\n")?;
+
+                    let mut deaths_by_weapon = Vec::from_iter(&player.deaths_by_weapon);
+                    deaths_by_weapon.sort_by(|(_, l), (_, r)| l.cmp(r).reverse());
+
+                    let mut table_builder = Builder::default();
+                    table_builder.push_record(["Weapon", "Deaths"]);
+
+                    for (weapon, deaths) in deaths_by_weapon {
+                        table_builder.push_record([format!("{weapon}"), deaths.to_string()]);
+                    }
+
+                    let mut table = table_builder.build();
+                    table.with(Style::markdown());
+
+                    writeln!(f, "{table}\n")?;
+                }
+
+                // Ammo usage section, only available for the player that recorded the demo
+                if !player.ammo.is_empty() {
+                    writeln!(f, "#### Ammo Usage\n")?;
+
+                    let mut table_builder = Builder::default();
+                    table_builder.push_record(["Weapon", "Shots", "Reloads", "Kills per Shot"]);
+
+                    for (weapon, usage) in &player.ammo {
+                        table_builder.push_record([
+                            weapon.to_string(),
+                            usage.shots.to_string(),
+                            usage.reloads.to_string(),
+                            player
+                                .kills_per_shot(weapon)
+                                .map(|efficiency| format!("{efficiency:.2}"))
+                                .unwrap_or_default(),
+                        ]);
+                    }
+
+                    let mut table = table_builder.build();
+                    table.with(Style::markdown());
+
+                    writeln!(f, "{table}\n")?;
+                }
+
+                // Kill distances section, only available for the player that recorded the demo
+                let weapon_ranges = self.1.state.weapon_ranges(&player.id);
+
+                if !weapon_ranges.is_empty() {
+                    writeln!(f, "#### Kill Distances\n")?;
+
+                    let mut table_builder = Builder::default();
+                    table_builder.push_record(["Weapon", "Kills", "Avg. Distance", "Long Range"]);
+
+                    for range in weapon_ranges {
+                        table_builder.push_record([
+                            range.weapon.to_string(),
+                            range.kills.to_string(),
+                            format!("{:.1} m", range.avg_meters()),
+                            range.long_range_kills.to_string(),
+                        ]);
+                    }
+
+                    let mut table = table_builder.build();
+                    table.with(Style::markdown());
+
+                    writeln!(f, "{table}\n")?;
+
+                    if let Some(longest_kill) = self.1.state.longest_kill(&player.id) {
+                        let victim = self
+                            .1
+                            .state
+                            .players
+                            .iter()
+                            .find(|player| player.id == longest_kill.victim)
+                            .map(|player| escape::markdown(&player.name))
+                            .unwrap_or_default();
+
+                        writeln!(
+                            f,
+                            "Longest kill: {:.1} m with {} on {victim} at {}\n",
+                            longest_kill.meters(),
+                            longest_kill.weapon,
+                            settings::format_game_time(&longest_kill.time, &self.1.config)
+                        )?;
+                    }
+                }
+
+                // Kill timing section, comparing the weapons' stats with the kills
+                let weapon_timings = self.1.state.weapon_timings(&player.id);
+
+                if !weapon_timings.is_empty() {
+                    writeln!(f, "#### Kill Timing\n")?;
+
+                    let mut table_builder = Builder::default();
+                    table_builder.push_record([
+                        "Weapon",
+                        "Kills",
+                        "Theoretical TTK",
+                        "Fire Interval",
+                        "Fastest Follow-up",
+                    ]);
+
+                    for timing in weapon_timings {
+                        table_builder.push_record([
+                            timing.weapon.to_string(),
+                            timing.kills.to_string(),
+                            format!("{} ms", timing.time_to_kill.as_millis()),
+                            format!("{} ms", timing.fire_interval.as_millis()),
+                            timing
+                                .fastest_follow_up
+                                .map(|interval| format!("{} ms", interval.as_millis()))
+                                .unwrap_or_default(),
+                        ]);
+                    }
+
+                    let mut table = table_builder.build();
+                    table.with(Style::markdown());
+
+                    writeln!(f, "{table}\n")?;
+                }
+
+                // Positions held section, only available for the player that recorded the demo
+                let positions_held = player.positions_held();
+
+                if !positions_held.is_empty() {
+                    writeln!(f, "#### Positions Held\n")?;
+
+                    let mut table_builder = Builder::default();
+                    table_builder.push_record(["Start Time", "Duration", "Kills", "Position"]);
+
+                    for hold in positions_held {
+                        let [x, y, z] = hold.origin;
+
+                        table_builder.push_record([
+                            settings::format_game_time(&hold.start_time, &self.1.config),
+                            format_duration(Duration::new(hold.duration().as_secs(), 0))
+                                .to_string(),
+                            hold.kills.to_string(),
+                            format!("({x:.0}, {y:.0}, {z:.0})"),
+                        ]);
+                    }
+
+                    let mut table = table_builder.build();
+                    table.with(Style::markdown());
+
+                    writeln!(f, "{table}\n")?;
+                }
+
+                // Movement section, only available for the player that recorded the demo
+                let movement_per_life = player.movement_per_life();
+
+                if !movement_per_life.is_empty() {
+                    writeln!(f, "#### Movement\n")?;
+
+                    let mut table_builder = Builder::default();
+                    table_builder.push_record([
+                        "Life",
+                        "Spawn Time",
+                        "Distance",
+                        "Avg. Speed",
+                        "Max. Speed",
+                    ]);
+
+                    for (life, movement) in movement_per_life.iter().enumerate() {
+                        table_builder.push_record([
+                            (life + 1).to_string(),
+                            settings::format_game_time(&movement.spawn_time, &self.1.config),
+                            format!("{:.0} units", movement.distance),
+                            format!("{:.0} units/s", movement.avg_speed),
+                            movement
+                                .max_speed
+                                .as_ref()
+                                .map(|(_, speed)| format!("{speed:.0} units/s"))
+                                .unwrap_or_default(),
+                        ]);
+                    }
+
+                    let mut table = table_builder.build();
+                    table.with(Style::markdown());
+
+                    writeln!(f, "{table}\n")?;
+
+                    writeln!(
+                        f,
+                        "Distance traveled: {:.0} units\n",
+                        player.distance_traveled()
+                    )?;
+                }
+
+                // Time after spawn section
+                writeln!(f, "#### Time After Spawn\n")?;
+
+                let mut table_builder = Builder::default();
+                table_builder.push_record(["", "Min.", "Avg."]);
+
+                for (label, min, avg) in [
+                    (
+                        "First kill",
+                        player.min_time_to_first_kill(),
+                        player.avg_time_to_first_kill(),
+                    ),
+                    ("Death", player.min_lifespan(), player.avg_lifespan()),
+                ] {
+                    table_builder.push_record([
+                        label.to_string(),
+                        format_duration(Duration::new(min.as_secs(), 0)).to_string(),
+                        format_duration(Duration::new(avg.as_secs(), 0)).to_string(),
+                    ]);
+                }
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                writeln!(f, "{table}\n")?;
+
+                // Kill streaks section
+                writeln!(f, "#### Kill Streaks\n")?;
+
+                // The start is shown on every clock, to line it up with the demo or a recording
+                let mut table_builder = Builder::default();
+                table_builder.push_record(
+                    ["Wave".to_string(), "Total Kills".to_string()]
+                        .into_iter()
+                        .chain(Clock::ALL.iter().map(|clock| format!("Start ({clock:?})")))
+                        .chain(
+                            ["Duration", "Weapons Used", "Seek"]
+                                .into_iter()
+                                .map(str::to_string),
+                        ),
+                );
+
+                for (wave, kill_streak) in player.notable_kill_streaks(self.1.config.min_streak) {
+                    if let (Some((start_time, _)), Some((end_time, _))) =
+                        (kill_streak.kills.first(), kill_streak.kills.last())
+                    {
+                        let start_time_labels = Clock::ALL
+                            .iter()
+                            .map(|clock| settings::format_clock_time(start_time, clock));
+                        let streak_duration = Duration::new((end_time - start_time).as_secs(), 0);
+
+                        let weapons_used = kill_streak
+                            .kills
+                            .iter()
+                            .map(|(_, weapon)| format!("{weapon}"))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        table_builder.push_record(
+                            [(wave + 1).to_string(), kill_streak.kills.len().to_string()]
+                                .into_iter()
+                                .chain(start_time_labels)
+                                .chain([
+                                    format_duration(streak_duration).to_string(),
+                                    weapons_used,
+                                    escape::markdown(&self.0.seek_command(start_time)),
+                                ]),
+                        );
+                    }
+                }
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                writeln!(f, "{table}\n")?;
+            }
+        }
+
+        // Narrative section
+        {
+            writeln!(f, "## Narrative\n")?;
+
+            let narrative = Narrative::new(&self.1.state, &self.1.config.narrative);
+            writeln!(f, "{}", escape::markdown(&narrative.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
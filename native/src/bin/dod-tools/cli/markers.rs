@@ -0,0 +1,23 @@
+//! The `markers` command, which prints the highlights of a demo as markers for a video editor.
+
+use crate::cli::MarkerFormat;
+use analysis::AnalysisConfig;
+use native::{markers, run_analyzer};
+use std::path::PathBuf;
+
+/// Prints the markers of the highlights in the demo, or only of the player's highlights.
+pub fn print_markers(
+    demo_path: &PathBuf,
+    player: Option<&str>,
+    format: &MarkerFormat,
+    frame_rate: u32,
+    config: &AnalysisConfig,
+) {
+    let (file, analysis) = run_analyzer(demo_path, config);
+    let markers = markers::highlight_markers(&analysis, player);
+
+    match format {
+        MarkerFormat::Csv => print!("{}", markers::to_csv(&markers)),
+        MarkerFormat::Edl => print!("{}", markers::to_edl(&markers, &file.name, frame_rate)),
+    }
+}
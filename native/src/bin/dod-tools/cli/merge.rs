@@ -0,0 +1,178 @@
+//! The `merge` command, which prints one scoreboard for a match recorded in several demos.
+
+use crate::cli::{AnalyzerOutput, Json, OutputFormat};
+use analysis::{MatchAggregation, rank};
+use native::{
+    ANALYZER_VERSION, SCHEMA_VERSION, escape,
+    html::{self, Cell},
+};
+use serde_json::json;
+use tabled::{builder::Builder, settings::Style};
+
+/// Prints one scoreboard with the results of each player summed across the analyses.
+pub fn print_aggregation(analyses: &[AnalyzerOutput], output_format: &OutputFormat) {
+    let aggregation = MatchAggregation::new(analyses.iter().map(|(_, analysis)| analysis));
+
+    let mut players = aggregation.players.iter().collect::<Vec<_>>();
+    players.sort_by(|l, r| rank(&l.stats, &r.stats));
+
+    match output_format {
+        OutputFormat::Json => {
+            let players = players
+                .iter()
+                .map(|player| {
+                    json!({
+                        "id": player.id.to_string(),
+                        "name": player.name,
+                        "demos": player.analyses,
+                        "score": player.stats.0,
+                        "kills": player.stats.1,
+                        "deaths": player.stats.2,
+                        "objective_score": player.objective_score,
+                        "rounds": player.rounds_played,
+                        "longest_kill_streak": player.longest_kill_streak(),
+                        "kill_streaks": player
+                            .kill_streaks
+                            .iter()
+                            .map(|streak| streak.kills.len())
+                            .collect::<Vec<_>>(),
+                        "weapons": player
+                            .weapons_by_kills()
+                            .into_iter()
+                            .map(|(weapon, kills, teamkills)| {
+                                json!({
+                                    "weapon": weapon.to_string(),
+                                    "kills": kills,
+                                    "teamkills": teamkills,
+                                })
+                            })
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            println!(
+                "{}",
+                Json::from(json!({
+                    "analyzer_version": ANALYZER_VERSION,
+                    "schema_version": SCHEMA_VERSION,
+                    "files": analyses.iter().map(|(file, _)| &file.path).collect::<Vec<_>>(),
+                    "players": players,
+                }))
+            );
+        }
+
+        OutputFormat::Csv => {
+            let mut csv = String::from(
+                "id,name,demos,score,kills,deaths,objective_score,rounds,longest_streak\n",
+            );
+
+            for player in players {
+                let fields = [
+                    escape::csv(&player.id.to_string()),
+                    escape::csv(&player.name),
+                    player.analyses.to_string(),
+                    player.stats.0.to_string(),
+                    player.stats.1.to_string(),
+                    player.stats.2.to_string(),
+                    player.objective_score.to_string(),
+                    player.rounds_played.to_string(),
+                    player.longest_kill_streak().to_string(),
+                ];
+
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+
+            print!("{csv}");
+        }
+
+        OutputFormat::Markdown | OutputFormat::Html => {
+            let header = [
+                "Name",
+                "Demos",
+                "Score",
+                "Kills",
+                "Deaths",
+                "Caps/Obj Points",
+                "Rounds",
+                "Longest Streak",
+                "Weapons",
+            ];
+
+            let records = players
+                .iter()
+                .map(|player| {
+                    let weapons = player
+                        .weapons_by_kills()
+                        .into_iter()
+                        .filter(|(_, kills, _)| *kills > 0)
+                        .map(|(weapon, kills, _)| format!("{weapon} ({kills})"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    [
+                        player.name.clone(),
+                        player.analyses.to_string(),
+                        player.stats.0.to_string(),
+                        player.stats.1.to_string(),
+                        player.stats.2.to_string(),
+                        player.objective_score.to_string(),
+                        player.rounds_played.to_string(),
+                        player.longest_kill_streak().to_string(),
+                        weapons,
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            let title = format!("Combined: {} demos", aggregation.analyses);
+
+            if let OutputFormat::Html = output_format {
+                let rows = records
+                    .into_iter()
+                    .map(|record| {
+                        record
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, text)| match i {
+                                0 | 8 => Cell::from(text),
+                                _ => {
+                                    let sort_key = text.parse().unwrap_or_default();
+
+                                    Cell::number(text, sort_key)
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                let body = format!(
+                    "<h1>{}</h1>\n{}",
+                    escape::html(&title),
+                    html::table(&header, rows)
+                );
+
+                print!("{}", html::document(&title, &body));
+            } else {
+                let mut table_builder = Builder::default();
+                table_builder.push_record(header);
+
+                for mut record in records {
+                    record[0] = escape::markdown(&record[0]);
+                    table_builder.push_record(record);
+                }
+
+                let mut table = table_builder.build();
+                table.with(Style::markdown());
+
+                println!("# {title}\n");
+
+                for (file, _) in analyses {
+                    println!("- {}", escape::markdown(&file.name));
+                }
+
+                println!("\n{table}");
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Reports of a single round, written by `analyze --split-rounds`.
+
+use crate::cli::{AnalyzerOutput, Json, OutputFormat};
+use analysis::{Analysis, Player, Round};
+use humantime::format_duration;
+use native::{
+    ANALYZER_VERSION, FileInfo, SCHEMA_VERSION, escape,
+    html::{self, Cell},
+    settings,
+};
+use serde_json::json;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tabled::{builder::Builder, settings::Style};
+
+/// Writes a report file for every completed round of an analysis, named after the demo file.
+pub fn write_round_reports(
+    (file, analysis): &AnalyzerOutput,
+    output_format: &OutputFormat,
+) -> std::io::Result<()> {
+    let file_stem = Path::new(&file.name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&file.name);
+
+    for (i, round) in analysis.state.rounds.iter().enumerate() {
+        if !matches!(round, Round::Completed { .. }) {
+            continue;
+        }
+
+        let report = RoundReport {
+            file,
+            analysis,
+            number: i + 1,
+            round,
+        };
+
+        let (extension, contents) = match output_format {
+            OutputFormat::Markdown => ("md", report.to_string()),
+            OutputFormat::Json => ("json", Json::from(&report).to_string()),
+            OutputFormat::Csv => ("csv", report.to_csv()),
+            OutputFormat::Html => ("html", report.to_html()),
+        };
+
+        // Next to the demo, so demos with the same name in different directories keep their reports
+        let report_path = Path::new(&file.path)
+            .with_file_name(format!("{file_stem}.round-{}.{extension}", i + 1));
+
+        fs::write(&report_path, contents)?;
+
+        eprintln!("Wrote {}", report_path.display());
+    }
+
+    Ok(())
+}
+
+/// Report scoped to a single [Round] of an [Analysis].
+struct RoundReport<'a> {
+    file: &'a FileInfo,
+    analysis: &'a Analysis,
+    number: usize,
+    round: &'a Round,
+}
+
+impl RoundReport<'_> {
+    /// Formats the kills and deaths of each participant as CSV, with the weapons separated by
+    /// spaces.
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("id,name,team,kills,deaths,weapons\n");
+
+        for (player, weapons) in self.player_kills() {
+            let fields = [
+                escape::csv(&player.id.to_string()),
+                escape::csv(&player.name),
+                player
+                    .team
+                    .as_ref()
+                    .map(|team| format!("{team:?}"))
+                    .unwrap_or_default(),
+                weapons.len().to_string(),
+                self.deaths_of(player).to_string(),
+                escape::csv(&weapons.join(" ")),
+            ];
+
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Formats the round as a standalone HTML document, with a table of the kills and deaths of
+    /// each participant.
+    fn to_html(&self) -> String {
+        let title = format!(
+            "Round {}: {} on {}",
+            self.number, self.file.name, self.analysis.demo_info.map_name
+        );
+
+        let rows = self
+            .player_kills()
+            .into_iter()
+            .map(|(player, weapons)| {
+                vec![
+                    Cell::from(player.name.as_str()),
+                    Cell::from(
+                        player
+                            .team
+                            .as_ref()
+                            .map(|team| format!("{team:?}"))
+                            .unwrap_or("Unknown".to_string()),
+                    ),
+                    Cell::number(weapons.len(), weapons.len() as f64),
+                    Cell::number(self.deaths_of(player), self.deaths_of(player) as f64),
+                    Cell::from(weapons.join(", ")),
+                ]
+            })
+            .collect();
+
+        let body = format!(
+            "<h1>{}</h1>\n{}",
+            escape::html(&title),
+            html::table(&["Name", "Team", "Kills", "Deaths", "Weapons Used"], rows)
+        );
+
+        html::document(&title, &body)
+    }
+
+    /// Returns the players that participated in the round, with the weapons they used for kills.
+    fn player_kills(&self) -> Vec<(&Player, Vec<String>)> {
+        self.analysis
+            .state
+            .players
+            .iter()
+            .filter(|player| self.round.is_participant(&player.id))
+            .map(|player| {
+                let weapons = player
+                    .kills_in(self.round)
+                    .map(|(_, weapon)| format!("{weapon}"))
+                    .collect::<Vec<_>>();
+
+                (player, weapons)
+            })
+            .collect()
+    }
+
+    /// Returns the number of times the player died in the round, by any cause.
+    fn deaths_of(&self, player: &Player) -> usize {
+        self.analysis
+            .state
+            .kills
+            .iter()
+            .filter(|kill| kill.victim == player.id && self.round.contains(&kill.time))
+            .count()
+    }
+}
+
+impl From<&RoundReport<'_>> for Json {
+    fn from(value: &RoundReport) -> Self {
+        let Round::Completed {
+            start_time,
+            end_time,
+            winner_stats,
+            ..
+        } = value.round
+        else {
+            return json!(null).into();
+        };
+
+        let players = value
+            .player_kills()
+            .into_iter()
+            .map(|(player, weapons)| {
+                let scoreboard = value.round.stats_of(&player.id);
+
+                json!({
+                    "id": player.id.to_string(),
+                    "name": player.name,
+                    "team": player.team.clone().map(|t| format!("{t:?}").to_lowercase()),
+                    "kills": weapons.len(),
+                    "deaths": value.deaths_of(player),
+                    "weapons": weapons,
+                    "scoreboard": scoreboard.map(|stats| json!({
+                        "score": stats.score,
+                        "kills": stats.kills,
+                        "deaths": stats.deaths,
+                        "objective_score": stats.objective_score,
+                    })),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "file": value.file.path,
+            "analyzer_version": ANALYZER_VERSION,
+            "schema_version": SCHEMA_VERSION,
+            "round": value.number,
+            "start_time": settings::format_game_time(start_time, &value.analysis.config),
+            "duration": format_duration(Duration::new((end_time - start_time).as_secs(), 0)).to_string(),
+            "winner": winner_stats.as_ref().map(|(team, _)| format!("{team:?}").to_lowercase()),
+            "kills_by_winner": winner_stats.as_ref().map(|(_, kills)| kills),
+            "time_left_at_end": value
+                .analysis
+                .state
+                .time_left_at_round_end(value.round)
+                .map(|time_left| format_duration(Duration::new(time_left.as_secs(), 0)).to_string()),
+            "players": players,
+        })
+        .into()
+    }
+}
+
+impl Display for RoundReport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Round::Completed {
+            start_time,
+            end_time,
+            winner_stats,
+            ..
+        } = self.round
+        else {
+            return Ok(());
+        };
+
+        // Header section
+        {
+            let file_name = escape::markdown(&self.file.name);
+            let map_name = escape::markdown(&self.analysis.demo_info.map_name);
+            let round_number = self.number;
+            writeln!(f, "# Round {round_number}: {file_name} on {map_name}\n")?;
+
+            let start_time_label = settings::format_game_time(start_time, &self.analysis.config);
+            writeln!(f, "- Start time: {start_time_label}")?;
+            let duration = Duration::new((end_time - start_time).as_secs(), 0);
+            writeln!(f, "- Duration: {}", format_duration(duration))?;
+
+            if let Some((winner, kills)) = winner_stats {
+                writeln!(f, "- Winner: {winner:?}")?;
+                writeln!(f, "- Kills by winner: {kills}")?;
+            }
+
+            if let Some(time_left) = self.analysis.state.time_left_at_round_end(self.round) {
+                let time_left = Duration::new(time_left.as_secs(), 0);
+                writeln!(f, "- Time left on the map: {}", format_duration(time_left))?;
+            }
+        }
+
+        writeln!(f)?;
+
+        // Player kills section
+        {
+            let mut table_builder = Builder::default();
+            table_builder.push_record(["Name", "Team", "Kills", "Deaths", "Weapons Used"]);
+
+            for (player, weapons) in self.player_kills() {
+                table_builder.push_record([
+                    escape::markdown(&player.name),
+                    match &player.team {
+                        Some(team) => format!("{team:?}"),
+                        None => "Unknown".to_string(),
+                    },
+                    weapons.len().to_string(),
+                    self.deaths_of(player).to_string(),
+                    weapons.join(", "),
+                ]);
+            }
+
+            writeln!(f, "## Players\n")?;
+
+            let mut table = table_builder.build();
+            table.with(Style::markdown());
+
+            writeln!(f, "{table}")?;
+        }
+
+        Ok(())
+    }
+}
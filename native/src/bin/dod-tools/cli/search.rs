@@ -0,0 +1,61 @@
+//! The `search` command, which lists the indexed demos that match a filter.
+
+use native::{
+    index::{DemoFilter, DemoIndex, IndexedDemo},
+    settings::{self, DisplaySettings},
+    storage,
+};
+use std::path::Path;
+use tabled::{builder::Builder, settings::Style};
+
+/// Prints the demos in the index stored under the key that match the filter.
+pub fn print_search(filter: &DemoFilter, index: &Path, display: &DisplaySettings) {
+    let demo_index = DemoIndex::load_from(
+        storage::shared_or_local_storage().as_ref(),
+        &index.to_string_lossy(),
+    )
+    .expect("Could not read the index");
+
+    print_search_results(demo_index.search(filter), display);
+}
+
+/// Prints a table of the indexed demos that matched a search.
+fn print_search_results<'a>(
+    demos: impl Iterator<Item = &'a IndexedDemo>,
+    display: &DisplaySettings,
+) {
+    let mut table_builder = Builder::default();
+    table_builder.push_record(["Date", "Map", "Allies", "Axis", "Score", "Demo"]);
+
+    let mut outdated = 0;
+
+    for demo in demos {
+        let [allies, axis] = &demo.clans;
+        let [allies_score, axis_score] = demo.score;
+
+        if demo.is_outdated() {
+            outdated += 1;
+        }
+
+        table_builder.push_record([
+            settings::format_local_time(demo.created_at, display),
+            demo.map_name.clone(),
+            allies.clone(),
+            axis.clone(),
+            format!("{allies_score} - {axis_score}"),
+            demo.path.display().to_string(),
+        ]);
+    }
+
+    let mut table = table_builder.build();
+    table.with(Style::rounded());
+
+    println!("{table}");
+
+    if outdated > 0 {
+        eprintln!(
+            "{outdated} of the demos were indexed by another version of the analyzer, so their \
+            results may not compare; run the index command again to update them"
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! The `serve` command, which shares a directory with the reviewers of a team.
+
+use native::storage::{self, LocalStorage};
+use std::net::TcpListener;
+use std::path::PathBuf;
+
+/// Serves the files in the directory at the address until the program is stopped.
+pub fn serve(dir: PathBuf, address: &str) {
+    let token = storage::storage_token()
+        .expect("Set DOD_TOOLS_STORAGE_TOKEN to a secret shared with the reviewers");
+
+    let listener = TcpListener::bind(address).expect("Could not listen on the address");
+
+    eprintln!(
+        "Serving {} at http://{address}; set {} to it and {} to the same secret on each \
+         reviewer's machine",
+        dir.display(),
+        storage::STORAGE_URL_VAR,
+        storage::STORAGE_TOKEN_VAR
+    );
+
+    storage::serve(listener, &LocalStorage { dir }, &token).expect("Could not serve the storage");
+}
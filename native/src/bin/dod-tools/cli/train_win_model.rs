@@ -0,0 +1,25 @@
+//! The `train-win-model` command, which fits the round win probability model to a set of demos.
+
+use analysis::{AnalysisConfig, WinModel};
+use native::run_analyzer;
+use std::path::PathBuf;
+
+/// Prints the round win probability model fitted to the kills in the demos.
+pub fn print_win_model(demo_paths: &[PathBuf], config: &AnalysisConfig) {
+    let samples = demo_paths
+        .iter()
+        .flat_map(|demo_path| {
+            let (_, analysis) = run_analyzer(demo_path, config);
+
+            analysis.state.win_model_samples()
+        })
+        .collect::<Vec<_>>();
+
+    let model = WinModel::fit(&samples);
+
+    println!("# Fitted to {} kills", samples.len());
+    println!(
+        "{}",
+        toml::to_string(&model).expect("Could not write the win model")
+    );
+}
@@ -0,0 +1,1253 @@
+//! Demo analyzer that runs with an interactable user interface.
+
+mod archive_window;
+mod batch_summary;
+mod combined;
+mod report;
+mod settings_window;
+mod streak_leaderboard;
+
+use crate::gui::archive_window::{ArchiveAction, ArchiveWindow};
+use crate::gui::batch_summary::batch_summary_ui;
+use crate::gui::combined::combined_ui;
+use crate::gui::report::{ReportAction, report_ui};
+use crate::gui::settings_window::{SettingsAction, SettingsWindow};
+use crate::gui::streak_leaderboard::streak_leaderboard_ui;
+use analysis::{Analysis, AnalysisConfig, Clan, GameTime, PlayerGlobalId, Team};
+use egui::{
+    Align, Button, CentralPanel, CollapsingHeader, Color32, Context, Event, FontData, FontFamily,
+    Frame, Id, Key, KeyboardShortcut, LayerId, Layout, Modifiers, Order, Pos2, ProgressBar, Rect,
+    RichText, ScrollArea, SidePanel, Sides, TextEdit, TopBottomPanel, Ui, UserData, Vec2,
+    ViewportCommand, WidgetText, Window,
+    epaint::text::{FontInsert, FontPriority, InsertFontFamily},
+    panel::Side,
+};
+use egui_file_dialog::FileDialog;
+use native::{
+    FileInfo, association,
+    index::DEFAULT_INDEX_KEY,
+    notes::{self, DemoNotes},
+    results::{ResultLine, result_changes, result_lines},
+    run_analyzer,
+    settings::{self, DisplaySettings},
+    storage::{self, LocalStorage},
+    timeline, win_model,
+};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, mpsc};
+
+/// Opens the user interface, analyzing the demos on launch.
+#[tokio::main]
+pub async fn run(demo_paths: Vec<PathBuf>) {
+    // Release builds on Windows hide the console window, which the other commands print to
+    #[cfg(all(windows, not(debug_assertions)))]
+    detach_console();
+
+    let gui = Gui::default().with_initial_files(demo_paths);
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_maximized(true),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "dod-tools",
+        options,
+        Box::new(|cc| {
+            add_system_fallback_fonts(&cc.egui_ctx);
+
+            if let Some(font_path) = &gui.font_path {
+                add_user_font(&cc.egui_ctx, font_path);
+            }
+
+            Ok(Box::new(gui))
+        }),
+    )
+    .expect("Could not run the GUI");
+}
+
+/// Detaches the program from its console, which closes the console window opened for it when it
+/// was not started from a terminal.
+#[cfg(all(windows, not(debug_assertions)))]
+fn detach_console() {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn FreeConsole() -> i32;
+    }
+
+    // SAFETY: FreeConsole takes no arguments and only fails when there is no console to detach
+    unsafe {
+        FreeConsole();
+    }
+}
+
+struct Gui {
+    analyses: Vec<(FileInfo, Analysis)>,
+    archive_window: Option<ArchiveWindow>,
+    batch_progress: Option<(usize, usize)>,
+    batch_summary: Option<BatchSummary>,
+    config: AnalysisConfig,
+    display: DisplaySettings,
+    file_picker: FileDialog,
+
+    /// Font chosen by the user, saved with the settings to load it on the next launch.
+    font_path: Option<PathBuf>,
+
+    font_picker: FileDialog,
+    index_picker: FileDialog,
+
+    /// Picks where to save an export, along with the path of the demo being exported.
+    export_picker: FileDialog,
+    export_demo_path: Option<String>,
+    export_error: Option<String>,
+
+    initial_files: Vec<PathBuf>,
+
+    /// Notes about demos shared by the reviewers, and the notes being edited, keyed by the file
+    /// path.
+    notes: DemoNotes,
+    note_drafts: HashMap<String, String>,
+
+    open_windows: HashSet<String>,
+    player_highlight: PlayerHighlighting,
+    report_titles: HashMap<String, String>,
+
+    /// Impact rating of each player, keyed by the file path, rated once when the demo is analyzed.
+    impact_ratings: HashMap<String, HashMap<PlayerGlobalId, f32>>,
+
+    /// Results that changed the last time a report was re-run, keyed by the file path.
+    rerun_changes: HashMap<String, Vec<String>>,
+
+    /// Results indexed by an older version of the analyzer, keyed by the file path, to compare
+    /// with once the demo is analyzed again.
+    reanalysis_baselines: HashMap<String, Vec<ResultLine>>,
+
+    /// Reports checked in the list of open reports, to combine them, keyed by the file path.
+    selected_reports: HashSet<String>,
+
+    /// Paths of the reports shown combined, in the order they were opened.
+    combined_reports: Option<Vec<String>>,
+
+    settings_window: Option<SettingsWindow>,
+    streak_leaderboard_open: bool,
+
+    rx: mpsc::Receiver<GuiMessage>,
+    tx: mpsc::Sender<GuiMessage>,
+}
+
+#[derive(Default)]
+struct PlayerHighlighting {
+    highlighted: HashSet<PlayerGlobalId>,
+}
+
+/// Outcome of the last batch of demos, shown once the batch finishes.
+#[derive(Default)]
+struct BatchSummary {
+    files: usize,
+
+    /// Paths of the demos that were analyzed, in order.
+    analyzed: Vec<String>,
+
+    /// Paths of the demos that could not be analyzed, with the reason.
+    failures: Vec<(String, String)>,
+
+    is_finished: bool,
+}
+
+enum GuiMessage {
+    Idle,
+
+    AnalyzerStart {
+        files: usize,
+    },
+
+    AnalyzerProgress {
+        analysis: Box<Analysis>,
+        file_info: FileInfo,
+        progress: (usize, usize),
+    },
+
+    AnalyzerFailure {
+        path: String,
+        error: String,
+        progress: (usize, usize),
+    },
+}
+
+impl Gui {
+    fn with_initial_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.initial_files = files;
+        self
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &Context) {
+        let [open, next, previous, expand, collapse, search] = ctx.input_mut(|i| {
+            [
+                &OPEN_SHORTCUT,
+                &NEXT_REPORT_SHORTCUT,
+                &PREVIOUS_REPORT_SHORTCUT,
+                &EXPAND_SECTIONS_SHORTCUT,
+                &COLLAPSE_SECTIONS_SHORTCUT,
+                &SEARCH_SHORTCUT,
+            ]
+            .map(|shortcut| i.consume_shortcut(shortcut))
+        });
+
+        if open {
+            self.file_picker.pick_multiple();
+        }
+
+        if (next || previous) && !self.analyses.is_empty() {
+            let top_layer = ctx.memory(|memory| memory.areas().top_layer_id(Order::Middle));
+            let current = self.analyses.iter().position(|(file_info, _)| {
+                Some(LayerId::new(
+                    Order::Middle,
+                    Id::from(file_info.path.clone()),
+                )) == top_layer
+            });
+
+            let len = self.analyses.len();
+            let target = match (current, next) {
+                (Some(i), true) => (i + 1) % len,
+                (Some(i), false) => (i + len - 1) % len,
+                (None, true) => 0,
+                (None, false) => len - 1,
+            };
+
+            let demo_path = self.analyses[target].0.path.clone();
+
+            self.open_windows.insert(demo_path.clone());
+            ctx.move_to_top(LayerId::new(Order::Middle, Id::from(demo_path)));
+        }
+
+        // Applied by every section of the reports during this frame only
+        ctx.data_mut(|data| match (expand, collapse) {
+            (true, _) => data.insert_temp(Id::new(SECTIONS_OPEN_ID), true),
+            (_, true) => data.insert_temp(Id::new(SECTIONS_OPEN_ID), false),
+            _ => {
+                data.remove_temp::<bool>(Id::new(SECTIONS_OPEN_ID));
+            }
+        });
+
+        if search {
+            match &mut self.archive_window {
+                Some(archive_window) => archive_window.focus_search = true,
+                None => self.open_archive(),
+            }
+        }
+    }
+
+    /// Opens the index shared by a team if a shared storage is set, or asks for an index file.
+    fn open_archive(&mut self) {
+        match storage::shared_storage() {
+            Some(shared_storage) => {
+                self.archive_window = Some(ArchiveWindow::new(&shared_storage, DEFAULT_INDEX_KEY));
+            }
+            None => self.index_picker.pick_file(),
+        }
+    }
+}
+
+const OPEN_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
+
+const NEXT_REPORT_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::PageDown);
+
+const PREVIOUS_REPORT_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::PageUp);
+
+const EXPAND_SECTIONS_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::ArrowDown);
+
+const COLLAPSE_SECTIONS_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::ArrowUp);
+
+const SEARCH_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::F);
+
+/// Key of the temporary data that opens or closes every report section for a frame.
+const SECTIONS_OPEN_ID: &str = "sections_open";
+
+/// Returns the collapsing header of a report section, which follows the shortcuts that open or
+/// close every section.
+fn section(ui: &Ui, heading: impl Into<WidgetText>) -> CollapsingHeader {
+    let open = ui.data(|data| data.get_temp::<bool>(Id::new(SECTIONS_OPEN_ID)));
+
+    CollapsingHeader::new(heading).open(open)
+}
+
+impl Default for Gui {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let settings = settings::load_settings();
+
+        Self {
+            batch_progress: Default::default(),
+            batch_summary: Default::default(),
+            config: settings.analysis,
+            display: settings.display,
+            font_path: settings.font_path,
+
+            file_picker: FileDialog::default()
+                .add_file_filter(
+                    "Demo files (*.dem)",
+                    Arc::new(|path| path.extension().unwrap_or_default() == "dem"),
+                )
+                .default_file_filter("Demo files (*.dem)"),
+
+            font_picker: FileDialog::default()
+                .add_file_filter(
+                    "Font files (*.ttf, *.otf)",
+                    Arc::new(|path| {
+                        let extension = path.extension().unwrap_or_default();
+
+                        extension == "ttf" || extension == "otf"
+                    }),
+                )
+                .default_file_filter("Font files (*.ttf, *.otf)"),
+
+            index_picker: FileDialog::default()
+                .add_file_filter(
+                    "Index files (*.json)",
+                    Arc::new(|path| path.extension().unwrap_or_default() == "json"),
+                )
+                .default_file_filter("Index files (*.json)"),
+
+            export_picker: Default::default(),
+            export_demo_path: Default::default(),
+            export_error: Default::default(),
+            initial_files: Default::default(),
+            notes: notes::load_notes(),
+            note_drafts: Default::default(),
+            player_highlight: Default::default(),
+            open_windows: Default::default(),
+            report_titles: Default::default(),
+            impact_ratings: Default::default(),
+            rerun_changes: Default::default(),
+            reanalysis_baselines: Default::default(),
+            settings_window: Default::default(),
+            streak_leaderboard_open: Default::default(),
+            selected_reports: Default::default(),
+            combined_reports: Default::default(),
+            analyses: Default::default(),
+            archive_window: Default::default(),
+            rx,
+            tx,
+        }
+    }
+}
+
+impl eframe::App for Gui {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        match self.rx.try_recv() {
+            Ok(GuiMessage::Idle) => {
+                self.batch_progress = None;
+
+                // A single demo opens its report, which is feedback enough when it worked
+                if let Some(summary) = &mut self.batch_summary {
+                    summary.is_finished = true;
+
+                    if summary.files <= 1 && summary.failures.is_empty() {
+                        self.batch_summary = None;
+                    }
+                }
+            }
+
+            Ok(GuiMessage::AnalyzerStart { files }) => {
+                self.batch_progress = Some((0, files));
+                self.batch_summary = Some(BatchSummary {
+                    files,
+                    ..Default::default()
+                });
+            }
+
+            Ok(GuiMessage::AnalyzerFailure {
+                path,
+                error,
+                progress,
+            }) => {
+                self.batch_progress = Some(progress);
+
+                if let Some(summary) = &mut self.batch_summary {
+                    summary.failures.push((path, error));
+                }
+            }
+
+            Ok(GuiMessage::AnalyzerProgress {
+                mut file_info,
+                progress,
+                analysis,
+            }) => {
+                self.batch_progress = Some(progress);
+
+                // Demos are checked against the index of the archive being browsed, if any
+                if let Some(archive_window) = &self.archive_window {
+                    file_info.roster_newcomers =
+                        archive_window.index.roster_newcomers(&file_info, &analysis);
+                }
+
+                self.open_windows.insert(file_info.path.clone());
+
+                if let Some(summary) = &mut self.batch_summary {
+                    summary.analyzed.push(file_info.path.clone());
+                }
+
+                let existing = self
+                    .analyses
+                    .iter()
+                    .position(|(other, _)| other.path == file_info.path);
+
+                let old_results = match self.reanalysis_baselines.remove(&file_info.path) {
+                    Some(results) => Some(results),
+                    None => existing.map(|index| result_lines(&self.analyses[index].1)),
+                };
+
+                if let Some(old_results) = old_results {
+                    let changes = result_changes(&old_results, &result_lines(&analysis));
+
+                    self.rerun_changes.insert(file_info.path.clone(), changes);
+                }
+
+                self.impact_ratings.insert(
+                    file_info.path.clone(),
+                    analysis
+                        .state
+                        .impact_ratings(&win_model::win_model(&analysis.config)),
+                );
+
+                if let Some(index) = existing {
+                    self.analyses[index] = (file_info, *analysis);
+                } else {
+                    self.analyses.push((file_info, *analysis));
+                }
+
+                self.report_titles = report_titles(&self.analyses, &self.display);
+            }
+            _ => {}
+        }
+
+        if !self.initial_files.is_empty() {
+            analyze_files_async(
+                ctx.clone(),
+                self.tx.clone(),
+                self.initial_files.clone(),
+                self.config.clone(),
+            );
+
+            self.initial_files.clear();
+        }
+
+        self.file_picker.update(ctx);
+        self.font_picker.update(ctx);
+        self.index_picker.update(ctx);
+        self.export_picker.update(ctx);
+
+        if let Some(font_path) = self.font_picker.take_picked() {
+            add_user_font(ctx, &font_path);
+            self.font_path = Some(font_path);
+
+            let settings = settings::Settings {
+                analysis: self.config.clone(),
+                display: self.display.clone(),
+                font_path: self.font_path.clone(),
+            };
+
+            if let Err(e) = settings::save_settings(&settings) {
+                self.export_error = Some(format!("Could not save settings: {e}"));
+            }
+        }
+
+        if let Some(index_path) = self.index_picker.take_picked() {
+            self.archive_window = Some(ArchiveWindow::new(
+                &LocalStorage::default(),
+                &index_path.to_string_lossy(),
+            ));
+        }
+
+        if let Some(export_path) = self.export_picker.take_picked()
+            && let Some(demo_path) = self.export_demo_path.take()
+            && let Some((file_info, analysis)) = self
+                .analyses
+                .iter()
+                .find(|(file_info, _)| file_info.path == demo_path)
+        {
+            let series = timeline::team_score_timeline(analysis, file_info.rosters());
+
+            if let Err(e) = fs::write(&export_path, timeline::to_csv(&series)) {
+                self.export_error = Some(format!("Could not write {}: {e}", export_path.display()));
+            }
+        }
+
+        if let Some(export_error) = &self.export_error {
+            let mut is_open = true;
+
+            Window::new("Export failed")
+                .collapsible(false)
+                .resizable(false)
+                .open(&mut is_open)
+                .show(ctx, |ui| {
+                    ui.label(export_error);
+                });
+
+            if !is_open {
+                self.export_error = None;
+            }
+        }
+
+        copy_report_screenshots(ctx);
+
+        self.handle_shortcuts(ctx);
+
+        ctx.input(|i| {
+            let from_picker = self.file_picker.take_picked_multiple().unwrap_or_default();
+
+            let from_drop = i
+                .raw
+                .dropped_files
+                .iter()
+                .filter_map(|dropped_file| dropped_file.path.clone())
+                .collect::<Vec<PathBuf>>();
+
+            let demo_paths =
+                Vec::from_iter(from_picker.into_iter().chain(from_drop).filter(|path| {
+                    if let Some(path) = path.to_str().and_then(|str| String::from_str(str).ok()) {
+                        !self
+                            .analyses
+                            .iter()
+                            .any(|(file_info, _)| file_info.path == path)
+                    } else {
+                        false
+                    }
+                }));
+
+            if !demo_paths.is_empty() {
+                analyze_files_async(
+                    ctx.clone(),
+                    self.tx.clone(),
+                    demo_paths,
+                    self.config.clone(),
+                );
+            }
+        });
+
+        TopBottomPanel::top("controls")
+            .frame(Frame::side_top_panel(&ctx.style()).inner_margin(6.))
+            .show(ctx, |ui| {
+                Sides::default().show(
+                    ui,
+                    |ui| {
+                        ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+                            ui.menu_button("File ⏷", |ui| {
+                                let open_button = Button::new("Open")
+                                    .shortcut_text(ctx.format_shortcut(&OPEN_SHORTCUT));
+
+                                if ui.add(open_button).clicked() {
+                                    self.file_picker.pick_multiple();
+                                }
+
+                                let archive_button = Button::new("Open archive")
+                                    .shortcut_text(ctx.format_shortcut(&SEARCH_SHORTCUT));
+
+                                if ui
+                                    .add(archive_button)
+                                    .on_hover_text(
+                                        "Browse an index written by the CLI's index command",
+                                    )
+                                    .clicked()
+                                {
+                                    self.open_archive();
+                                }
+
+                                ui.separator();
+
+                                if ui.button("Quit").clicked() {
+                                    std::process::exit(0);
+                                }
+                            });
+
+                            ui.menu_button("Settings ⏷", |ui| {
+                                if ui.button("Load font").clicked() {
+                                    self.font_picker.pick_file();
+                                }
+
+                                if ui.button("Analysis settings").clicked() {
+                                    self.settings_window = Some(SettingsWindow {
+                                        config: self.config.clone(),
+                                        display: self.display.clone(),
+                                        error: None,
+                                    });
+                                }
+
+                                if cfg!(windows)
+                                    && ui
+                                        .button("Open demo files with dod-tools")
+                                        .on_hover_text(
+                                            "Open the report of a .dem file when it is \
+                                            double-clicked",
+                                        )
+                                        .clicked()
+                                {
+                                    let registered = std::env::current_exe().and_then(|exe| {
+                                        association::register_demo_association(&exe)
+                                    });
+
+                                    if let Err(e) = registered {
+                                        self.export_error = Some(format!(
+                                            "Could not make dod-tools open demo files: {e}"
+                                        ));
+                                    }
+                                }
+                            });
+
+                            if !self.analyses.is_empty() {
+                                ui.separator();
+
+                                if ui.button("Clear memory").clicked() {
+                                    self.open_windows.clear();
+                                    self.analyses.clear();
+                                    self.report_titles.clear();
+                                    self.impact_ratings.clear();
+                                    self.rerun_changes.clear();
+                                }
+
+                                if ui.button("Organize windows").clicked() {
+                                    ctx.memory_mut(|mem| mem.reset_areas());
+                                }
+
+                                ui.toggle_value(
+                                    &mut self.streak_leaderboard_open,
+                                    "Streak leaderboard",
+                                );
+                            };
+                        });
+                    },
+                    |ui| {
+                        egui::widgets::global_theme_preference_buttons(ui);
+                    },
+                );
+            });
+
+        if let Some(settings_window) = &mut self.settings_window {
+            match settings_window.show(ctx) {
+                Some(SettingsAction::Save) => {
+                    let settings = settings::Settings {
+                        analysis: settings_window.config.clone(),
+                        display: settings_window.display.clone(),
+                        font_path: self.font_path.clone(),
+                    };
+
+                    match settings::save_settings(&settings) {
+                        Ok(()) => {
+                            // Identities are saved separately, and may have changed since
+                            self.config = AnalysisConfig {
+                                identities: self.config.identities.clone(),
+                                ..settings_window.config.clone()
+                            };
+                            self.display = settings_window.display.clone();
+                            self.settings_window = None;
+                        }
+                        Err(e) => {
+                            settings_window.error = Some(format!("Could not save settings: {e}"));
+                        }
+                    }
+                }
+
+                Some(SettingsAction::Close) => {
+                    self.settings_window = None;
+                }
+
+                None => {}
+            }
+        }
+
+        if let Some(archive_window) = &mut self.archive_window {
+            match archive_window.show(ctx, &self.analyses, &self.display) {
+                Some(ArchiveAction::Open(demo_path)) => {
+                    let path = demo_path.to_string_lossy().to_string();
+
+                    // Demos that are already analyzed are shown as they are
+                    if self
+                        .analyses
+                        .iter()
+                        .any(|(file_info, _)| file_info.path == path)
+                    {
+                        self.open_windows.insert(path.clone());
+                        ctx.move_to_top(LayerId::new(Order::Middle, Id::from(path)));
+                    } else {
+                        analyze_files_async(
+                            ctx.clone(),
+                            self.tx.clone(),
+                            vec![demo_path],
+                            self.config.clone(),
+                        );
+                    }
+                }
+
+                Some(ArchiveAction::Reanalyze(demo_path, results)) => {
+                    let path = demo_path.to_string_lossy().to_string();
+
+                    self.reanalysis_baselines.insert(path, results);
+
+                    analyze_files_async(
+                        ctx.clone(),
+                        self.tx.clone(),
+                        vec![demo_path],
+                        self.config.clone(),
+                    );
+                }
+
+                Some(ArchiveAction::Close) => {
+                    self.archive_window = None;
+                }
+
+                None => {}
+            }
+        }
+
+        if self.streak_leaderboard_open && !self.analyses.is_empty() {
+            let mut jump_to = None;
+
+            Window::new("Streak Leaderboard")
+                .default_height(400.)
+                .open(&mut self.streak_leaderboard_open)
+                .show(ctx, |ui| {
+                    jump_to = streak_leaderboard_ui(&self.analyses, &self.report_titles, ui);
+                });
+
+            if let Some(demo_path) = jump_to {
+                self.open_windows.insert(demo_path.clone());
+                ctx.move_to_top(LayerId::new(Order::Middle, Id::from(demo_path)));
+            }
+        }
+
+        if let Some(combined_reports) = &self.combined_reports {
+            let mut is_open = true;
+            let analyses = self
+                .analyses
+                .iter()
+                .filter(|(file_info, _)| combined_reports.contains(&file_info.path))
+                .collect::<Vec<_>>();
+
+            Window::new("Combined")
+                .default_height(400.)
+                .open(&mut is_open)
+                .show(ctx, |ui| {
+                    combined_ui(&analyses, &self.report_titles, ui);
+                });
+
+            if !is_open {
+                self.combined_reports = None;
+            }
+        }
+
+        if let Some(summary) = self
+            .batch_summary
+            .as_ref()
+            .filter(|summary| summary.is_finished)
+        {
+            let mut is_open = true;
+            let mut jump_to = None;
+
+            Window::new("Batch summary")
+                .collapsible(false)
+                .default_height(400.)
+                .open(&mut is_open)
+                .show(ctx, |ui| {
+                    jump_to = batch_summary_ui(summary, &self.analyses, &self.report_titles, ui);
+                });
+
+            if let Some(demo_path) = jump_to {
+                self.open_windows.insert(demo_path.clone());
+                ctx.move_to_top(LayerId::new(Order::Middle, Id::from(demo_path)));
+            }
+
+            if !is_open {
+                self.batch_summary = None;
+            }
+        }
+
+        if let Some(batch_progress) = self.batch_progress {
+            TopBottomPanel::bottom("status")
+                .frame(Frame::side_top_panel(&ctx.style()).inner_margin(6.))
+                .show(ctx, |ui| {
+                    let bar_progress = (batch_progress.0 + 1) as f32 / batch_progress.1 as f32;
+                    let bar_label = format!(
+                        "Analyzing: {} of {}",
+                        batch_progress.0 + 1,
+                        batch_progress.1
+                    );
+
+                    ui.add(
+                        ProgressBar::new(bar_progress)
+                            .show_percentage()
+                            .text(bar_label),
+                    );
+                });
+        }
+
+        if !self.analyses.is_empty() {
+            SidePanel::new(Side::Left, "open_reports")
+                .frame(Frame::side_top_panel(&ctx.style()).inner_margin(6.))
+                .show(ctx, |ui| {
+                    ScrollArea::vertical().show(ui, |ui| {
+                        ui.with_layout(Layout::top_down_justified(Align::LEFT), |ui| {
+                            if ui
+                                .add_enabled(
+                                    self.selected_reports.len() > 1,
+                                    Button::new("Combine selected"),
+                                )
+                                .on_hover_text(
+                                    "Show one scoreboard with the results of the checked reports \
+                                    summed, e.g. both halves of a match",
+                                )
+                                .clicked()
+                            {
+                                self.combined_reports = Some(
+                                    self.analyses
+                                        .iter()
+                                        .map(|(file_info, _)| file_info.path.clone())
+                                        .filter(|path| self.selected_reports.contains(path))
+                                        .collect(),
+                                );
+                            }
+
+                            ui.separator();
+
+                            let mut reports = self.analyses.iter().peekable();
+
+                            while let Some((file_info, _)) = reports.next() {
+                                let demo_path = file_info.path.clone();
+                                let title =
+                                    self.report_titles.get(&demo_path).unwrap_or(&demo_path);
+                                let mut is_open = self.open_windows.contains(&demo_path);
+                                let mut is_selected = self.selected_reports.contains(&demo_path);
+
+                                ui.horizontal(|ui| {
+                                    ui.checkbox(&mut is_selected, "")
+                                        .on_hover_text("Select to combine with other reports");
+                                    ui.toggle_value(&mut is_open, title)
+                                        .on_hover_text(&demo_path);
+                                });
+
+                                if is_selected {
+                                    self.selected_reports.insert(demo_path.clone());
+                                } else {
+                                    self.selected_reports.remove(&demo_path);
+                                }
+
+                                if !is_open {
+                                    self.open_windows.remove(&demo_path);
+                                } else {
+                                    self.open_windows.insert(demo_path);
+                                }
+
+                                if reports.peek().is_some() {
+                                    ui.separator();
+                                }
+                            }
+                        });
+                    });
+                });
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            ui.centered_and_justified(|ui| {
+                if self.analyses.is_empty() {
+                    ui.heading("To start, drag and drop demos here or open with the File > Open menu.");
+                } else if self.open_windows.is_empty() {
+                    ui.heading(
+                        "You still have demos open. Select one from the list on the left to re-open an existing demo, or you can add a new demo.",
+                    );
+                }
+            });
+
+            let mut rerun_paths = vec![];
+            let mut timeline_export = None;
+            let mut merged_ids = None;
+
+            for (file_info, analysis) in &self.analyses {
+                let demo_path = &file_info.path;
+                let mut is_open = self.open_windows.contains(demo_path);
+                let title = self.report_titles.get(demo_path).unwrap_or(&file_info.name);
+
+                Window::new(title)
+                    .id(demo_path.clone().into())
+                    .default_height(600.)
+                    .open(&mut is_open)
+                    .show(ctx, |ui| {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    self.batch_progress.is_none(),
+                                    Button::new("Re-run analysis"),
+                                )
+                                .on_hover_text("Analyze the file again with the current settings")
+                                .clicked()
+                            {
+                                rerun_paths.push((
+                                    PathBuf::from(demo_path),
+                                    analysis.config.match_start_secs,
+                                ));
+                            }
+
+                            if ui
+                                .button("Copy screenshot")
+                                .on_hover_text("Copy an image of the whole report to the clipboard")
+                                .clicked()
+                            {
+                                ctx.send_viewport_cmd(ViewportCommand::Screenshot(UserData::new(
+                                    demo_path.clone(),
+                                )));
+                            }
+
+                            if self
+                                .rerun_changes
+                                .get(demo_path)
+                                .is_some_and(|changes| changes.is_empty())
+                            {
+                                ui.weak("No changes");
+                            }
+                        });
+
+                        if let Some(changes) = self
+                            .rerun_changes
+                            .get(demo_path)
+                            .filter(|changes| !changes.is_empty())
+                        {
+                            CollapsingHeader::new(
+                                RichText::new(format!("{} changed", changes.len()))
+                                    .color(ui.visuals().warn_fg_color),
+                            )
+                            .id_salt((demo_path, "changes"))
+                            .show(ui, |ui| {
+                                for change in changes {
+                                    ui.label(change);
+                                }
+                            });
+                        }
+
+                        section(ui, "Notes")
+                            .id_salt((demo_path, "notes"))
+                            .default_open(self.notes.note(demo_path).is_some())
+                            .show(ui, |ui| {
+                                let draft =
+                                    self.note_drafts.entry(demo_path.clone()).or_insert_with(|| {
+                                        self.notes.note(demo_path).unwrap_or_default().to_string()
+                                    });
+
+                                ui.add(
+                                    TextEdit::multiline(draft)
+                                        .hint_text("Notes shared with the other reviewers")
+                                        .desired_rows(3),
+                                );
+
+                                if ui.button("Save notes").clicked() {
+                                    match notes::save_note(demo_path, draft) {
+                                        Ok(notes) => self.notes = notes,
+                                        Err(e) => {
+                                            self.export_error =
+                                                Some(format!("Could not save notes: {e}"));
+                                        }
+                                    }
+                                }
+                            });
+
+                        ui.separator();
+
+                        let action =
+                            report_ui(
+                            file_info,
+                            analysis,
+                            &self.display,
+                            self.impact_ratings.get(demo_path),
+                            &mut self.player_highlight,
+                            ui,
+                        );
+
+                        match action {
+                            Some(ReportAction::ExportTimeline) => {
+                                timeline_export = Some(file_info);
+                            }
+
+                            Some(ReportAction::MarkMatchStart(secs)) => {
+                                rerun_paths.push((PathBuf::from(demo_path), Some(secs)));
+                            }
+
+                            Some(ReportAction::MergeIdentities(ids)) => {
+                                merged_ids = Some(ids);
+                            }
+
+                            None => {}
+                        }
+                    });
+
+                if !is_open {
+                    self.open_windows.remove(demo_path);
+                } else {
+                    self.open_windows.insert(demo_path.clone());
+                }
+            }
+
+            if let Some(ids) = merged_ids {
+                match settings::merge_identities(&ids) {
+                    Ok(identities) => self.config.identities = identities,
+                    Err(e) => {
+                        self.config.identities.merge(&ids);
+                        self.export_error = Some(format!("Could not save identities: {e}"));
+                    }
+                }
+
+                // Every report could include one of the merged players
+                rerun_paths.extend(self.analyses.iter().map(|(file_info, analysis)| {
+                    (
+                        PathBuf::from(&file_info.path),
+                        analysis.config.match_start_secs,
+                    )
+                }));
+            }
+
+            // Demos keep the match start they were marked with
+            for (demo_path, match_start_secs) in rerun_paths {
+                let config = AnalysisConfig {
+                    match_start_secs,
+                    ..self.config.clone()
+                };
+
+                analyze_files_async(ctx.clone(), self.tx.clone(), vec![demo_path], config);
+            }
+
+            if let Some(file_info) = timeline_export {
+                let file_stem = Path::new(&file_info.name)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(&file_info.name);
+
+                self.export_picker =
+                    FileDialog::new().default_file_name(&format!("{file_stem}.timeline.csv"));
+                self.export_picker.save_file();
+                self.export_demo_path = Some(file_info.path.clone());
+            }
+        });
+    }
+}
+
+/// Copies the part of each finished screenshot that shows the report it was requested for.
+///
+/// Report windows grow to fit their contents, so the image has every expanded section.
+fn copy_report_screenshots(ctx: &Context) {
+    let screenshots = ctx.input(|i| {
+        i.events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Screenshot {
+                    user_data, image, ..
+                } => {
+                    let demo_path = user_data.data.as_ref()?.downcast_ref::<String>()?;
+
+                    Some((demo_path.clone(), image.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for (demo_path, image) in screenshots {
+        let Some(window_rect) = ctx.memory(|mem| mem.area_rect(Id::from(demo_path))) else {
+            continue;
+        };
+
+        // Parts of the window outside of the viewport are not in the screenshot
+        let pixels_per_point = ctx.pixels_per_point();
+        let [width, height] = image.size;
+        let image_rect = Rect::from_min_size(
+            Pos2::ZERO,
+            Vec2::new(width as f32, height as f32) / pixels_per_point,
+        );
+
+        let region = window_rect.intersect(image_rect);
+
+        if region.is_positive() {
+            ctx.copy_image(image.region(&region, Some(pixels_per_point)));
+        }
+    }
+}
+
+/// Minimum share of players two demos must have in common on each side to be halves of one match.
+const SAME_MATCH_ROSTER_OVERLAP: f32 = 0.5;
+
+/// Builds a title for each report from the clans, map, half and date of the match, keyed by the
+/// file path.
+///
+/// Demos on the same map whose rosters overlap are treated as halves of one match, numbered in the
+/// order they were recorded.
+fn report_titles(
+    analyses: &[(FileInfo, Analysis)],
+    display: &DisplaySettings,
+) -> HashMap<String, String> {
+    let clans = analyses
+        .iter()
+        .map(|(_, analysis)| {
+            (
+                Clan::new(&analysis.state, Team::Allies, &[]),
+                Clan::new(&analysis.state, Team::Axis, &[]),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    analyses
+        .iter()
+        .zip(&clans)
+        .map(|((file_info, analysis), (allies, axis))| {
+            let map_name = &analysis.demo_info.map_name;
+
+            let halves = analyses
+                .iter()
+                .zip(&clans)
+                .filter(|((_, other), _)| other.demo_info.map_name == *map_name)
+                .filter(|(_, (other_allies, other_axis))| {
+                    let same_sides = allies
+                        .roster_overlap(other_allies)
+                        .min(axis.roster_overlap(other_axis));
+                    let swapped_sides = allies
+                        .roster_overlap(other_axis)
+                        .min(axis.roster_overlap(other_allies));
+
+                    same_sides.max(swapped_sides) >= SAME_MATCH_ROSTER_OVERLAP
+                })
+                .map(|((other_file_info, _), _)| other_file_info.created_at)
+                .collect::<Vec<_>>();
+
+            let mut title = format!("{} vs {} on {map_name}", allies.name(), axis.name());
+
+            if halves.len() > 1 {
+                let half = halves
+                    .iter()
+                    .filter(|created_at| **created_at < file_info.created_at)
+                    .count()
+                    + 1;
+
+                title += &format!(", half {half}");
+            }
+
+            let created_at = settings::format_local_time(file_info.created_at, display);
+            let date = created_at.split('T').next().unwrap_or(&created_at);
+
+            title += &format!(" ({date})");
+
+            (file_info.path.clone(), title)
+        })
+        .collect()
+}
+
+/// System fonts with broad Unicode coverage, used for glyphs missing from the built-in fonts.
+///
+/// The first font that exists on the system is loaded.
+const SYSTEM_FALLBACK_FONTS: [&str; 6] = [
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/noto/NotoSans-Regular.ttf",
+];
+
+fn add_system_fallback_fonts(ctx: &Context) {
+    let system_font = SYSTEM_FALLBACK_FONTS
+        .iter()
+        .find_map(|path| fs::read(path).ok().map(|bytes| (path, bytes)));
+
+    if let Some((path, bytes)) = system_font {
+        add_font(ctx, path, bytes, FontPriority::Lowest);
+    }
+}
+
+/// Loads a font chosen by the user, preferring it over the built-in fonts.
+fn add_user_font(ctx: &Context, path: &Path) {
+    if let (Ok(bytes), Some(name)) = (fs::read(path), path.to_str()) {
+        add_font(ctx, name, bytes, FontPriority::Highest);
+    }
+}
+
+fn add_font(ctx: &Context, name: &str, bytes: Vec<u8>, priority: FontPriority) {
+    let families = [FontFamily::Proportional, FontFamily::Monospace]
+        .into_iter()
+        .map(|family| InsertFontFamily {
+            family,
+            priority: priority.clone(),
+        })
+        .collect();
+
+    ctx.add_font(FontInsert::new(name, FontData::from_owned(bytes), families));
+}
+
+const TABLE_ROW_HEIGHT: f32 = 18.;
+
+const NEUTRAL_COLOR: Color32 = Color32::WHITE;
+
+fn team_color(display: &DisplaySettings, team: &Team) -> Color32 {
+    let [r, g, b] = match team {
+        Team::Allies => display.allies_color,
+        Team::Axis => display.axis_color,
+        _ => return NEUTRAL_COLOR,
+    };
+
+    Color32::from_rgb(r, g, b)
+}
+
+/// Shows a button that copies the console commands to play the demo from the moment, to review
+/// it in game.
+fn seek_button_ui(file_info: &FileInfo, time: &GameTime, ui: &mut Ui) {
+    let command = file_info.seek_command(time);
+
+    if ui
+        .small_button("📋")
+        .on_hover_text(format!("Copy {command}"))
+        .clicked()
+    {
+        ui.ctx().copy_text(command);
+    }
+}
+
+fn analyze_files_async(
+    ctx: Context,
+    tx: mpsc::Sender<GuiMessage>,
+    paths: Vec<PathBuf>,
+    config: AnalysisConfig,
+) {
+    tokio::spawn(async move {
+        tx.send(GuiMessage::AnalyzerStart { files: paths.len() })
+            .unwrap();
+
+        for (index, demo_path) in paths.iter().enumerate() {
+            let progress = (index + 1, paths.len());
+
+            // A demo that cannot be read should not stop the rest of the batch
+            let message = match panic::catch_unwind(|| run_analyzer(demo_path, &config)) {
+                Ok((file_info, analysis)) => GuiMessage::AnalyzerProgress {
+                    file_info,
+                    progress,
+                    analysis: Box::new(analysis),
+                },
+
+                Err(payload) => GuiMessage::AnalyzerFailure {
+                    path: demo_path.to_string_lossy().to_string(),
+                    error: payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or("Unknown error".to_string()),
+                    progress,
+                },
+            };
+
+            tx.send(message).unwrap();
+
+            ctx.request_repaint();
+        }
+
+        tx.send(GuiMessage::Idle).unwrap();
+    });
+}
@@ -0,0 +1,217 @@
+//! Window that browses the demos listed in an index.
+
+use crate::gui::TABLE_ROW_HEIGHT;
+use analysis::Analysis;
+use egui::{Align, Context, Grid, Layout, TextEdit, Window};
+use egui_extras::{Column, TableBuilder};
+use native::{
+    FileInfo,
+    index::{DemoFilter, DemoIndex},
+    results::ResultLine,
+    settings::{self, DisplaySettings},
+    storage::Storage,
+};
+use std::path::{Path, PathBuf};
+
+/// Browses the demos listed in an index written by the CLI's `index` command.
+pub struct ArchiveWindow {
+    pub index: DemoIndex,
+    error: Option<String>,
+
+    player: String,
+    map_name: String,
+    clan: String,
+
+    /// Dates in the `YYYY-MM-DD` format.
+    recorded_after: String,
+    recorded_before: String,
+
+    /// Moves the keyboard focus to the player filter on the next frame.
+    pub focus_search: bool,
+}
+
+pub enum ArchiveAction {
+    /// Show the report of the demo, analyzing it first if it is not open yet.
+    Open(PathBuf),
+
+    /// Analyze the demo again, and show how the results differ from the indexed ones.
+    Reanalyze(PathBuf, Vec<ResultLine>),
+
+    Close,
+}
+
+impl ArchiveWindow {
+    pub fn new(storage: &dyn Storage, key: &str) -> Self {
+        let (index, error) = match DemoIndex::load_from(storage, key) {
+            Ok(index) => (index, None),
+            Err(e) => (
+                DemoIndex::default(),
+                Some(format!("Could not read {key}: {e}")),
+            ),
+        };
+
+        Self {
+            index,
+            error,
+            player: String::new(),
+            map_name: String::new(),
+            clan: String::new(),
+            recorded_after: String::new(),
+            recorded_before: String::new(),
+            focus_search: true,
+        }
+    }
+
+    fn filter(&self) -> DemoFilter {
+        let text = |value: &str| Some(value.trim().to_string()).filter(|value| !value.is_empty());
+        let date = |value: &str, time: &str| {
+            humantime::parse_rfc3339_weak(&format!("{} {time}", value.trim())).ok()
+        };
+
+        DemoFilter {
+            player: text(&self.player),
+            map_name: text(&self.map_name),
+            clan: text(&self.clan),
+            recorded_after: date(&self.recorded_after, "00:00:00"),
+            recorded_before: date(&self.recorded_before, "23:59:59"),
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        analyses: &[(FileInfo, Analysis)],
+        display: &DisplaySettings,
+    ) -> Option<ArchiveAction> {
+        let mut action = None;
+        let mut is_open = true;
+
+        Window::new("Archive")
+            .default_height(500.)
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                Grid::new("archive_filters").num_columns(2).show(ui, |ui| {
+                    for (label, value, hint) in [
+                        ("Player", &mut self.player, "Steam ID or name"),
+                        ("Map", &mut self.map_name, "e.g. dod_anzio"),
+                        ("Clan", &mut self.clan, "Part of the clan name"),
+                        ("Recorded after", &mut self.recorded_after, "YYYY-MM-DD"),
+                        ("Recorded before", &mut self.recorded_before, "YYYY-MM-DD"),
+                    ] {
+                        let label = ui.label(label);
+                        let response = ui
+                            .add(TextEdit::singleline(value).hint_text(hint))
+                            .labelled_by(label.id);
+
+                        if std::mem::take(&mut self.focus_search) {
+                            response.request_focus();
+                        }
+
+                        ui.end_row();
+                    }
+                });
+
+                ui.separator();
+
+                let filter = self.filter();
+                let demos = self.index.search(&filter).collect::<Vec<_>>();
+
+                ui.label(format!(
+                    "{} of {} demos",
+                    demos.len(),
+                    self.index.demos.len()
+                ));
+
+                let outdated = self.index.outdated();
+
+                if outdated > 0 {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        format!(
+                            "{outdated} demos were indexed by another version of the analyzer, so \
+                            their results may not compare. Run the CLI's index command again to \
+                            update them."
+                        ),
+                    );
+                }
+
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .cell_layout(Layout::left_to_right(Align::Center))
+                    .columns(Column::auto(), 6)
+                    .header(TABLE_ROW_HEIGHT, |mut row| {
+                        for column in ["Date", "Map", "Allies", "Axis", "Score", ""] {
+                            row.col(|ui| {
+                                ui.strong(column);
+                            });
+                        }
+                    })
+                    .body(|mut body| {
+                        for demo in demos {
+                            let [allies, axis] = &demo.clans;
+                            let [allies_score, axis_score] = demo.score;
+
+                            body.row(TABLE_ROW_HEIGHT, |mut row| {
+                                row.col(|ui| {
+                                    ui.label(settings::format_local_time(demo.created_at, display))
+                                        .on_hover_text(demo.path.display().to_string());
+                                });
+
+                                row.col(|ui| {
+                                    ui.label(&demo.map_name);
+                                });
+
+                                row.col(|ui| {
+                                    ui.label(allies);
+                                });
+
+                                row.col(|ui| {
+                                    ui.label(axis);
+                                });
+
+                                row.col(|ui| {
+                                    ui.label(format!("{allies_score} - {axis_score}"));
+                                });
+
+                                row.col(|ui| {
+                                    let is_analyzed = analyses.iter().any(|(file_info, _)| {
+                                        Path::new(&file_info.path) == demo.path
+                                    });
+
+                                    let label = if is_analyzed { "Show" } else { "Open" };
+
+                                    if ui.button(label).clicked() {
+                                        action = Some(ArchiveAction::Open(demo.path.clone()));
+                                    }
+
+                                    if demo.is_outdated()
+                                        && ui
+                                            .button("Re-analyze and show changes")
+                                            .on_hover_text(match demo.analyzer_version.as_str() {
+                                                "" => "Indexed by an older version".to_string(),
+                                                version => format!("Indexed by version {version}"),
+                                            })
+                                            .clicked()
+                                    {
+                                        action = Some(ArchiveAction::Reanalyze(
+                                            demo.path.clone(),
+                                            demo.results.clone(),
+                                        ));
+                                    }
+                                });
+                            });
+                        }
+                    });
+            });
+
+        if !is_open {
+            action = Some(ArchiveAction::Close);
+        }
+
+        action
+    }
+}
@@ -0,0 +1,107 @@
+//! Window that sums up the last batch of demos analyzed.
+
+use crate::gui::BatchSummary;
+use analysis::Analysis;
+use egui::{Grid, ScrollArea, Ui};
+use native::FileInfo;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+/// Number of players to watch listed in the batch summary.
+const BATCH_HIGHLIGHTS_SIZE: usize = 5;
+
+/// Shows what a finished batch found, returning the path of a report to jump to.
+pub fn batch_summary_ui(
+    summary: &BatchSummary,
+    analyses: &[(FileInfo, Analysis)],
+    report_titles: &HashMap<String, String>,
+    ui: &mut Ui,
+) -> Option<String> {
+    let mut jump_to = None;
+
+    let batch = analyses
+        .iter()
+        .filter(|(file_info, _)| summary.analyzed.contains(&file_info.path))
+        .collect::<Vec<_>>();
+
+    let players = batch
+        .iter()
+        .flat_map(|(_, analysis)| analysis.state.players.iter())
+        .map(|player| &player.id)
+        .collect::<HashSet<_>>();
+
+    Grid::new("batch_summary_grid").show(ui, |ui| {
+        ui.strong("Files analyzed");
+        ui.label(format!("{} of {}", summary.analyzed.len(), summary.files));
+        ui.end_row();
+
+        ui.strong("Failures");
+        ui.label(summary.failures.len().to_string());
+        ui.end_row();
+
+        ui.strong("Players found");
+        ui.label(players.len().to_string());
+        ui.end_row();
+    });
+
+    if !summary.failures.is_empty() {
+        ui.separator();
+
+        for (path, error) in &summary.failures {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {path}"))
+                .on_hover_text(error);
+        }
+    }
+
+    let mut players_to_watch = batch
+        .iter()
+        .flat_map(|(file_info, analysis)| {
+            analysis
+                .state
+                .players_to_watch()
+                .into_iter()
+                .map(move |watch| (file_info, watch))
+        })
+        .collect::<Vec<_>>();
+
+    players_to_watch.sort_by_key(|(_, watch)| Reverse(watch.score));
+
+    if !players_to_watch.is_empty() {
+        ui.separator();
+        ui.strong("Players to watch");
+
+        Grid::new("batch_highlights_grid")
+            .striped(true)
+            .show(ui, |ui| {
+                for (file_info, watch) in players_to_watch.iter().take(BATCH_HIGHLIGHTS_SIZE) {
+                    let title = report_titles
+                        .get(&file_info.path)
+                        .unwrap_or(&file_info.name);
+
+                    ui.label(&watch.player.name);
+                    ui.label(format!("{} points", watch.score));
+                    ui.label(title).on_hover_text(&file_info.path);
+                    ui.end_row();
+                }
+            });
+    }
+
+    if !batch.is_empty() {
+        ui.separator();
+        ui.strong("Reports");
+
+        ScrollArea::vertical().max_height(200.).show(ui, |ui| {
+            for (file_info, _) in &batch {
+                let title = report_titles
+                    .get(&file_info.path)
+                    .unwrap_or(&file_info.name);
+
+                if ui.link(title).on_hover_text(&file_info.path).clicked() {
+                    jump_to = Some(file_info.path.clone());
+                }
+            }
+        });
+    }
+
+    jump_to
+}
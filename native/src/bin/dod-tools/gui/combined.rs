@@ -0,0 +1,87 @@
+//! Window with one scoreboard for the reports checked in the list of open reports.
+
+use crate::gui::TABLE_ROW_HEIGHT;
+use analysis::{Analysis, MatchAggregation, rank};
+use egui::{Align, Layout, Ui};
+use egui_extras::{Column, TableBuilder};
+use native::FileInfo;
+use std::collections::HashMap;
+
+/// Shows one scoreboard with the results of each player summed across the analyses.
+pub fn combined_ui(
+    analyses: &[&(FileInfo, Analysis)],
+    report_titles: &HashMap<String, String>,
+    ui: &mut Ui,
+) {
+    for (file_info, _) in analyses {
+        ui.label(
+            report_titles
+                .get(&file_info.path)
+                .unwrap_or(&file_info.name),
+        )
+        .on_hover_text(&file_info.path);
+    }
+
+    ui.separator();
+
+    let aggregation = MatchAggregation::new(analyses.iter().map(|(_, analysis)| analysis));
+    let mut players = aggregation.players.iter().collect::<Vec<_>>();
+    players.sort_by(|l, r| rank(&l.stats, &r.stats));
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .cell_layout(Layout::left_to_right(Align::Center))
+        .columns(Column::auto(), 9)
+        .header(TABLE_ROW_HEIGHT, |mut row| {
+            for column in [
+                "Name",
+                "Demos",
+                "Score",
+                "Kills",
+                "Deaths",
+                "Caps/Obj Points",
+                "Rounds",
+                "Longest Streak",
+                "Top Weapon",
+            ] {
+                row.col(|ui| {
+                    ui.strong(column);
+                });
+            }
+        })
+        .body(|mut body| {
+            for player in players {
+                let (score, kills, deaths) = player.stats;
+
+                body.row(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        ui.label(&player.name);
+                    });
+
+                    for value in [
+                        player.analyses as i32,
+                        score,
+                        kills,
+                        deaths,
+                        player.objective_score,
+                        player.rounds_played as i32,
+                        player.longest_kill_streak() as i32,
+                    ] {
+                        row.col(|ui| {
+                            ui.label(value.to_string());
+                        });
+                    }
+
+                    row.col(|ui| {
+                        if let Some((weapon, kills, _)) = player
+                            .weapons_by_kills()
+                            .into_iter()
+                            .find(|(_, kills, _)| *kills > 0)
+                        {
+                            ui.label(format!("{weapon} ({kills})"));
+                        }
+                    });
+                });
+            }
+        });
+}
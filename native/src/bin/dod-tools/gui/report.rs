@@ -0,0 +1,305 @@
+//! Window with the report of a demo.
+
+mod data_quality;
+mod kills;
+mod players;
+mod rounds;
+mod scoreboard;
+mod watch;
+
+use crate::gui::report::data_quality::{
+    data_quality_ui, server_performance_ui, suspicious_events_ui,
+};
+use crate::gui::report::kills::{
+    danger_zones_ui, kill_feed_ui, matchups_ui, score_check_ui, teamkills_ui,
+};
+use crate::gui::report::players::player_summaries_ui;
+use crate::gui::report::rounds::{
+    death_economy_ui, match_pace_ui, rounds_ui, team_score_timeline_ui, warmup_ui,
+};
+use crate::gui::report::scoreboard::scoreboard_ui;
+use crate::gui::report::watch::{late_joins_ui, players_to_watch_ui};
+use crate::gui::{PlayerHighlighting, TABLE_ROW_HEIGHT, section};
+use analysis::{Analysis, Clan, Narrative, PlayerGlobalId, Team};
+use egui::{Align, Grid, Label, Layout, Ui};
+use egui_extras::{Column, TableBuilder};
+use native::{
+    ANALYZER_VERSION, FileInfo,
+    settings::{self, DisplaySettings},
+};
+use std::collections::HashMap;
+
+/// Something a report asks the app to do, which needs more than the report's own data.
+pub enum ReportAction {
+    ExportTimeline,
+
+    /// Analyze the demo again with the match starting at this many seconds into it.
+    MarkMatchStart(u64),
+
+    /// Declare that the players with these IDs are the same person.
+    MergeIdentities(Vec<PlayerGlobalId>),
+}
+
+pub fn report_ui(
+    file_info: &FileInfo,
+    r: &Analysis,
+    display: &DisplaySettings,
+    impact_ratings: Option<&HashMap<PlayerGlobalId, f32>>,
+    player_highlighting: &mut PlayerHighlighting,
+    ui: &mut Ui,
+) -> Option<ReportAction> {
+    let header_action = header_ui(file_info, r, display, ui);
+
+    ui.separator();
+
+    let scoreboard_action = scoreboard_ui(r, player_highlighting, ui);
+
+    ui.separator();
+
+    let timeline_action = team_score_timeline_ui(file_info, r, display, ui);
+
+    ui.separator();
+
+    rounds_ui(r, display, ui);
+
+    ui.separator();
+
+    match_pace_ui(r, ui);
+
+    ui.separator();
+
+    death_economy_ui(r, display, ui);
+
+    ui.separator();
+
+    kill_feed_ui(r, ui);
+
+    ui.separator();
+
+    teamkills_ui(r, ui);
+
+    ui.separator();
+
+    if let Some(formula) = &r.config.score_formula {
+        score_check_ui(r, formula, ui);
+
+        ui.separator();
+    }
+
+    matchups_ui(r, ui);
+
+    ui.separator();
+
+    players_to_watch_ui(file_info, r, ui);
+
+    ui.separator();
+
+    late_joins_ui(file_info, r, ui);
+
+    ui.separator();
+
+    warmup_ui(r, ui);
+
+    ui.separator();
+
+    danger_zones_ui(r, display, ui);
+
+    ui.separator();
+
+    suspicious_events_ui(r, ui);
+
+    ui.separator();
+
+    data_quality_ui(r, ui);
+
+    ui.separator();
+
+    server_performance_ui(r, ui);
+
+    ui.separator();
+
+    chat_ui(r, ui);
+
+    ui.separator();
+
+    narrative_ui(r, ui);
+
+    ui.separator();
+
+    player_summaries_ui(file_info, r, impact_ratings, player_highlighting, ui);
+
+    header_action.or(scoreboard_action).or(timeline_action)
+}
+
+fn header_ui(
+    file_info: &FileInfo,
+    analysis: &Analysis,
+    display: &DisplaySettings,
+    ui: &mut Ui,
+) -> Option<ReportAction> {
+    let mut action = None;
+
+    section(ui, "Summary").default_open(true).show(ui, |ui| {
+        Grid::new("header").show(ui, |ui| {
+            ui.strong("File path");
+            ui.monospace(&file_info.path);
+            ui.end_row();
+
+            ui.strong("File created at");
+            ui.label(settings::format_local_time(file_info.created_at, display));
+            ui.end_row();
+
+            ui.strong("Map name");
+            ui.label(&analysis.demo_info.map_name);
+            ui.end_row();
+
+            ui.strong("Demo protocol");
+            ui.label(analysis.demo_info.demo_protocol.to_string());
+            ui.end_row();
+
+            ui.strong("Network protocol");
+            ui.label(analysis.demo_info.network_protocol.to_string());
+            ui.end_row();
+
+            ui.strong("Game version (estimated)");
+            ui.label(analysis.demo_info.game_version.to_string());
+            ui.end_row();
+
+            ui.strong("Match went live");
+            match analysis.state.match_start_time() {
+                Some(time) => {
+                    let time = settings::format_game_time(time, &analysis.config);
+                    let source = match analysis.config.match_start_secs {
+                        Some(_) => "set manually",
+                        None => "detected",
+                    };
+                    ui.label(format!("{time} ({source})"))
+                }
+                None if !analysis.config.clan_match_detection => ui.label("Detection disabled"),
+                None => ui.label("Not detected"),
+            };
+            ui.end_row();
+
+            if analysis.state.match_start_candidates().len() > 1 {
+                ui.strong("Restarts before going live");
+                ui.horizontal(|ui| {
+                    for time in analysis.state.match_start_candidates() {
+                        let secs = time.viewdemo_offset.as_secs();
+
+                        if ui
+                            .button(settings::format_game_time(time, &analysis.config))
+                            .on_hover_text(
+                                "Analyze the demo again with the match going live at this time",
+                            )
+                            .clicked()
+                        {
+                            action = Some(ReportAction::MarkMatchStart(secs));
+                        }
+                    }
+                });
+                ui.end_row();
+            }
+
+            ui.strong("Server");
+            if analysis.demo_info.is_modified_server() {
+                ui.label("Modified").on_hover_text(format!(
+                    "Registers messages the mod does not: {}",
+                    analysis.demo_info.extra_messages.join(", ")
+                ));
+            } else {
+                ui.label("Stock");
+            }
+            ui.end_row();
+
+            ui.strong("Directory");
+            ui.vertical(|ui| {
+                for entry in &analysis.demo_info.directory_entries {
+                    ui.label(entry.to_string());
+                }
+            });
+            ui.end_row();
+
+            if analysis.demo_info.has_multiple_segments() {
+                ui.strong("Playback segments");
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    analysis.demo_info.playback_segments().count().to_string(),
+                )
+                .on_hover_text(
+                    "The demo was stopped and recorded again, so events between the segments are \
+                    missing",
+                );
+                ui.end_row();
+            }
+
+            ui.strong("Teams");
+            ui.label(format!(
+                "{} (Allies) vs {} (Axis)",
+                Clan::new(&analysis.state, Team::Allies, &[]).name(),
+                Clan::new(&analysis.state, Team::Axis, &[]).name()
+            ));
+            ui.end_row();
+
+            ui.strong("Analyzer version");
+            ui.label(ANALYZER_VERSION);
+            ui.end_row();
+        });
+    });
+
+    action
+}
+
+fn chat_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Chat").show(ui, |ui| {
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .max_scroll_height(260.)
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::remainder())
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                row.col(|ui| {
+                    ui.strong("Time");
+                });
+                row.col(|ui| {
+                    ui.strong("Player");
+                });
+                row.col(|ui| {
+                    ui.strong("Message");
+                });
+            })
+            .body(|mut body| {
+                for message in &r.state.chat {
+                    let sender = message
+                        .sender
+                        .as_ref()
+                        .and_then(|id| r.state.players.iter().find(|player| player.id == *id));
+
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(settings::format_game_time(&message.time, &r.config));
+                        });
+
+                        row.col(|ui| {
+                            if let Some(sender) = sender {
+                                ui.label(&sender.name);
+                            }
+                        });
+
+                        row.col(|ui| {
+                            ui.add(Label::new(&message.text).extend());
+                        });
+                    });
+                }
+            });
+    });
+}
+
+fn narrative_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Narrative").show(ui, |ui| {
+        let narrative = Narrative::new(&r.state, &r.config.narrative);
+
+        ui.label(narrative.to_string());
+    });
+}
@@ -0,0 +1,279 @@
+//! Sections of a report about how far its results can be trusted.
+
+use crate::gui::{TABLE_ROW_HEIGHT, section};
+use analysis::{Analysis, KillLatency};
+use egui::{Align, Grid, Id, Label, Layout, RichText, Sense, Ui};
+use egui_extras::{Column, TableBuilder};
+use native::settings;
+
+pub fn suspicious_events_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Suspicious Events").show(ui, |ui| {
+        ui.label("These are detected with heuristics and are not proof of cheating.");
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .max_scroll_height(260.)
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::remainder())
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                row.col(|ui| {
+                    ui.strong("Time");
+                });
+                row.col(|ui| {
+                    ui.strong("Player");
+                });
+                row.col(|ui| {
+                    ui.strong("Event");
+                });
+            })
+            .body(|mut body| {
+                for event in r.state.suspicious_events() {
+                    let player = r
+                        .state
+                        .players
+                        .iter()
+                        .find(|player| player.id == event.player);
+
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(settings::format_game_time(&event.time, &r.config));
+                        });
+
+                        row.col(|ui| {
+                            if let Some(player) = player {
+                                ui.label(&player.name);
+                            }
+                        });
+
+                        row.col(|ui| {
+                            ui.label(event.kind.to_string());
+                        });
+                    });
+                }
+            });
+    });
+}
+
+/// Sections of a report whose numbers can be estimates when the demo has problems.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AffectedSection {
+    Scoreboard,
+    Timeline,
+    Rounds,
+    KillFeed,
+}
+
+impl AffectedSection {
+    /// Returns the names of the messages the section is built from.
+    fn messages(&self) -> &'static [&'static str] {
+        match self {
+            Self::Scoreboard => &[
+                "DeathMsg",
+                "Frags",
+                "ObjScore",
+                "PClass",
+                "PTeam",
+                "ScoreInfo",
+                "ScoreShort",
+            ],
+            Self::Timeline => &["CapMsg", "RoundState", "TeamScore"],
+            Self::Rounds => &["DeathMsg", "RoundState"],
+            Self::KillFeed => &["DeathMsg"],
+        }
+    }
+}
+
+/// Returns the problems with the demo that make the numbers in the section estimates.
+fn section_warnings(r: &Analysis, section: AffectedSection) -> Vec<String> {
+    let mut warnings = r
+        .state
+        .malformed_message_counts()
+        .into_iter()
+        .filter(|(name, _)| section.messages().contains(name))
+        .map(|(name, count)| format!("{count} {name} messages could not be read"))
+        .collect::<Vec<_>>();
+
+    if !r.state.server_anomalies.is_empty() && section != AffectedSection::Rounds {
+        warnings.push(format!(
+            "The server lagged {} times, so events may be missing around them",
+            r.state.server_anomalies.len()
+        ));
+    }
+
+    if r.demo_info.has_multiple_segments() {
+        warnings.push(format!(
+            "The demo has {} playback segments, and events between them are missing",
+            r.demo_info.playback_segments().count()
+        ));
+    }
+
+    warnings
+}
+
+/// Shows a warning icon when the section is affected by problems with the demo, which opens the
+/// Data Quality section when clicked.
+pub fn section_warnings_ui(r: &Analysis, section: AffectedSection, ui: &mut Ui) {
+    let warnings = section_warnings(r, section);
+
+    if warnings.is_empty() {
+        return;
+    }
+
+    let icon = Label::new(RichText::new("⚠ Estimated").color(ui.visuals().warn_fg_color))
+        .sense(Sense::click());
+
+    let response = ui.add(icon).on_hover_text(format!(
+        "{}\n\nClick to see every warning in Data Quality.",
+        warnings.join("\n")
+    ));
+
+    if response.clicked() {
+        // Every section of a report is drawn in the layer of its window
+        let show_id = Id::new(SHOW_DATA_QUALITY_ID).with(ui.layer_id());
+
+        ui.data_mut(|data| data.insert_temp(show_id, true));
+    }
+}
+
+/// Key of the temporary data that opens and scrolls to the Data Quality section of a report.
+const SHOW_DATA_QUALITY_ID: &str = "show_data_quality";
+
+pub fn data_quality_ui(r: &Analysis, ui: &mut Ui) {
+    let show_id = Id::new(SHOW_DATA_QUALITY_ID).with(ui.layer_id());
+    let show = ui
+        .data_mut(|data| data.remove_temp::<bool>(show_id))
+        .is_some();
+
+    let mut data_quality = section(ui, "Data Quality");
+
+    if show {
+        data_quality = data_quality.open(Some(true));
+    }
+
+    let response = data_quality.show(ui, |ui| {
+        let malformed_counts = r.state.malformed_message_counts();
+
+        if malformed_counts.is_empty() {
+            ui.label("Every message in the demo was read.");
+        } else {
+            let counts = malformed_counts
+                .iter()
+                .map(|(description, count)| format!("{description} × {count}"))
+                .collect::<Vec<_>>();
+
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "{} malformed messages could not be read",
+                    r.state.malformed_messages.len()
+                ),
+            )
+            .on_hover_text(format!(
+                "The events in them are missing from the report: {}",
+                counts.join(", ")
+            ));
+        }
+
+        if !r.state.server_anomalies.is_empty() {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "The server lagged {} times, listed in Server Performance",
+                    r.state.server_anomalies.len()
+                ),
+            );
+        }
+
+        if r.demo_info.has_multiple_segments() {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "The demo has {} playback segments, and events between them are missing",
+                    r.demo_info.playback_segments().count()
+                ),
+            );
+        }
+
+        let Some(latency) = KillLatency::new(&r.state) else {
+            ui.label("No kills to measure the delay of kill messages.");
+            return;
+        };
+
+        Grid::new("data_quality_grid").show(ui, |ui| {
+            ui.label("Recorded by");
+            ui.label(if latency.recorded_by_hltv {
+                "HLTV"
+            } else {
+                "Player (POV)"
+            });
+            ui.end_row();
+
+            ui.label("Kill message delay");
+            ui.label(format!(
+                "{:.1} s median, up to {:.1} s",
+                latency.median_delay.as_secs_f32(),
+                latency.max_delay.as_secs_f32()
+            ))
+            .on_hover_text(
+                "How late kill messages reached the demo compared with the quickest one, which \
+                explains kill times that are a few seconds off",
+            );
+            ui.end_row();
+        });
+    });
+
+    if show {
+        response.header_response.scroll_to_me(Some(Align::TOP));
+    }
+}
+
+pub fn server_performance_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Server Performance").show(ui, |ui| {
+        if r.state.server_anomalies.is_empty() {
+            ui.label("No lag was found in the recording.");
+            return;
+        }
+
+        ui.label(
+            "Kills and times near these moments may be missing or off because the server lagged.",
+        );
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .max_scroll_height(260.)
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::remainder())
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                row.col(|ui| {
+                    ui.strong("Time");
+                });
+                row.col(|ui| {
+                    ui.strong("Anomaly");
+                });
+                row.col(|ui| {
+                    ui.strong("Duration");
+                });
+            })
+            .body(|mut body| {
+                for anomaly in &r.state.server_anomalies {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(settings::format_game_time(&anomaly.start, &r.config));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(anomaly.kind.to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(format!("{:.1} s", anomaly.duration.as_secs_f32()));
+                        });
+                    });
+                }
+            });
+    });
+}
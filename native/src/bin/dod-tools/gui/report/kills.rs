@@ -0,0 +1,355 @@
+//! Sections of a report about the kills, such as the kill feed, teamkills, and matchups.
+
+use crate::gui::report::data_quality::{AffectedSection, section_warnings_ui};
+use crate::gui::{TABLE_ROW_HEIGHT, section, team_color};
+use analysis::{Analysis, KillCause, Player, PlayerGlobalId, ScoreFormula, Team};
+use egui::{Align, Grid, Layout, ScrollArea, Ui};
+use egui_extras::{Column, TableBuilder};
+use egui_plot::{Corner, Legend, Plot, PlotPoint, PlotPoints, Points, Text};
+use native::{
+    icons,
+    settings::{self, DisplaySettings},
+};
+
+pub fn kill_feed_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Kill Feed").show(ui, |ui| {
+        section_warnings_ui(r, AffectedSection::KillFeed, ui);
+
+        let kill_feed = r.state.kill_feed(None);
+
+        let player_name = |id: &PlayerGlobalId| {
+            r.state
+                .players
+                .iter()
+                .find(|player| player.id == *id)
+                .map(|player| player.name.clone())
+                .unwrap_or_default()
+        };
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .max_scroll_height(260.)
+            .columns(Column::auto(), 4)
+            .column(Column::remainder())
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                for column in ["Time", "Killer", "Victim", "Weapon", "Note"] {
+                    row.col(|ui| {
+                        ui.strong(column);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for entry in &kill_feed {
+                    let kill = entry.kill;
+
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(settings::format_game_time(&kill.time, &r.config));
+                        });
+
+                        row.col(|ui| {
+                            if kill.cause == KillCause::World {
+                                ui.label("World");
+                            } else if let Some(killer) = &kill.killer {
+                                ui.label(player_name(killer));
+                            }
+                        });
+
+                        row.col(|ui| {
+                            ui.label(player_name(&kill.victim));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(icons::weapon_label(&kill.weapon));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(entry.notes().join(", "));
+                        });
+                    });
+                }
+            });
+    });
+}
+
+pub fn teamkills_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Team Kills").show(ui, |ui| {
+        if r.state.teamkills.is_empty() {
+            ui.label("No player killed a teammate.");
+            return;
+        }
+
+        let player_name = |id: &PlayerGlobalId| {
+            r.state
+                .players
+                .iter()
+                .find(|player| player.id == *id)
+                .map(|player| player.name.clone())
+                .unwrap_or_default()
+        };
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .max_scroll_height(260.)
+            .columns(Column::auto(), 4)
+            .column(Column::remainder())
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                for column in ["Time", "Round", "Killer", "Victim", "Weapon"] {
+                    row.col(|ui| {
+                        ui.strong(column);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for teamkill in &r.state.teamkills {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(settings::format_game_time(&teamkill.time, &r.config));
+                        });
+
+                        row.col(|ui| {
+                            if let Some((round, _)) = r.state.round_time(&teamkill.time) {
+                                ui.label(round.to_string());
+                            }
+                        });
+
+                        row.col(|ui| {
+                            ui.label(player_name(&teamkill.killer));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(player_name(&teamkill.victim));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(icons::weapon_label(&teamkill.weapon));
+                        });
+                    });
+                }
+            });
+    });
+}
+
+pub fn score_check_ui(r: &Analysis, formula: &ScoreFormula, ui: &mut Ui) {
+    section(ui, "Score Check").show(ui, |ui| {
+        let mismatches = r.state.score_mismatches(formula);
+
+        if mismatches.is_empty() {
+            ui.label("Every score matches the scoring formula.");
+            return;
+        }
+
+        let player_name = |id: &PlayerGlobalId| {
+            r.state
+                .players
+                .iter()
+                .find(|player| player.id == *id)
+                .map(|player| player.name.clone())
+                .unwrap_or_default()
+        };
+
+        ui.label("Points gained in each round that differ from what the formula gives.");
+
+        TableBuilder::new(ui)
+            .id_salt("score_check")
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .max_scroll_height(260.)
+            .columns(Column::auto(), 4)
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                for column in ["Round", "Player", "Reported", "Expected"] {
+                    row.col(|ui| {
+                        ui.strong(column);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for mismatch in &mismatches {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(mismatch.round.to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(player_name(&mismatch.player));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(format!("{:+}", mismatch.reported));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(format!("{:+}", mismatch.expected));
+                        });
+                    });
+                }
+            });
+    });
+}
+
+pub fn matchups_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Matchups").show(ui, |ui| {
+        let matchups = r.state.matchups();
+        let matrix = r.state.kill_matrix();
+
+        if matchups.is_empty() {
+            ui.label("No kills between opponents.");
+            return;
+        }
+
+        let find_matchup = |killer: &PlayerGlobalId, victim: &PlayerGlobalId| {
+            matchups
+                .iter()
+                .find(|matchup| matchup.killer == *killer && matchup.victim == *victim)
+        };
+
+        let describe = |killer: &Player, victim: &Player| {
+            let weapons = find_matchup(&killer.id, &victim.id)
+                .map(|matchup| {
+                    matchup
+                        .weapons
+                        .iter()
+                        .map(|(weapon, kills)| format!("{kills} × {weapon}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or("no kills".to_string());
+
+            format!("{} killed {}: {weapons}", killer.name, victim.name)
+        };
+
+        ui.label("Rows are killers and columns are victims. Hover a cell to see the weapons used.");
+
+        ScrollArea::horizontal().show(ui, |ui| {
+            Grid::new("matchups_grid").striped(true).show(ui, |ui| {
+                ui.label("");
+
+                for victim in &matrix.players {
+                    ui.strong(&victim.name);
+                }
+
+                ui.strong("Kills");
+                ui.end_row();
+
+                for (row, killer) in matrix.players.iter().enumerate() {
+                    ui.strong(&killer.name);
+
+                    for (column, victim) in matrix.players.iter().enumerate() {
+                        if killer.id == victim.id {
+                            ui.label("-");
+                            continue;
+                        }
+
+                        ui.label(matrix.kills[row][column].to_string())
+                            .on_hover_text(format!(
+                                "{}\n{}",
+                                describe(killer, victim),
+                                describe(victim, killer)
+                            ));
+                    }
+
+                    ui.strong(matrix.total_kills(row).to_string());
+                    ui.end_row();
+                }
+
+                ui.strong("Deaths");
+
+                for column in 0..matrix.players.len() {
+                    ui.strong(matrix.total_deaths(column).to_string());
+                }
+
+                ui.end_row();
+            });
+        });
+    });
+}
+
+/// Number of areas labeled on the danger zones map and listed below it.
+const DANGER_ZONES_SIZE: usize = 10;
+
+pub fn danger_zones_ui(r: &Analysis, display: &DisplaySettings, ui: &mut Ui) {
+    section(ui, "Danger Zones").show(ui, |ui| {
+        if r.state.death_positions.is_empty() {
+            ui.label("No death positions were recorded.");
+            return;
+        }
+
+        let zones = r.state.danger_zones();
+
+        let plot = Plot::new(ui.id().with("danger_zones_plot"))
+            .height(400.)
+            .data_aspect(1.)
+            .legend(Legend::default().position(Corner::LeftTop))
+            .custom_x_axes(vec![]) // Remove the x-axis
+            .custom_y_axes(vec![]); // Remove the y-axis
+
+        plot.show(ui, |plot_ui| {
+            for (team, name) in [(Team::Allies, "Allies deaths"), (Team::Axis, "Axis deaths")] {
+                let points = r
+                    .state
+                    .death_positions
+                    .iter()
+                    .filter(|death| death.team == team)
+                    .map(|death| [death.origin[0] as f64, death.origin[1] as f64]);
+
+                let points = Points::new(name, PlotPoints::from_iter(points))
+                    .color(team_color(display, &team))
+                    .radius(3.);
+
+                plot_ui.points(points);
+            }
+
+            for zone in zones.iter().take(DANGER_ZONES_SIZE) {
+                let [x, y] = zone.center;
+
+                plot_ui.text(Text::new(
+                    "Danger zones",
+                    PlotPoint::new(x, y),
+                    format!("{} ({})", zone.name, zone.deaths()),
+                ));
+            }
+        });
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .columns(Column::auto(), 4)
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                row.col(|ui| {
+                    ui.strong("Area");
+                });
+                row.col(|ui| {
+                    ui.strong("Allies Deaths");
+                });
+                row.col(|ui| {
+                    ui.strong("Axis Deaths");
+                });
+                row.col(|ui| {
+                    ui.strong("Total");
+                });
+            })
+            .body(|mut body| {
+                for zone in zones.iter().take(DANGER_ZONES_SIZE) {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(&zone.name);
+                        });
+
+                        row.col(|ui| {
+                            ui.label(zone.allies_deaths.to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(zone.axis_deaths.to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(zone.deaths().to_string());
+                        });
+                    });
+                }
+            });
+    });
+}
@@ -0,0 +1,655 @@
+//! Section of a report with the details of each player.
+
+use crate::gui::{PlayerHighlighting, TABLE_ROW_HEIGHT, section, seek_button_ui};
+use analysis::{Analysis, AnalysisConfig, Clock, MortalityState, Player, PlayerGlobalId};
+use egui::{Align, Grid, Layout, ScrollArea, Ui};
+use egui_extras::{Column, TableBuilder};
+use egui_plot::{Corner, Legend, Plot, PlotPoints, Points};
+use humantime::format_duration;
+use native::{FileInfo, settings};
+use std::collections::HashMap;
+use std::iter;
+use std::time::Duration;
+
+pub fn player_summaries_ui(
+    file_info: &FileInfo,
+    r: &Analysis,
+    impact_ratings: Option<&HashMap<PlayerGlobalId, f32>>,
+    player_highlighting: &PlayerHighlighting,
+    ui: &mut Ui,
+) {
+    let mut players = Vec::from_iter(&r.state.players);
+
+    players.sort_by(|l, r| l.name.cmp(&r.name));
+
+    ScrollArea::vertical()
+        .auto_shrink(false)
+        .min_scrolled_height(260.)
+        .show(ui, |ui| {
+            for p in players {
+                if !player_highlighting.highlighted.is_empty()
+                    && !player_highlighting.highlighted.contains(&p.id)
+                {
+                    continue;
+                }
+
+                section(ui, &p.name).default_open(false).show(ui, |ui| {
+                    ui.label(format!(
+                        "First bloods: {}, round enders: {}",
+                        r.state.first_bloods_by(&p.id),
+                        r.state.round_enders_by(&p.id)
+                    ));
+
+                    ui.label(format!(
+                        "Shots heard: {}, estimated accuracy: {}",
+                        p.shots.len(),
+                        p.estimated_accuracy()
+                            .map(|accuracy| format!("{:.0}%", accuracy * 100.))
+                            .unwrap_or("Unknown".to_string())
+                    ))
+                    .on_hover_text(
+                        "Weapon sounds made by the player, and the hits needed for their kills \
+                            divided by those sounds",
+                    );
+
+                    ui.label(format!(
+                        "Impact rating: {:+.2}",
+                        impact_ratings
+                            .and_then(|ratings| ratings.get(&p.id))
+                            .copied()
+                            .unwrap_or_default()
+                    ))
+                    .on_hover_text(
+                        "Round win probability added by the player's kills, minus what their \
+                            deaths cost their team",
+                    );
+
+                    weapon_breakdown_ui(p, ui);
+                    ammo_usage_ui(p, ui);
+                    weapon_timeline_ui(p, ui);
+                    kill_distances_ui(r, p, ui);
+                    kill_timing_ui(r, p, ui);
+                    positions_held_ui(p, &r.config, ui);
+                    movement_ui(p, &r.config, ui);
+                    time_after_spawn_ui(p, ui);
+                    kill_streaks_ui(file_info, p, &r.config, ui);
+                });
+            }
+        });
+}
+
+fn weapon_breakdown_ui(p: &Player, ui: &mut Ui) {
+    section(ui, "Weapon Breakdown")
+        .default_open(true)
+        .show(ui, |ui| {
+            weapon_breakdown_table_ui(p, ui);
+
+            if !p.deaths_by_weapon.is_empty() {
+                ui.separator();
+
+                deaths_by_weapon_table_ui(p, ui);
+            }
+        });
+}
+
+fn weapon_breakdown_table_ui(p: &Player, ui: &mut Ui) {
+    let categories = p.weapon_breakdown_by_category();
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .cell_layout(Layout::left_to_right(Align::Center))
+        .columns(Column::auto(), 5)
+        .header(TABLE_ROW_HEIGHT, |mut row| {
+            row.col(|ui| {
+                ui.strong("Weapon");
+            });
+            row.col(|ui| {
+                ui.strong("Kills");
+            });
+            row.col(|ui| {
+                ui.strong("% of Total");
+            });
+            row.col(|ui| {
+                ui.strong("Team Kills");
+            });
+            row.col(|ui| {
+                ui.strong("% of Total");
+            });
+        })
+        .body(|mut body| {
+            let (total_kills, total_teamkills) =
+                categories.iter().fold((0, 0), |(k_sum, tk_sum), category| {
+                    (k_sum + category.kills, tk_sum + category.teamkills)
+                });
+
+            let pct_of_total = |count: u32, total: u32| {
+                if total > 0 {
+                    ((count as f32 / total as f32) * 100.).floor()
+                } else {
+                    0.
+                }
+            };
+
+            // Each category starts with a subtotal row, followed by its weapons
+            for category in categories {
+                let rows = iter::once((
+                    category.category.to_string(),
+                    category.kills,
+                    category.teamkills,
+                    true,
+                ))
+                .chain(category.weapons.iter().map(
+                    |(weapon, kills, teamkills)| (format!("{weapon}"), *kills, *teamkills, false),
+                ));
+
+                for (name, kills, teamkills, is_subtotal) in rows {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        let cell = |ui: &mut Ui, text: String| {
+                            if is_subtotal {
+                                ui.strong(text);
+                            } else {
+                                ui.label(text);
+                            }
+                        };
+
+                        row.col(|ui| {
+                            cell(ui, name);
+                        });
+
+                        row.col(|ui| {
+                            cell(ui, format!("{kills}"));
+                        });
+
+                        row.col(|ui| {
+                            cell(ui, format!("{}%", pct_of_total(kills, total_kills)));
+                        });
+
+                        row.col(|ui| {
+                            cell(ui, format!("{teamkills}"));
+                        });
+
+                        row.col(|ui| {
+                            cell(ui, format!("{}%", pct_of_total(teamkills, total_teamkills)));
+                        });
+                    });
+                }
+            }
+        });
+}
+
+fn deaths_by_weapon_table_ui(p: &Player, ui: &mut Ui) {
+    let mut deaths_by_weapon = Vec::from_iter(&p.deaths_by_weapon);
+
+    deaths_by_weapon.sort_by(|(_, l), (_, r)| l.cmp(r).reverse());
+
+    let total_deaths = deaths_by_weapon
+        .iter()
+        .map(|(_, deaths)| **deaths)
+        .sum::<u32>();
+
+    TableBuilder::new(ui)
+        .id_salt("deaths_by_weapon")
+        .striped(true)
+        .cell_layout(Layout::left_to_right(Align::Center))
+        .columns(Column::auto(), 3)
+        .header(TABLE_ROW_HEIGHT, |mut row| {
+            row.col(|ui| {
+                ui.strong("Killed By");
+            });
+            row.col(|ui| {
+                ui.strong("Deaths");
+            });
+            row.col(|ui| {
+                ui.strong("% of Total");
+            });
+        })
+        .body(|mut body| {
+            for (weapon, deaths) in deaths_by_weapon {
+                body.row(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        ui.label(format!("{weapon}"));
+                    });
+
+                    row.col(|ui| {
+                        ui.label(format!("{deaths}"));
+                    });
+
+                    row.col(|ui| {
+                        let pct_of_total = ((*deaths as f32 / total_deaths as f32) * 100.).floor();
+
+                        ui.label(format!("{pct_of_total}%"));
+                    });
+                });
+            }
+        });
+}
+
+fn ammo_usage_ui(p: &Player, ui: &mut Ui) {
+    // Ammo is only reported to the player that recorded the demo
+    if p.ammo.is_empty() {
+        return;
+    }
+
+    section(ui, "Ammo Usage").default_open(true).show(ui, |ui| {
+        TableBuilder::new(ui)
+            .id_salt("ammo_usage")
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .columns(Column::auto(), 4)
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                for column in ["Weapon", "Shots", "Reloads", "Kills per Shot"] {
+                    row.col(|ui| {
+                        ui.strong(column);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for (weapon, usage) in &p.ammo {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(weapon.to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(usage.shots.to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(usage.reloads.to_string());
+                        });
+
+                        row.col(|ui| {
+                            if let Some(efficiency) = p.kills_per_shot(weapon) {
+                                ui.label(format!("{efficiency:.2}"));
+                            }
+                        });
+                    });
+                }
+            });
+    });
+}
+
+fn weapon_timeline_ui(p: &Player, ui: &mut Ui) {
+    // Weapons in the order they were first used, which also determines their row in the plot
+    let mut weapons = vec![];
+
+    for (_, weapon) in p.kill_streaks.iter().flat_map(|streak| streak.kills.iter()) {
+        if !weapons.contains(&weapon) {
+            weapons.push(weapon);
+        }
+    }
+
+    section(ui, "Weapon Timeline")
+        .default_open(true)
+        .show(ui, |ui| {
+            let plot = Plot::new(ui.id().with("weapon_timeline_plot"))
+                .allow_scroll(false)
+                .height(120.)
+                .width(ui.max_rect().width())
+                .legend(Legend::default().position(Corner::LeftTop))
+                .custom_x_axes(vec![]) // Remove the x-axis
+                .custom_y_axes(vec![]) // Remove the y-axis
+                .label_formatter(|weapon, point| {
+                    if !weapon.is_empty() {
+                        let duration = Duration::from_secs_f64(point.x);
+                        let duration = Duration::new(duration.as_secs(), 0);
+
+                        format!("{}\n{}", format_duration(duration), weapon)
+                    } else {
+                        String::default()
+                    }
+                });
+
+            plot.show(ui, |plot_ui| {
+                for (row, weapon) in weapons.iter().enumerate() {
+                    let points = p
+                        .kill_streaks
+                        .iter()
+                        .flat_map(|streak| streak.kills.iter())
+                        .filter(|(_, w)| w == *weapon)
+                        .map(|(time, _)| [time.viewdemo_offset.as_secs_f64(), row as f64]);
+
+                    let points =
+                        Points::new(format!("{weapon}"), PlotPoints::from_iter(points)).radius(4.);
+
+                    plot_ui.points(points);
+                }
+            });
+        });
+}
+
+fn kill_distances_ui(r: &Analysis, p: &Player, ui: &mut Ui) {
+    // Positions are only available for the player that recorded the demo
+    let weapon_ranges = r.state.weapon_ranges(&p.id);
+
+    if weapon_ranges.is_empty() {
+        return;
+    }
+
+    section(ui, "Kill Distances")
+        .default_open(true)
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .columns(Column::auto(), 4)
+                .header(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        ui.strong("Weapon");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Kills");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Avg. Distance");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Long Range");
+                    });
+                })
+                .body(|mut body| {
+                    for range in weapon_ranges {
+                        body.row(TABLE_ROW_HEIGHT, |mut row| {
+                            row.col(|ui| {
+                                ui.label(range.weapon.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(range.kills.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{:.1} m", range.avg_meters()));
+                            });
+                            row.col(|ui| {
+                                ui.label(range.long_range_kills.to_string());
+                            });
+                        });
+                    }
+                });
+
+            if let Some(longest_kill) = r.state.longest_kill(&p.id) {
+                let victim = r
+                    .state
+                    .players
+                    .iter()
+                    .find(|player| player.id == longest_kill.victim)
+                    .map(|player| player.name.as_str())
+                    .unwrap_or_default();
+
+                ui.label(format!(
+                    "Longest kill: {:.1} m with {} on {victim}",
+                    longest_kill.meters(),
+                    longest_kill.weapon
+                ));
+            }
+        });
+}
+
+fn kill_timing_ui(r: &Analysis, p: &Player, ui: &mut Ui) {
+    let weapon_timings = r.state.weapon_timings(&p.id);
+
+    if weapon_timings.is_empty() {
+        return;
+    }
+
+    section(ui, "Kill Timing")
+        .default_open(true)
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .columns(Column::auto(), 5)
+                .header(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        ui.strong("Weapon");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Kills");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Theoretical TTK")
+                            .on_hover_text("Shortest time to kill with body hits");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Fire Interval");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Fastest Follow-up")
+                            .on_hover_text("Shortest time between two kills with the weapon");
+                    });
+                })
+                .body(|mut body| {
+                    for timing in weapon_timings {
+                        body.row(TABLE_ROW_HEIGHT, |mut row| {
+                            row.col(|ui| {
+                                ui.label(timing.weapon.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(timing.kills.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{} ms", timing.time_to_kill.as_millis()));
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{} ms", timing.fire_interval.as_millis()));
+                            });
+                            row.col(|ui| {
+                                if let Some(interval) = timing.fastest_follow_up {
+                                    ui.label(format!("{} ms", interval.as_millis()));
+                                }
+                            });
+                        });
+                    }
+                });
+        });
+}
+
+fn positions_held_ui(p: &Player, config: &AnalysisConfig, ui: &mut Ui) {
+    // Positions are only available for the player that recorded the demo
+    let positions_held = p.positions_held();
+
+    if positions_held.is_empty() {
+        return;
+    }
+
+    section(ui, "Positions Held")
+        .default_open(true)
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .columns(Column::auto(), 4)
+                .header(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        ui.strong("Start Time");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Duration");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Kills");
+                    });
+                    row.col(|ui| {
+                        ui.strong("Position");
+                    });
+                })
+                .body(|mut body| {
+                    for hold in positions_held {
+                        body.row(TABLE_ROW_HEIGHT, |mut row| {
+                            row.col(|ui| {
+                                ui.label(settings::format_game_time(&hold.start_time, config));
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{}s", hold.duration().as_secs()));
+                            });
+                            row.col(|ui| {
+                                ui.label(hold.kills.to_string());
+                            });
+                            row.col(|ui| {
+                                let [x, y, z] = hold.origin;
+
+                                ui.label(format!("({x:.0}, {y:.0}, {z:.0})"));
+                            });
+                        });
+                    }
+                });
+        });
+}
+
+fn movement_ui(p: &Player, config: &AnalysisConfig, ui: &mut Ui) {
+    // Positions are only available for the player that recorded the demo
+    let movement_per_life = p.movement_per_life();
+
+    if movement_per_life.is_empty() {
+        return;
+    }
+
+    section(ui, "Movement").default_open(true).show(ui, |ui| {
+        ui.label(format!(
+            "Distance traveled: {:.0} units",
+            p.distance_traveled()
+        ));
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .max_scroll_height(260.)
+            .columns(Column::auto(), 5)
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                for column in ["Life", "Spawn Time", "Distance", "Avg. Speed", "Max. Speed"] {
+                    row.col(|ui| {
+                        ui.strong(column);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for (life, movement) in movement_per_life.iter().enumerate() {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label((life + 1).to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(settings::format_game_time(&movement.spawn_time, config));
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{:.0} units", movement.distance));
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{:.0} units/s", movement.avg_speed));
+                        });
+                        row.col(|ui| {
+                            if let Some((_, speed)) = &movement.max_speed {
+                                ui.label(format!("{speed:.0} units/s"));
+                            }
+                        });
+                    });
+                }
+            });
+    });
+}
+
+fn time_after_spawn_ui(p: &Player, ui: &mut Ui) {
+    section(ui, "Time After Spawn")
+        .default_open(true)
+        .show(ui, |ui| {
+            Grid::new(ui.id().with("time_after_spawn"))
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.strong("Min.");
+                    ui.strong("Avg.");
+                    ui.end_row();
+
+                    ui.strong("First kill");
+                    ui.label(format!("{}s", p.min_time_to_first_kill().as_secs()));
+                    ui.label(format!("{}s", p.avg_time_to_first_kill().as_secs()));
+                    ui.end_row();
+
+                    ui.strong("Death");
+                    ui.label(format!("{}s", p.min_lifespan().as_secs()));
+                    ui.label(format!("{}s", p.avg_lifespan().as_secs()));
+                    ui.end_row();
+                });
+        });
+}
+
+fn kill_streaks_ui(file_info: &FileInfo, p: &Player, config: &AnalysisConfig, ui: &mut Ui) {
+    section(ui, "Kill Streaks")
+        .default_open(true)
+        .show(ui, |ui| {
+            kill_streaks_table_ui(file_info, p, config, ui);
+        });
+}
+
+fn kill_streaks_table_ui(file_info: &FileInfo, p: &Player, config: &AnalysisConfig, ui: &mut Ui) {
+    TableBuilder::new(ui)
+        .striped(true)
+        .cell_layout(Layout::left_to_right(Align::Center))
+        .columns(Column::auto(), 5 + Clock::ALL.len())
+        .header(TABLE_ROW_HEIGHT, |mut row| {
+            row.col(|ui| {
+                ui.strong("Wave");
+            });
+            row.col(|ui| {
+                ui.strong("Total Kills");
+            });
+            // The start is shown on every clock, to line it up with the demo or a recording
+            for clock in &Clock::ALL {
+                row.col(|ui| {
+                    ui.strong(format!("Start ({clock:?})"));
+                });
+            }
+            row.col(|ui| {
+                ui.strong("Duration");
+            });
+            row.col(|ui| {
+                ui.strong("Weapons Used");
+            });
+            row.col(|ui| {
+                ui.strong("Seek");
+            });
+        })
+        .body(|mut body| {
+            for (wave, streak) in p.notable_kill_streaks(config.min_streak) {
+                if let (Some((start, _)), Some((end, _))) =
+                    (streak.kills.first(), streak.kills.last())
+                {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label((wave + 1).to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(streak.kills.len().to_string());
+                        });
+
+                        for clock in &Clock::ALL {
+                            row.col(|ui| {
+                                ui.label(settings::format_clock_time(start, clock));
+                            });
+                        }
+
+                        row.col(|ui| {
+                            let duration = Duration::new((end - start).as_secs(), 0);
+
+                            ui.label(format_duration(duration).to_string());
+                        });
+
+                        row.col(|ui| {
+                            let weapons = streak
+                                .kills
+                                .iter()
+                                .map(|(_, weapon)| format!("{weapon}"))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+
+                            ui.label(weapons);
+                        });
+
+                        row.col(|ui| {
+                            seek_button_ui(file_info, start, ui);
+                        });
+                    });
+                }
+            }
+        });
+}
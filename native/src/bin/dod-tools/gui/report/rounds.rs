@@ -0,0 +1,641 @@
+//! Sections of a report about the rounds, from the warmup to the pace of the match.
+
+use crate::gui::report::ReportAction;
+use crate::gui::report::data_quality::{AffectedSection, section_warnings_ui};
+use crate::gui::{NEUTRAL_COLOR, TABLE_ROW_HEIGHT, section, team_color};
+use analysis::{
+    Analysis, ChatMessage, DeathEconomy, MatchPace, PlayerGlobalId, Round, RoundChat, RoundPace,
+    TRADE_WINDOW, Team, rank,
+};
+use egui::{Align, Align2, Color32, Grid, Label, Layout, Ui};
+use egui_extras::{Column, TableBuilder};
+use egui_plot::{Corner, Legend, Line, Plot, PlotPoint, PlotPoints, Text, VLine};
+use humantime::format_duration;
+use native::{
+    FileInfo,
+    settings::{self, DisplaySettings},
+    timeline,
+};
+use std::time::Duration;
+
+/// Returns true if the timeline should be exported.
+pub fn team_score_timeline_ui(
+    file_info: &FileInfo,
+    r: &Analysis,
+    display: &DisplaySettings,
+    ui: &mut Ui,
+) -> Option<ReportAction> {
+    let mut action = None;
+
+    section(ui, "Timeline").default_open(true).show(ui, |ui| {
+        section_warnings_ui(r, AffectedSection::Timeline, ui);
+
+        if ui
+            .button("Export CSV")
+            .on_hover_text("Save the team scores over time as a time,team,score CSV file")
+            .clicked()
+        {
+            action = Some(ReportAction::ExportTimeline);
+        }
+
+        // Kills are plotted separately, since there are many more of them than points, but
+        // both plots pan and zoom together
+        let axes_id = ui.id().with("timeline_axes");
+
+        let plot = Plot::new("timeline_plot")
+            .allow_scroll(false)
+            .height(200.)
+            .width(ui.max_rect().width())
+            .legend(Legend::default().position(Corner::LeftTop))
+            .custom_x_axes(vec![]) // Remove the x-axis
+            .custom_y_axes(vec![]) // Remove the y-axis
+            .link_axis(axes_id, [true, false])
+            .link_cursor(axes_id, [true, false])
+            .label_formatter(timeline_label);
+
+        let swap_color = ui.visuals().warn_fg_color;
+
+        let plot_response = plot.show(ui, |plot_ui| {
+            let series = timeline::team_score_timeline(r, file_info.rosters());
+
+            let top = series
+                .iter()
+                .flat_map(|series| series.points.iter().map(|(_, score)| *score as f64))
+                .fold(0., f64::max);
+
+            for series in series {
+                let points = series
+                    .points
+                    .iter()
+                    .map(|(time, score)| [time.as_secs_f64(), *score as f64])
+                    .collect::<Vec<_>>();
+
+                let line = Line::new(series.name, PlotPoints::new(points))
+                    .color(team_color(display, &series.team));
+
+                plot_ui.line(line);
+            }
+
+            for swap in &r.state.team_swaps {
+                let x = swap.viewdemo_offset.as_secs_f64();
+
+                plot_ui.vline(VLine::new("Team swaps", x).color(swap_color));
+            }
+
+            // Mark the start of each round with its kill pace
+
+            for round in MatchPace::new(&r.state).rounds {
+                let x = round.start_time.viewdemo_offset.as_secs_f64();
+
+                plot_ui.vline(VLine::new("Rounds", x).color(Color32::GRAY));
+
+                plot_ui.text(
+                    Text::new(
+                        "Rounds",
+                        PlotPoint::new(x, top),
+                        format!("R{} ({:.1}/min)", round.number, round.kills_per_minute),
+                    )
+                    .anchor(Align2::LEFT_TOP),
+                );
+            }
+
+            plot_ui.pointer_coordinate().map(|pointer| pointer.x)
+        });
+
+        // Remember where the menu was opened, since the pointer moves onto the menu
+        let menu_time_id = ui.id().with("match_start_menu_time");
+
+        if plot_response.response.secondary_clicked()
+            && let Some(x) = plot_response.inner
+        {
+            ui.data_mut(|data| data.insert_temp(menu_time_id, x.max(0.) as u64));
+        }
+
+        plot_response.response.context_menu(|ui| {
+            let Some(secs) = ui.data(|data| data.get_temp::<u64>(menu_time_id)) else {
+                return;
+            };
+
+            let label = format!(
+                "Mark {} as match start",
+                format_duration(Duration::from_secs(secs))
+            );
+
+            if ui
+                .button(label)
+                .on_hover_text("Analyze the demo again with the match going live at this time")
+                .clicked()
+            {
+                action = Some(ReportAction::MarkMatchStart(secs));
+                ui.close();
+            }
+        });
+
+        let kill_plot = Plot::new("kill_timeline_plot")
+            .allow_scroll(false)
+            .height(120.)
+            .width(ui.max_rect().width())
+            .legend(Legend::default().position(Corner::LeftTop))
+            .custom_x_axes(vec![])
+            .custom_y_axes(vec![])
+            .link_axis(axes_id, [true, false])
+            .link_cursor(axes_id, [true, false])
+            .label_formatter(timeline_label);
+
+        kill_plot.show(ui, |plot_ui| {
+            for series in timeline::team_kill_timeline(r, file_info.rosters()) {
+                let points = series
+                    .points
+                    .iter()
+                    .map(|(time, kills)| [time.as_secs_f64(), *kills as f64])
+                    .collect::<Vec<_>>();
+
+                let line = Line::new(format!("{} kills", series.name), PlotPoints::new(points))
+                    .color(team_color(display, &series.team));
+
+                plot_ui.line(line);
+            }
+
+            for swap in &r.state.team_swaps {
+                let x = swap.viewdemo_offset.as_secs_f64();
+
+                plot_ui.vline(VLine::new("Team swaps", x).color(swap_color));
+            }
+        });
+    });
+
+    action
+}
+
+/// Labels a point of a timeline plot with its time and value.
+fn timeline_label(name: &str, point: &PlotPoint) -> String {
+    if name.is_empty() {
+        return String::default();
+    }
+
+    let duration = Duration::from_secs_f64(point.x);
+    let duration = Duration::new(duration.as_secs(), 0);
+
+    format!("{}\n{}: {}", format_duration(duration), name, point.y)
+}
+
+pub fn rounds_ui(r: &Analysis, display: &DisplaySettings, ui: &mut Ui) {
+    section(ui, "Rounds").show(ui, |ui| {
+        section_warnings_ui(r, AffectedSection::Rounds, ui);
+
+        let show_chat = r.config.round_chat;
+        let show_time_left = r.state.is_time_left_known();
+
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .columns(Column::auto(), 6);
+
+        if show_time_left {
+            table = table.column(Column::auto());
+        }
+
+        if show_chat {
+            table = table.column(Column::remainder());
+        }
+
+        table
+            .header(TABLE_ROW_HEIGHT, |mut ui| {
+                ui.col(|ui| {
+                    ui.add_space(ui.style().spacing.indent);
+                });
+                ui.col(|ui| {
+                    ui.strong("#");
+                });
+                ui.col(|ui| {
+                    ui.strong("Start Time");
+                });
+                ui.col(|ui| {
+                    ui.strong("Duration");
+                });
+                ui.col(|ui| {
+                    ui.strong("Winner");
+                });
+                ui.col(|ui| {
+                    ui.strong("Kills by Winner");
+                });
+
+                if show_time_left {
+                    ui.col(|ui| {
+                        ui.strong("Time Left").on_hover_text(
+                            "Time left on the map when the round ended, counted from the closest \
+                            time a client asked for it",
+                        );
+                    });
+                }
+
+                if show_chat {
+                    ui.col(|ui| {
+                        ui.strong("Chat");
+                    });
+                }
+            })
+            .body(|mut ui| {
+                let mut match_duration = Duration::default();
+
+                for (i, round) in r.state.rounds.iter().enumerate() {
+                    if let Round::Completed {
+                        start_time,
+                        end_time,
+                        winner_stats,
+                        ..
+                    } = round
+                    {
+                        match_duration += end_time - start_time;
+
+                        ui.row(TABLE_ROW_HEIGHT, |mut row| {
+                            row.col(|ui| {
+                                ui.painter().rect_filled(
+                                    ui.max_rect(),
+                                    0.0,
+                                    match winner_stats {
+                                        Some((team, _)) => team_color(display, team),
+                                        None => NEUTRAL_COLOR,
+                                    },
+                                );
+                            });
+
+                            row.col(|ui| {
+                                ui.label((i + 1).to_string());
+                            });
+
+                            row.col(|ui| {
+                                let start_time = Duration::from_millis(
+                                    start_time.viewdemo_offset.as_millis() as u64,
+                                );
+
+                                ui.label(format_duration(start_time).to_string());
+                            });
+
+                            row.col(|ui| {
+                                let duration = Duration::from_millis(
+                                    (end_time - start_time).as_millis() as u64,
+                                );
+
+                                ui.label(format_duration(duration).to_string());
+                            });
+
+                            if let Some((winner, kills)) = winner_stats {
+                                row.col(|ui| {
+                                    ui.label(if matches!(winner, Team::Allies) {
+                                        "Allies"
+                                    } else {
+                                        "Axis"
+                                    });
+                                });
+
+                                row.col(|ui| {
+                                    ui.label(kills.to_string());
+                                });
+                            } else {
+                                row.col(|_ui| {});
+                                row.col(|_ui| {});
+                            }
+
+                            if show_time_left {
+                                row.col(|ui| {
+                                    if let Some(time_left) = r.state.time_left_at_round_end(round) {
+                                        let time_left = Duration::new(time_left.as_secs(), 0);
+
+                                        ui.label(format_duration(time_left).to_string());
+                                    }
+                                });
+                            }
+
+                            if show_chat {
+                                row.col(|ui| {
+                                    if let Some(chat) = r.state.round_chat(i) {
+                                        round_chat_ui(r, &chat, ui);
+                                    }
+                                });
+                            }
+                        });
+                    }
+                }
+
+                ui.row(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|_| {});
+                    row.col(|_| {});
+                    row.col(|ui| {
+                        ui.label(format_duration(match_duration).to_string());
+                    });
+                    row.col(|_| {});
+                });
+            });
+
+        round_breakdown_ui(r, ui);
+    });
+}
+
+/// Shows the scoreboard gains of each player in every completed round, one expander per round.
+fn round_breakdown_ui(r: &Analysis, ui: &mut Ui) {
+    let player_name = |id: &PlayerGlobalId| {
+        r.state
+            .players
+            .iter()
+            .find(|player| player.id == *id)
+            .map(|player| player.name.clone())
+            .unwrap_or_default()
+    };
+
+    for (i, round) in r.state.rounds.iter().enumerate() {
+        if !matches!(round, Round::Completed { .. }) || round.player_stats().is_empty() {
+            continue;
+        }
+
+        let mut player_stats = Vec::from_iter(round.player_stats());
+
+        player_stats
+            .sort_by(|l, r| rank(&(l.score, l.kills, l.deaths), &(r.score, r.kills, r.deaths)));
+
+        section(ui, format!("Round {} breakdown", i + 1))
+            .id_salt(("round_breakdown", i))
+            .default_open(false)
+            .show(ui, |ui| {
+                TableBuilder::new(ui)
+                    .id_salt(("round_breakdown_table", i))
+                    .striped(true)
+                    .cell_layout(Layout::left_to_right(Align::Center))
+                    .columns(Column::auto(), 5)
+                    .header(TABLE_ROW_HEIGHT, |mut row| {
+                        for column in ["Player", "Score", "Kills", "Deaths", "Objective"] {
+                            row.col(|ui| {
+                                ui.strong(column);
+                            });
+                        }
+                    })
+                    .body(|mut body| {
+                        for stats in player_stats {
+                            body.row(TABLE_ROW_HEIGHT, |mut row| {
+                                row.col(|ui| {
+                                    ui.label(player_name(&stats.player));
+                                });
+
+                                for value in [
+                                    stats.score,
+                                    stats.kills,
+                                    stats.deaths,
+                                    stats.objective_score,
+                                ] {
+                                    row.col(|ui| {
+                                        ui.label(value.to_string());
+                                    });
+                                }
+                            });
+                        }
+                    });
+            });
+    }
+}
+
+/// Shows the last message of the round, with every message around its end on hover.
+fn round_chat_ui(r: &Analysis, chat: &RoundChat, ui: &mut Ui) {
+    let Some(last) = chat.before.last().or(chat.after.first()) else {
+        return;
+    };
+
+    let line = |message: &ChatMessage| {
+        let sender = message
+            .sender
+            .as_ref()
+            .and_then(|id| r.state.players.iter().find(|player| player.id == *id))
+            .map(|player| player.name.as_str())
+            .unwrap_or_default();
+
+        format!("{sender}: {}", message.text)
+    };
+
+    ui.add(Label::new(line(last)).truncate()).on_hover_ui(|ui| {
+        for message in &chat.before {
+            ui.label(line(message));
+        }
+
+        if !chat.after.is_empty() {
+            ui.separator();
+            ui.weak("After the round");
+
+            for message in &chat.after {
+                ui.label(line(message));
+            }
+        }
+    });
+}
+
+pub fn match_pace_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Match Pace").show(ui, |ui| {
+        let pace = MatchPace::new(&r.state);
+
+        let round_label = |round: &RoundPace| {
+            format!(
+                "Round {} at {} ({})",
+                round.number,
+                settings::format_game_time(&round.start_time, &r.config),
+                format_duration(Duration::new(round.duration.as_secs(), 0)),
+            )
+        };
+
+        Grid::new("match_pace_grid").show(ui, |ui| {
+            ui.label("Average round");
+            ui.label(format_duration(Duration::new(pace.avg_duration.as_secs(), 0)).to_string());
+            ui.end_row();
+
+            ui.label("Median round");
+            ui.label(format_duration(Duration::new(pace.median_duration.as_secs(), 0)).to_string());
+            ui.end_row();
+
+            if let Some(round) = pace.fastest() {
+                ui.label("Fastest round");
+                ui.label(round_label(round));
+                ui.end_row();
+            }
+
+            if let Some(round) = pace.slowest() {
+                ui.label("Slowest round");
+                ui.label(round_label(round));
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(ui.style().spacing.item_spacing.y);
+
+        let table = TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .columns(Column::auto(), 4);
+
+        table
+            .header(TABLE_ROW_HEIGHT, |mut ui| {
+                ui.col(|ui| {
+                    ui.strong("#");
+                });
+                ui.col(|ui| {
+                    ui.strong("Duration");
+                });
+                ui.col(|ui| {
+                    ui.strong("Kills");
+                });
+                ui.col(|ui| {
+                    ui.strong("Kills/min");
+                });
+            })
+            .body(|mut ui| {
+                for round in &pace.rounds {
+                    ui.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(round.number.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(
+                                format_duration(Duration::new(round.duration.as_secs(), 0))
+                                    .to_string(),
+                            );
+                        });
+                        row.col(|ui| {
+                            ui.label(round.kills.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{:.1}", round.kills_per_minute));
+                        });
+                    });
+                }
+            });
+    });
+}
+
+pub fn death_economy_ui(r: &Analysis, display: &DisplaySettings, ui: &mut Ui) {
+    section(ui, "Economy of Deaths").show(ui, |ui| {
+        let columns = [
+            "Team",
+            "Deaths",
+            "Flags Captured",
+            "Deaths per Flag",
+            "Traded Deaths",
+            "Trade Kills",
+        ];
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .columns(Column::auto(), columns.len())
+            .header(TABLE_ROW_HEIGHT, |mut header| {
+                for column in columns {
+                    header.col(|ui| {
+                        ui.strong(column);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for team in [Team::Allies, Team::Axis] {
+                    let economy = DeathEconomy::new(&r.state, team);
+
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.colored_label(
+                                team_color(display, &economy.team),
+                                format!("{:?}", economy.team),
+                            );
+                        });
+                        row.col(|ui| {
+                            ui.label(economy.deaths.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(economy.captures.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(
+                                economy
+                                    .deaths_per_capture()
+                                    .map(|deaths| format!("{deaths:.1}"))
+                                    .unwrap_or("-".to_string()),
+                            );
+                        });
+                        row.col(|ui| {
+                            ui.label(format!(
+                                "{} ({:.0}%)",
+                                economy.traded_deaths,
+                                economy.trade_rate() * 100.
+                            ))
+                            .on_hover_text(format!(
+                                "Deaths a teammate answered with a kill within {} s",
+                                TRADE_WINDOW.as_secs()
+                            ));
+                        });
+                        row.col(|ui| {
+                            ui.label(economy.trade_kills.to_string())
+                                .on_hover_text(format!(
+                                    "Kills made within {} s of a teammate's death",
+                                    TRADE_WINDOW.as_secs()
+                                ));
+                        });
+                    });
+                }
+            });
+    });
+}
+
+pub fn warmup_ui(r: &Analysis, ui: &mut Ui) {
+    section(ui, "Warmup").show(ui, |ui| {
+        let Some(warmup) = r.state.warmup_stats() else {
+            ui.label("The match going live was not detected, so every result is shown.");
+            return;
+        };
+
+        ui.label(format!(
+            "Rounds: {}, captures: {}",
+            warmup.rounds.len(),
+            warmup.captures.len()
+        ));
+
+        let players = r
+            .state
+            .players
+            .iter()
+            .map(|player| {
+                let (kills, deaths) = (warmup.kills_by(&player.id), warmup.deaths_of(&player.id));
+
+                (player, kills, deaths)
+            })
+            .filter(|(_, kills, deaths)| *kills > 0 || *deaths > 0)
+            .collect::<Vec<_>>();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::remainder())
+            .header(TABLE_ROW_HEIGHT, |mut row| {
+                row.col(|ui| {
+                    ui.strong("Name");
+                });
+                row.col(|ui| {
+                    ui.strong("Kills");
+                });
+                row.col(|ui| {
+                    ui.strong("Deaths");
+                });
+            })
+            .body(|mut body| {
+                for (player, kills, deaths) in players {
+                    body.row(TABLE_ROW_HEIGHT, |mut row| {
+                        row.col(|ui| {
+                            ui.label(&player.name);
+                        });
+
+                        row.col(|ui| {
+                            ui.label(kills.to_string());
+                        });
+
+                        row.col(|ui| {
+                            ui.label(deaths.to_string());
+                        });
+                    });
+                }
+            });
+    });
+}
@@ -0,0 +1,309 @@
+//! Scoreboard section of a report.
+
+use crate::gui::report::ReportAction;
+use crate::gui::report::data_quality::{AffectedSection, section_warnings_ui};
+use crate::gui::{PlayerHighlighting, TABLE_ROW_HEIGHT, section};
+use analysis::{
+    ASSIST_WINDOW, Analysis, Clan, Consistency, KillParticipation, MortalityState, Player, SteamId,
+    Team,
+};
+use egui::{Align, ComboBox, Label, Layout, Ui, WidgetInfo, WidgetType};
+use egui_extras::{Column, TableBody, TableBuilder};
+
+pub fn scoreboard_ui(
+    r: &Analysis,
+    player_highlighting: &mut PlayerHighlighting,
+    ui: &mut Ui,
+) -> Option<ReportAction> {
+    let mut action = None;
+
+    let (allies_score, axis_score) = (
+        r.state.team_scores.get_team_score(Team::Allies),
+        r.state.team_scores.get_team_score(Team::Axis),
+    );
+
+    let (allies, axis) = (
+        Clan::new(&r.state, Team::Allies, &[]),
+        Clan::new(&r.state, Team::Axis, &[]),
+    );
+
+    let match_result_fragment = format!(
+        ": {} ({}) {} {} ({})",
+        allies.name(),
+        allies_score,
+        if allies_score > axis_score { ">" } else { "<" },
+        axis.name(),
+        axis_score
+    );
+
+    section(ui, format!("Scoreboard: {match_result_fragment}"))
+        .default_open(true)
+        .show(ui, |ui| {
+            section_warnings_ui(r, AffectedSection::Scoreboard, ui);
+
+            let history = &r.state.scoreboard_history;
+            let snapshot_id = ui.id().with("scoreboard_snapshot");
+            let mut selected_round = ui
+                .data(|data| data.get_temp::<Option<usize>>(snapshot_id))
+                .flatten();
+
+            if !history.is_empty() {
+                ComboBox::from_id_salt(snapshot_id)
+                    .selected_text(match selected_round {
+                        Some(round) => format!("After round {round}"),
+                        None => "Final".to_string(),
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut selected_round, None, "Final");
+
+                        for snapshot in history {
+                            ui.selectable_value(
+                                &mut selected_round,
+                                Some(snapshot.round),
+                                format!("After round {}", snapshot.round),
+                            );
+                        }
+                    });
+
+                ui.data_mut(|data| data.insert_temp(snapshot_id, selected_round));
+            }
+
+            let snapshot = selected_round
+                .and_then(|round| history.iter().find(|snapshot| snapshot.round == round));
+
+            // Players checked in other reports count too, so IDs from different demos can be merged
+            if player_highlighting.highlighted.len() > 1
+                && ui
+                    .button("Same person")
+                    .on_hover_text(
+                        "Treat the checked players as the same person in this and future analyses",
+                    )
+                    .clicked()
+            {
+                let ids = player_highlighting.highlighted.drain().collect();
+
+                action = Some(ReportAction::MergeIdentities(ids));
+            }
+
+            let mut columns = vec![
+                "", "ID", "Name", "Team", "Class", "Score", "Kills", "Deaths",
+            ];
+
+            if r.config.infer_assists {
+                columns.push("Assists*");
+            }
+
+            columns.extend([
+                "Env. Deaths",
+                "Suicides",
+                "Flags",
+                "Caps/Obj Points",
+                "Rounds",
+                "Avg. Life",
+                "Min. Life",
+                "Max. Life",
+                "Consistency",
+                "Kill Part.",
+            ]);
+
+            let table = TableBuilder::new(ui)
+                .striped(true)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .max_scroll_height(260.)
+                .column(Column::auto())
+                .column(Column::auto_with_initial_suggestion(150.))
+                .columns(Column::auto(), columns.len());
+
+            table
+                .header(TABLE_ROW_HEIGHT, |mut header| {
+                    for column in columns {
+                        header.col(|ui| {
+                            ui.strong(column);
+                        });
+                    }
+                })
+                .body(|ref mut body| {
+                    let players = Vec::from_iter(&r.state.players);
+
+                    for p in players {
+                        let stats = match snapshot {
+                            Some(snapshot) => snapshot.get_player_stats(&p.id).unwrap_or_default(),
+                            None => p.stats,
+                        };
+
+                        scoreboard_row_ui(r, p, stats, player_highlighting, body);
+                    }
+                });
+        });
+
+    action
+}
+
+fn scoreboard_row_ui(
+    r: &Analysis,
+    p: &Player,
+    stats: (i32, i32, i32),
+    player_highlighting: &mut PlayerHighlighting,
+    body: &mut TableBody,
+) {
+    let row_label = |ui: &mut Ui, str: &str| {
+        ui.add(Label::new(str).extend());
+    };
+
+    body.row(TABLE_ROW_HEIGHT, |mut row| {
+        let mut is_checked = player_highlighting.highlighted.contains(&p.id);
+
+        row.set_selected(is_checked);
+
+        row.col(|ui| {
+            let response = ui
+                .checkbox(&mut is_checked, "")
+                .on_hover_text("Highlight the player in the report");
+
+            // The checkbox has no visible label, so name the player for screen readers instead
+            response.widget_info(|| {
+                WidgetInfo::selected(
+                    WidgetType::Checkbox,
+                    ui.is_enabled(),
+                    is_checked,
+                    format!("Highlight {}", p.name),
+                )
+            });
+
+            if response.changed() {
+                if is_checked {
+                    player_highlighting.highlighted.insert(p.id.clone());
+                } else {
+                    player_highlighting.highlighted.remove(&p.id);
+                }
+            }
+        });
+
+        row.col(|ui| {
+            let response = match SteamId::try_from(&p.id) {
+                Ok(steam_id) => {
+                    let link_text = steam_id.to_string();
+                    let link_url = format!("https://steamcommunity.com/profiles/{}", p.id);
+
+                    ui.hyperlink_to(link_text, link_url)
+                }
+                _ => ui.label(p.id.to_string()),
+            };
+
+            let aliases = r.config.identities.aliases(&p.id);
+
+            if !aliases.is_empty() {
+                let aliases = aliases.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+                response.on_hover_text(format!("Also seen as {}", aliases.join(", ")));
+            }
+        });
+
+        row.col(|ui| {
+            row_label(ui, &p.name);
+        });
+
+        row.col(|ui| {
+            ui.label(match &p.team {
+                None => "Unknown",
+                Some(Team::Allies) => "Allies",
+                Some(Team::Axis) => "Axis",
+                Some(Team::Spectators) => "Spectators",
+            });
+        });
+
+        row.col(|ui| {
+            ui.label(match &p.class {
+                None => "Unknown".to_string(),
+                Some(x) => format!("{x:?}"),
+            });
+        });
+
+        row.col(|ui| {
+            ui.label(stats.0.to_string());
+        });
+
+        row.col(|ui| {
+            ui.label(stats.1.to_string());
+        });
+
+        row.col(|ui| {
+            ui.label(stats.2.to_string());
+        });
+
+        if r.config.infer_assists {
+            row.col(|ui| {
+                ui.label(r.state.assists_by(&p.id).to_string())
+                    .on_hover_text(format!(
+                        "Probable assists: a teammate of the killer was heard firing, and blood \
+                        was seen near the victim, in the {} s before the kill",
+                        ASSIST_WINDOW.as_secs()
+                    ));
+            });
+        }
+
+        row.col(|ui| {
+            ui.label(r.state.environment_deaths(&p.id).to_string())
+                .on_hover_text("Deaths caused by the map, such as falls, instead of a player");
+        });
+
+        row.col(|ui| {
+            ui.label(r.state.suicides(&p.id).to_string())
+                .on_hover_text("Deaths by the player's own weapon, such as their grenade");
+        });
+
+        row.col(|ui| {
+            ui.label(r.state.captures_by(&p.id).to_string());
+        });
+
+        row.col(|ui| {
+            ui.label(p.objective_score.to_string())
+                .on_hover_text("Points from capturing flags and other objectives");
+        });
+
+        row.col(|ui| {
+            ui.label(p.rounds_played(&r.state.rounds).to_string());
+        });
+
+        row.col(|ui| {
+            ui.label(format!("{}s", p.avg_lifespan().as_secs()));
+        });
+
+        row.col(|ui| {
+            ui.label(format!("{}s", p.min_lifespan().as_secs()));
+        });
+
+        row.col(|ui| {
+            ui.label(format!("{}s", p.max_lifespan().as_secs()));
+        });
+
+        row.col(|ui| {
+            let consistency = Consistency::new(p, &r.state.rounds);
+
+            ui.label(format!("{:.2}", consistency.index))
+                .on_hover_text(format!(
+                    "{}\nKills per round played: {:?}",
+                    if consistency.is_steady() {
+                        "Steady"
+                    } else {
+                        "Streaky"
+                    },
+                    consistency.kills_per_round
+                ));
+        });
+
+        row.col(|ui| {
+            let participation = KillParticipation::new(p, &r.state);
+
+            ui.label(format!("{:.0}%", participation.average * 100.))
+                .on_hover_text(format!(
+                    "Average share of the team's kills per round: {:?}",
+                    participation
+                        .per_round
+                        .iter()
+                        .map(|share| format!("{:.0}%", share * 100.))
+                        .collect::<Vec<_>>()
+                ));
+        });
+    });
+}
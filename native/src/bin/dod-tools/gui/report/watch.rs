@@ -0,0 +1,143 @@
+//! Sections of a report about the players to look into, such as late joins.
+
+use crate::gui::{TABLE_ROW_HEIGHT, section, seek_button_ui};
+use analysis::{Analysis, Player, Team};
+use egui::{Align, Grid, Layout, Ui};
+use egui_extras::{Column, TableBuilder};
+use native::{FileInfo, index::RosterNewcomer, settings};
+
+pub fn players_to_watch_ui(file_info: &FileInfo, r: &Analysis, ui: &mut Ui) {
+    section(ui, "Players to Watch").show(ui, |ui| {
+        let players_to_watch = r.state.players_to_watch();
+
+        if players_to_watch.is_empty() {
+            ui.label("No player had a highlight.");
+            return;
+        }
+
+        for (rank, watch) in players_to_watch.iter().enumerate() {
+            section(
+                ui,
+                format!(
+                    "{}. {} ({} points)",
+                    rank + 1,
+                    watch.player.name,
+                    watch.score
+                ),
+            )
+            .id_salt(("players_to_watch", &watch.player.id))
+            .show(ui, |ui| {
+                Grid::new(("players_to_watch_grid", &watch.player.id)).show(ui, |ui| {
+                    for highlight in &watch.highlights {
+                        ui.label(settings::format_game_time(&highlight.time, &r.config));
+                        ui.label(highlight.kind.to_string());
+                        seek_button_ui(file_info, &highlight.time, ui);
+                        ui.end_row();
+                    }
+                });
+            });
+        }
+    });
+}
+
+pub fn late_joins_ui(file_info: &FileInfo, r: &Analysis, ui: &mut Ui) {
+    section(ui, "Late Joins").show(ui, |ui| {
+        let late_joins = r.state.late_joins();
+
+        if late_joins.is_empty() {
+            ui.label("No players joined after the match went live.");
+        } else {
+            late_joins_table_ui(r, &late_joins, ui);
+        }
+
+        if !file_info.roster_newcomers.is_empty() {
+            ui.add_space(8.);
+            ui.label("New to their clan's roster in the indexed demos:");
+
+            roster_newcomers_table_ui(&file_info.roster_newcomers, ui);
+        }
+    });
+}
+
+fn late_joins_table_ui(r: &Analysis, late_joins: &[&Player], ui: &mut Ui) {
+    TableBuilder::new(ui)
+        .striped(true)
+        .cell_layout(Layout::left_to_right(Align::Center))
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::remainder())
+        .header(TABLE_ROW_HEIGHT, |mut row| {
+            row.col(|ui| {
+                ui.strong("Connected");
+            });
+            row.col(|ui| {
+                ui.strong("Name");
+            });
+            row.col(|ui| {
+                ui.strong("Team");
+            });
+        })
+        .body(|mut body| {
+            for player in late_joins {
+                body.row(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        ui.label(settings::format_game_time(
+                            &player.first_connected,
+                            &r.config,
+                        ));
+                    });
+
+                    row.col(|ui| {
+                        ui.label(&player.name);
+                    });
+
+                    row.col(|ui| {
+                        ui.label(match &player.team {
+                            None => "Unknown",
+                            Some(Team::Allies) => "Allies",
+                            Some(Team::Axis) => "Axis",
+                            Some(Team::Spectators) => "Spectators",
+                        });
+                    });
+                });
+            }
+        });
+}
+
+fn roster_newcomers_table_ui(newcomers: &[RosterNewcomer], ui: &mut Ui) {
+    TableBuilder::new(ui)
+        .id_salt("roster_newcomers")
+        .striped(true)
+        .cell_layout(Layout::left_to_right(Align::Center))
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::remainder())
+        .header(TABLE_ROW_HEIGHT, |mut row| {
+            row.col(|ui| {
+                ui.strong("Name");
+            });
+            row.col(|ui| {
+                ui.strong("Clan");
+            });
+            row.col(|ui| {
+                ui.strong("Other clans");
+            });
+        })
+        .body(|mut body| {
+            for newcomer in newcomers {
+                body.row(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        ui.label(&newcomer.name).on_hover_text(&newcomer.id);
+                    });
+
+                    row.col(|ui| {
+                        ui.label(&newcomer.clan);
+                    });
+
+                    row.col(|ui| {
+                        ui.label(newcomer.other_clans.join(", "));
+                    });
+                });
+            }
+        });
+}
@@ -0,0 +1,202 @@
+//! Window that edits the analysis settings.
+
+use analysis::{AnalysisConfig, Clock, Codepage, ScoreFormula, TeamkillPolicy};
+use egui::{ComboBox, Context, DragValue, Grid, Window};
+use native::settings::DisplaySettings;
+
+/// Edits a copy of the [AnalysisConfig] until it is saved.
+pub struct SettingsWindow {
+    pub config: AnalysisConfig,
+    pub display: DisplaySettings,
+    pub error: Option<String>,
+}
+
+pub enum SettingsAction {
+    Save,
+    Close,
+}
+
+impl SettingsWindow {
+    pub fn show(&mut self, ctx: &Context) -> Option<SettingsAction> {
+        let mut action = None;
+        let mut is_open = true;
+
+        Window::new("Analysis settings")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                Grid::new("settings_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Teamkills");
+                    ComboBox::from_id_salt("teamkill_policy")
+                        .selected_text(format!("{:?}", self.config.teamkill_policy))
+                        .show_ui(ui, |ui| {
+                            for (policy, description) in [
+                                (TeamkillPolicy::Exclude, "Left out of streaks and rounds"),
+                                (TeamkillPolicy::Penalize, "Left out, and end the streak"),
+                                (TeamkillPolicy::Include, "Counted like any other kill"),
+                            ] {
+                                let text = format!("{policy:?}");
+
+                                ui.selectable_value(&mut self.config.teamkill_policy, policy, text)
+                                    .on_hover_text(description);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Clan match detection");
+                    ui.checkbox(&mut self.config.clan_match_detection, "Detect match start")
+                        .on_hover_text(
+                            "Reset the results when a clan match goes live; turn off for public \
+                            server demos to treat the whole demo as one session",
+                        );
+                    ui.end_row();
+
+                    ui.label("Clan match detection window");
+                    ui.add_enabled(
+                        self.config.clan_match_detection,
+                        DragValue::new(&mut self.config.clan_match_window_secs)
+                            .range(1..=120)
+                            .suffix(" s"),
+                    )
+                    .on_hover_text("Time allowed between a round reset and the match going live");
+                    ui.end_row();
+
+                    ui.label("Minimum kill streak");
+                    ui.add(DragValue::new(&mut self.config.min_streak).range(1..=usize::MAX));
+                    ui.end_row();
+
+                    ui.label("Team scores");
+                    ui.checkbox(&mut self.config.scores_by_clan, "Follow clans across swaps")
+                        .on_hover_text("Show scores per clan instead of per side in the timeline");
+                    ui.end_row();
+
+                    ui.label("Assists");
+                    ui.checkbox(&mut self.config.infer_assists, "Infer probable assists")
+                        .on_hover_text(
+                            "Credit teammates heard firing at a victim shortly before the kill; \
+                            an estimate, since the game does not report assists",
+                        );
+                    ui.end_row();
+
+                    ui.label("Rounds");
+                    ui.checkbox(&mut self.config.round_chat, "Show round chat")
+                        .on_hover_text("Show what players said around the end of each round");
+                    ui.end_row();
+
+                    ui.label("Fallback encoding");
+                    ComboBox::from_id_salt("codepage")
+                        .selected_text(format!("{:?}", self.config.codepage))
+                        .show_ui(ui, |ui| {
+                            for (codepage, description) in [
+                                (
+                                    Codepage::Windows1252,
+                                    "Used by most Western European clients",
+                                ),
+                                (Codepage::Latin1, "ISO-8859-1"),
+                            ] {
+                                let text = format!("{codepage:?}");
+
+                                ui.selectable_value(&mut self.config.codepage, codepage, text)
+                                    .on_hover_text(description);
+                            }
+                        })
+                        .response
+                        .on_hover_text("Encoding of player names and chat that are not UTF-8");
+                    ui.end_row();
+
+                    let mut check_scores = self.config.score_formula.is_some();
+
+                    ui.label("Score check");
+                    if ui
+                        .checkbox(&mut check_scores, "Check against the league's formula")
+                        .on_hover_text(
+                            "Flag the rounds in which a player's score changed by a different \
+                            amount than the formula gives, e.g. on servers with scoring plugins",
+                        )
+                        .changed()
+                    {
+                        self.config.score_formula = check_scores.then(ScoreFormula::default);
+                    }
+                    ui.end_row();
+
+                    if let Some(formula) = &mut self.config.score_formula {
+                        for (label, points) in [
+                            ("Points per capture", &mut formula.points_per_capture),
+                            ("Points per kill", &mut formula.points_per_kill),
+                            ("Points per teamkill", &mut formula.points_per_teamkill),
+                            ("Points per suicide", &mut formula.points_per_suicide),
+                        ] {
+                            ui.label(label);
+                            ui.add(DragValue::new(points).range(-100..=100));
+                            ui.end_row();
+                        }
+                    }
+
+                    ui.label("UTC offset");
+                    ui.add(
+                        DragValue::new(&mut self.display.utc_offset_minutes)
+                            .range(-12 * 60..=14 * 60)
+                            .speed(15)
+                            .suffix(" min"),
+                    )
+                    .on_hover_text("Offset used to display dates and times");
+                    ui.end_row();
+
+                    ui.label("Times in reports");
+                    ComboBox::from_id_salt("clock")
+                        .selected_text(format!("{:?}", self.config.clock))
+                        .show_ui(ui, |ui| {
+                            for (clock, description) in [
+                                (Clock::Viewdemo, "Time shown by viewdemo"),
+                                (Clock::Recording, "Time since the demo started recording"),
+                                (
+                                    Clock::Server,
+                                    "Time stamped on each frame by the server, which starts over \
+                                    with each playback segment",
+                                ),
+                            ] {
+                                let text = format!("{clock:?}");
+
+                                ui.selectable_value(&mut self.config.clock, clock, text)
+                                    .on_hover_text(description);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Allies color");
+                    ui.color_edit_button_srgb(&mut self.display.allies_color);
+                    ui.end_row();
+
+                    ui.label("Axis color");
+                    ui.color_edit_button_srgb(&mut self.display.axis_color);
+                    ui.end_row();
+                });
+
+                ui.separator();
+
+                ui.label("Settings apply to demos opened afterwards.");
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        action = Some(SettingsAction::Save);
+                    }
+
+                    if ui.button("Reset to defaults").clicked() {
+                        self.config = AnalysisConfig::default();
+                        self.display = DisplaySettings::default();
+                    }
+                });
+            });
+
+        if !is_open {
+            action = Some(SettingsAction::Close);
+        }
+
+        action
+    }
+}
@@ -0,0 +1,111 @@
+//! Window that ranks the longest kill streaks across the open reports.
+
+use crate::gui::TABLE_ROW_HEIGHT;
+use analysis::Analysis;
+use egui::{Align, Layout, Ui};
+use egui_extras::{Column, TableBuilder};
+use native::{FileInfo, settings};
+use std::collections::HashMap;
+
+/// Number of streaks shown in the streak leaderboard.
+const STREAK_LEADERBOARD_SIZE: usize = 25;
+
+/// Lists the longest kill streaks across all reports, returning the path of a report to jump to.
+pub fn streak_leaderboard_ui(
+    analyses: &[(FileInfo, Analysis)],
+    report_titles: &HashMap<String, String>,
+    ui: &mut Ui,
+) -> Option<String> {
+    let mut streaks = analyses
+        .iter()
+        .flat_map(|(file_info, analysis)| {
+            analysis.state.players.iter().flat_map(move |player| {
+                player
+                    .notable_kill_streaks(analysis.config.min_streak)
+                    .filter_map(move |(wave, streak)| {
+                        let (start, _) = streak.kills.first()?;
+
+                        Some((
+                            file_info,
+                            &analysis.config,
+                            player,
+                            wave,
+                            streak.kills.len(),
+                            start,
+                        ))
+                    })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    streaks.sort_by(|(.., l_kills, l_start), (.., r_kills, r_start)| {
+        l_kills
+            .cmp(r_kills)
+            .reverse()
+            .then(l_start.viewdemo_offset.cmp(&r_start.viewdemo_offset))
+    });
+
+    let mut jump_to = None;
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .cell_layout(Layout::left_to_right(Align::Center))
+        .columns(Column::auto(), 6)
+        .header(TABLE_ROW_HEIGHT, |mut row| {
+            row.col(|ui| {
+                ui.strong("Demo");
+            });
+            row.col(|ui| {
+                ui.strong("Player");
+            });
+            row.col(|ui| {
+                ui.strong("Wave");
+            });
+            row.col(|ui| {
+                ui.strong("Kills");
+            });
+            row.col(|ui| {
+                ui.strong("Start Time");
+            });
+            row.col(|_| {});
+        })
+        .body(|mut body| {
+            for (file_info, config, player, wave, kills, start) in
+                streaks.into_iter().take(STREAK_LEADERBOARD_SIZE)
+            {
+                body.row(TABLE_ROW_HEIGHT, |mut row| {
+                    row.col(|ui| {
+                        let title = report_titles
+                            .get(&file_info.path)
+                            .unwrap_or(&file_info.name);
+
+                        ui.label(title).on_hover_text(&file_info.path);
+                    });
+
+                    row.col(|ui| {
+                        ui.label(&player.name);
+                    });
+
+                    row.col(|ui| {
+                        ui.label((wave + 1).to_string());
+                    });
+
+                    row.col(|ui| {
+                        ui.label(kills.to_string());
+                    });
+
+                    row.col(|ui| {
+                        ui.label(settings::format_game_time(start, config));
+                    });
+
+                    row.col(|ui| {
+                        if ui.link("Open report").clicked() {
+                            jump_to = Some(file_info.path.clone());
+                        }
+                    });
+                });
+            }
+        });
+
+    jump_to
+}
@@ -0,0 +1,35 @@
+//! Demo analyzer that runs in a terminal and produces text output, or with an interactable user
+//! interface.
+
+mod cli;
+mod gui;
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[command(flatten)]
+    Cli(cli::Command),
+
+    /// Open the user interface, the default when no command is given
+    Gui {
+        /// Demos to analyze on launch, e.g. the demo double-clicked when the GUI opens demo files
+        demo_paths: Vec<PathBuf>,
+    },
+}
+
+fn main() {
+    match Args::parse().command {
+        Some(Command::Cli(command)) => cli::run(command),
+        Some(Command::Gui { demo_paths }) => gui::run(demo_paths),
+        None => gui::run(vec![]),
+    }
+}
@@ -0,0 +1,185 @@
+//! Condensed per-player stats rendered as an image for sharing.
+
+use crate::settings::DisplaySettings;
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont, point};
+use analysis::{Analysis, Player, Team};
+use image::{ImageResult, Rgba, RgbaImage};
+use std::path::Path;
+
+const WIDTH: u32 = 600;
+
+const HEIGHT: u32 = 240;
+
+const MARGIN: f32 = 24.;
+
+const BACKGROUND_COLOR: Rgba<u8> = Rgba([27, 27, 27, 255]);
+
+const TEXT_COLOR: Rgba<u8> = Rgba([230, 230, 230, 255]);
+
+const MUTED_TEXT_COLOR: Rgba<u8> = Rgba([150, 150, 150, 255]);
+
+const NEUTRAL_COLOR: Rgba<u8> = Rgba([100, 100, 100, 255]);
+
+/// Stats shown on a player's card.
+pub struct StatsCard<'a> {
+    pub player: &'a Player,
+    pub map_name: &'a str,
+    pub captures: usize,
+
+    /// Weapon with the most kills, and how many kills it got.
+    pub favorite_weapon: Option<(String, u32)>,
+
+    /// Number of kills in the player's longest streak.
+    pub best_streak: usize,
+
+    /// Color of the band along the left edge, matching the player's team.
+    pub team_color: Rgba<u8>,
+}
+
+impl<'a> StatsCard<'a> {
+    pub fn new(analysis: &'a Analysis, player: &'a Player, display: &DisplaySettings) -> Self {
+        let captures = analysis
+            .state
+            .captures
+            .iter()
+            .filter(|capture| capture.player == player.id)
+            .count();
+
+        let favorite_weapon = player
+            .weapon_breakdown
+            .iter()
+            .max_by_key(|(_, (kills, _))| *kills)
+            .filter(|(_, (kills, _))| *kills > 0)
+            .map(|(weapon, (kills, _))| (format!("{weapon}"), *kills));
+
+        let best_streak = player
+            .kill_streaks
+            .iter()
+            .map(|streak| streak.kills.len())
+            .max()
+            .unwrap_or_default();
+
+        let team_color = match player.team {
+            Some(Team::Allies) => opaque(display.allies_color),
+            Some(Team::Axis) => opaque(display.axis_color),
+            _ => NEUTRAL_COLOR,
+        };
+
+        Self {
+            player,
+            map_name: &analysis.demo_info.map_name,
+            captures,
+            favorite_weapon,
+            best_streak,
+            team_color,
+        }
+    }
+
+    /// Draws the card into an image.
+    pub fn render(&self) -> RgbaImage {
+        let font = FontRef::try_from_slice(epaint_default_fonts::UBUNTU_LIGHT)
+            .expect("Could not load the built-in font");
+
+        let mut image = RgbaImage::from_pixel(WIDTH, HEIGHT, BACKGROUND_COLOR);
+
+        // Team color band along the left edge
+        for x in 0..8 {
+            for y in 0..HEIGHT {
+                image.put_pixel(x, y, self.team_color);
+            }
+        }
+
+        let (_, kills, deaths) = self.player.stats;
+        let ratio = kills as f32 / deaths.max(1) as f32;
+
+        let favorite_weapon = match &self.favorite_weapon {
+            Some((weapon, kills)) => format!("{weapon} ({kills} kills)"),
+            None => "None".to_string(),
+        };
+
+        let lines = [
+            (36., TEXT_COLOR, self.player.name.clone()),
+            (18., MUTED_TEXT_COLOR, self.map_name.to_string()),
+            (
+                22.,
+                TEXT_COLOR,
+                format!("K/D: {kills}/{deaths} ({ratio:.2})"),
+            ),
+            (22., TEXT_COLOR, format!("Captures: {}", self.captures)),
+            (
+                22.,
+                TEXT_COLOR,
+                format!("Favorite weapon: {favorite_weapon}"),
+            ),
+            (
+                22.,
+                TEXT_COLOR,
+                format!("Best streak: {} kills", self.best_streak),
+            ),
+        ];
+
+        let mut y = MARGIN;
+
+        for (size, color, text) in lines {
+            draw_text(&mut image, &font, size, MARGIN, y, &text, color);
+            y += size * 1.35;
+        }
+
+        image
+    }
+
+    /// Draws the card and saves it as a PNG file.
+    pub fn save_png(&self, path: &Path) -> ImageResult<()> {
+        self.render()
+            .save_with_format(path, image::ImageFormat::Png)
+    }
+}
+
+fn opaque([r, g, b]: [u8; 3]) -> Rgba<u8> {
+    Rgba([r, g, b, 255])
+}
+
+/// Draws a single line of text with its top left corner at `(x, y)`, blending it into the image.
+fn draw_text(
+    image: &mut RgbaImage,
+    font: &FontRef,
+    size: f32,
+    x: f32,
+    y: f32,
+    text: &str,
+    color: Rgba<u8>,
+) {
+    let font = font.as_scaled(PxScale::from(size));
+    let baseline = y + font.ascent();
+    let mut caret = x;
+
+    for c in text.chars() {
+        let glyph = font
+            .glyph_id(c)
+            .with_scale_and_position(size, point(caret, baseline));
+
+        caret += font.h_advance(glyph.id);
+
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+
+        let bounds = outline.px_bounds();
+
+        outline.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+
+            if px < 0 || py < 0 || px >= image.width() as i32 || py >= image.height() as i32 {
+                return;
+            }
+
+            let pixel = image.get_pixel_mut(px as u32, py as u32);
+
+            for channel in 0..3 {
+                pixel[channel] = (pixel[channel] as f32 * (1. - coverage)
+                    + color[channel] as f32 * coverage) as u8;
+            }
+        });
+    }
+}
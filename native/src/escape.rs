@@ -0,0 +1,76 @@
+//! Sanitizers for untrusted text, such as player names and chat, in rendered reports.
+
+/// Returns true for characters that are invisible or can reorder the text around them.
+fn is_unprintable(c: char) -> bool {
+    c.is_control()
+        || matches!(
+            c,
+            '\u{200B}'..='\u{200F}' // Zero-width spaces and directional marks
+            | '\u{202A}'..='\u{202E}' // Directional embeddings and overrides
+            | '\u{2066}'..='\u{2069}' // Directional isolates
+            | '\u{FEFF}' // Byte order mark
+        )
+}
+
+/// Removes control characters and bidirectional overrides.
+pub fn printable(str: &str) -> String {
+    str.chars().filter(|c| !is_unprintable(*c)).collect()
+}
+
+/// Escapes text so it renders literally in Markdown, including inside table cells.
+///
+/// Markdown renderers pass HTML through, so markup characters are replaced with entities.
+pub fn markdown(str: &str) -> String {
+    printable(str)
+        .chars()
+        .fold(String::with_capacity(str.len()), |mut acc, c| {
+            match c {
+                '&' => acc.push_str("&amp;"),
+                '<' => acc.push_str("&lt;"),
+                '>' => acc.push_str("&gt;"),
+                '\\' | '`' | '*' | '_' | '~' | '[' | ']' | '|' | '#' => {
+                    acc.push('\\');
+                    acc.push(c);
+                }
+                _ => acc.push(c),
+            };
+
+            acc
+        })
+}
+
+/// Escapes text so it can be used in HTML element content and quoted attribute values.
+pub fn html(str: &str) -> String {
+    printable(str)
+        .chars()
+        .fold(String::with_capacity(str.len()), |mut acc, c| {
+            match c {
+                '&' => acc.push_str("&amp;"),
+                '<' => acc.push_str("&lt;"),
+                '>' => acc.push_str("&gt;"),
+                '"' => acc.push_str("&quot;"),
+                '\'' => acc.push_str("&#39;"),
+                _ => acc.push(c),
+            };
+
+            acc
+        })
+}
+
+/// Escapes text so it can be used as a single CSV field.
+///
+/// Fields with separators or quotes are quoted, with any quotes doubled. Fields that spreadsheets
+/// would evaluate as formulas are prefixed with an apostrophe.
+pub fn csv(str: &str) -> String {
+    let mut str = printable(str);
+
+    if str.starts_with(['=', '+', '-', '@']) {
+        str.insert(0, '\'');
+    }
+
+    if str.contains([',', '"']) {
+        format!("\"{}\"", str.replace('"', "\"\""))
+    } else {
+        str
+    }
+}
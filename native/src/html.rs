@@ -0,0 +1,232 @@
+//! Building blocks of standalone HTML reports, with the styles, table sorting, and charts inlined
+//! so a report is a single file that can be shared and opened anywhere.
+
+use crate::escape;
+use crate::settings::DisplaySettings;
+use crate::timeline::TimelineSeries;
+use analysis::Team;
+use std::fmt::Write;
+use std::time::Duration;
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2em auto; max-width: 1200px; padding: 0 1em; color: #222; }
+h1 { border-bottom: 2px solid #ccc; padding-bottom: .25em; }
+h2 { margin-top: 1.5em; }
+table { border-collapse: collapse; margin: 1em 0; font-size: .9em; }
+th, td { border: 1px solid #ddd; padding: .3em .6em; text-align: left; }
+th { background: #f3f3f3; cursor: pointer; user-select: none; }
+th[data-order="asc"]::after { content: " \25B2"; }
+th[data-order="desc"]::after { content: " \25BC"; }
+tbody tr:nth-child(even) { background: #fafafa; }
+td.number { text-align: right; }
+svg text { font-size: 12px; fill: #555; }
+.report { margin-bottom: 4em; }
+"#;
+
+/// Sorts a table by a column when its header is clicked, by the `data-sort` value of the cells if
+/// they have one, or else by their text.
+const SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable th").forEach((th) => {
+  th.addEventListener("click", () => {
+    const table = th.closest("table");
+    const body = table.tBodies[0];
+    const ascending = th.dataset.order !== "asc";
+    const key = (row) => {
+      const cell = row.cells[th.cellIndex];
+      return cell.dataset.sort !== undefined ? parseFloat(cell.dataset.sort) : cell.textContent;
+    };
+
+    table.querySelectorAll("th").forEach((other) => delete other.dataset.order);
+    th.dataset.order = ascending ? "asc" : "desc";
+
+    const rows = Array.from(body.rows).sort((a, b) => {
+      const [x, y] = [key(a), key(b)];
+      const order = typeof x === "number" ? x - y : x.localeCompare(y);
+
+      return ascending ? order : -order;
+    });
+
+    body.append(...rows);
+  });
+});
+"#;
+
+/// Size of the score timeline chart, in pixels.
+const CHART_WIDTH: f32 = 800.;
+const CHART_HEIGHT: f32 = 240.;
+
+/// Space around the plot of the score timeline chart for the axis labels, in pixels.
+const CHART_PADDING: f32 = 40.;
+
+/// Text of a table cell, with a number to sort it by when its text does not sort in order, e.g. a
+/// duration.
+pub struct Cell {
+    pub text: String,
+    pub sort_key: Option<f64>,
+}
+
+impl Cell {
+    pub fn number(text: impl ToString, sort_key: f64) -> Self {
+        Self {
+            text: text.to_string(),
+            sort_key: Some(sort_key),
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            sort_key: None,
+        }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+/// Returns a complete HTML document with the styles and scripts the reports need.
+///
+/// The body is inserted as is, so any untrusted text in it must already be escaped.
+pub fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+        <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+        <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}\n\
+        <script>{SCRIPT}</script>\n</body>\n</html>\n",
+        escape::html(title)
+    )
+}
+
+/// Returns a table that can be sorted by clicking its headers, escaping the text of every cell.
+pub fn table(header: &[&str], rows: Vec<Vec<Cell>>) -> String {
+    let mut html = String::from("<table class=\"sortable\">\n<thead><tr>");
+
+    for name in header {
+        let _ = write!(html, "<th>{}</th>", escape::html(name));
+    }
+
+    html.push_str("</tr></thead>\n<tbody>\n");
+
+    for row in rows {
+        html.push_str("<tr>");
+
+        for cell in row {
+            let text = escape::html(&cell.text);
+
+            let _ = match cell.sort_key {
+                Some(sort_key) => write!(
+                    html,
+                    "<td class=\"number\" data-sort=\"{sort_key}\">{text}</td>"
+                ),
+                None => write!(html, "<td>{text}</td>"),
+            };
+        }
+
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</tbody>\n</table>\n");
+
+    html
+}
+
+/// Returns a line chart of the team score timeline as inline SVG, in the teams' colors.
+pub fn timeline_chart(series: &[TimelineSeries], display: &DisplaySettings) -> String {
+    let end = series
+        .iter()
+        .flat_map(|series| series.points.iter().map(|(time, _)| *time))
+        .max()
+        .unwrap_or_default()
+        .max(Duration::from_secs(1));
+
+    let max_score = series
+        .iter()
+        .flat_map(|series| series.points.iter().map(|(_, score)| *score))
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let (plot_width, plot_height) = (
+        CHART_WIDTH - 2. * CHART_PADDING,
+        CHART_HEIGHT - 2. * CHART_PADDING,
+    );
+    let x = |time: Duration| CHART_PADDING + plot_width * time.as_secs_f32() / end.as_secs_f32();
+    let y =
+        |score: i32| CHART_HEIGHT - CHART_PADDING - plot_height * score as f32 / max_score as f32;
+
+    let mut svg = format!(
+        "<svg width=\"{CHART_WIDTH}\" height=\"{CHART_HEIGHT}\" viewBox=\"0 0 {CHART_WIDTH} \
+        {CHART_HEIGHT}\" xmlns=\"http://www.w3.org/2000/svg\">\n"
+    );
+
+    // Axes, labeled with the highest score and the time of the last change
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{left},{top} {left},{bottom} {right},{bottom}\" fill=\"none\" \
+        stroke=\"#999\"/>",
+        left = CHART_PADDING,
+        top = CHART_PADDING,
+        bottom = CHART_HEIGHT - CHART_PADDING,
+        right = CHART_WIDTH - CHART_PADDING,
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{max_score}</text>",
+        CHART_PADDING - 6.,
+        CHART_PADDING + 4.,
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+        CHART_WIDTH - CHART_PADDING,
+        CHART_HEIGHT - CHART_PADDING + 16.,
+        humantime::format_duration(Duration::from_secs(end.as_secs())),
+    );
+
+    for (i, series) in series.iter().enumerate() {
+        let [r, g, b] = match series.team {
+            Team::Axis => display.axis_color,
+            _ => display.allies_color,
+        };
+
+        // Scores change in steps, so hold each score until the next change
+        let mut points = vec![(x(Duration::ZERO), y(0))];
+        let mut score = 0;
+
+        for (time, next_score) in &series.points {
+            points.push((x(*time), y(score)));
+            points.push((x(*time), y(*next_score)));
+            score = *next_score;
+        }
+
+        points.push((x(end), y(score)));
+
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{x:.1},{y:.1}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let _ = writeln!(
+            svg,
+            "<polyline points=\"{points}\" fill=\"none\" stroke=\"rgb({r},{g},{b})\" \
+            stroke-width=\"2\"/>"
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" style=\"fill: rgb({r},{g},{b})\">{}</text>",
+            CHART_PADDING + 8.,
+            CHART_PADDING + 16. * (i + 1) as f32,
+            escape::html(&series.name),
+        );
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
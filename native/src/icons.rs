@@ -0,0 +1,31 @@
+//! Glyphs shown next to weapons in kill feeds, standing in for the weapon sprites of the game's
+//! death notices, so reports read like the kill feed players are used to.
+
+use analysis::{Weapon, WeaponCategory};
+
+/// Returns the glyph of the weapon.
+///
+/// Glyphs are picked from the emoji fonts the GUI ships with, so they render there as well as in
+/// browsers.
+pub fn weapon_icon(weapon: &Weapon) -> &'static str {
+    match weapon {
+        _ if weapon.is_grenade() => "💣",
+        Weapon::Bazooka | Weapon::Panzerschreck | Weapon::Piat => "🚀",
+        Weapon::Mortar => "💥",
+
+        _ => match weapon.category() {
+            WeaponCategory::Rifle => "🎯",
+            WeaponCategory::Smg => "⚡",
+            WeaponCategory::Mg => "🔥",
+            WeaponCategory::Explosive => "💥",
+            WeaponCategory::Melee => "🔪",
+            WeaponCategory::Pistol => "🔫",
+            WeaponCategory::Other => "❔",
+        },
+    }
+}
+
+/// Returns the name of the weapon preceded by its glyph.
+pub fn weapon_label(weapon: &Weapon) -> String {
+    format!("{} {weapon}", weapon_icon(weapon))
+}
@@ -0,0 +1,332 @@
+//! Searchable list of the demos in an archive, so they do not need to be analyzed again to be
+//! found.
+
+use crate::results::{ResultLine, result_lines};
+use crate::storage::Storage;
+use crate::{ANALYZER_VERSION, FileInfo, SCHEMA_VERSION, run_analyzer};
+use analysis::{Analysis, AnalysisConfig, Clan, SteamId, Team};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use std::{fs, io};
+
+/// Key the index is kept under unless another one is given, e.g. in the storage shared by a team.
+pub const DEFAULT_INDEX_KEY: &str = "dod-tools-index.json";
+
+/// Demos found in a directory, read from and written to a JSON file.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct DemoIndex {
+    pub demos: Vec<IndexedDemo>,
+}
+
+/// Summary of a demo in a [DemoIndex].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IndexedDemo {
+    pub path: PathBuf,
+    pub created_at: SystemTime,
+    pub map_name: String,
+
+    /// Names of the Allies and Axis clans.
+    pub clans: [String; 2],
+
+    /// Final scores of the Allies and Axis teams.
+    pub score: [i32; 2],
+
+    pub players: Vec<IndexedPlayer>,
+
+    /// Version of the analyzer that indexed the demo, or empty for indexes written before it was
+    /// recorded.
+    #[serde(default)]
+    pub analyzer_version: String,
+
+    /// [SCHEMA_VERSION] of the results, or 0 for indexes written before it was recorded.
+    #[serde(default)]
+    pub schema_version: u32,
+
+    /// Summary of the results, to show what changes when the demo is analyzed with a newer
+    /// version.
+    #[serde(default)]
+    pub results: Vec<ResultLine>,
+}
+
+/// A player seen in an [IndexedDemo].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IndexedPlayer {
+    /// Steam ID of the player, or their global ID if they have none.
+    pub id: String,
+
+    pub name: String,
+
+    /// Tag of the clan the player played for, or null if it had none or the demo was indexed
+    /// before it was recorded.
+    #[serde(default)]
+    pub clan: Option<String>,
+}
+
+/// A player whose Steam ID never appeared in the earlier indexed demos of the clan they played
+/// for, e.g. a ringer brought in for one match.
+#[derive(Clone, Debug)]
+pub struct RosterNewcomer {
+    /// Steam ID of the player.
+    pub id: String,
+
+    pub name: String,
+
+    /// Tag of the clan the player played for.
+    pub clan: String,
+
+    /// Tags of the other clans the player played for in the indexed demos.
+    pub other_clans: Vec<String>,
+}
+
+impl DemoIndex {
+    /// Reads the index stored under the key, e.g. in a storage shared by a team.
+    pub fn load_from(storage: &dyn Storage, key: &str) -> io::Result<Self> {
+        let contents = storage.read(key)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("No index stored at {key}"))
+        })?;
+
+        let index: Self = serde_json::from_str(&contents).map_err(io::Error::other)?;
+
+        // Results that mean something this version does not know cannot be updated safely
+        if index
+            .demos
+            .iter()
+            .any(|demo| demo.schema_version > SCHEMA_VERSION)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{key} was written by a newer version of the analyzer"),
+            ));
+        }
+
+        Ok(index)
+    }
+
+    pub fn save_to(&self, storage: &dyn Storage, key: &str) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+
+        storage.write(key, &contents)
+    }
+
+    /// Adds the demos in the directory and its subdirectories, and drops those that no longer
+    /// exist.
+    ///
+    /// Demos that were indexed before by this version of the analyzer and have not changed since
+    /// are not analyzed again, so updating the index of a large archive only analyzes the new
+    /// demos.
+    pub fn update(&mut self, dir: &Path, config: &AnalysisConfig) -> io::Result<()> {
+        let mut demos = vec![];
+
+        for demo_path in find_demos(dir)? {
+            let modified_at = fs::metadata(&demo_path)?.modified()?;
+
+            let existing = self.demos.iter().position(|demo| {
+                demo.path == demo_path && demo.created_at == modified_at && !demo.is_outdated()
+            });
+
+            match existing {
+                Some(i) => demos.push(self.demos.swap_remove(i)),
+                None => demos.push(IndexedDemo::new(&demo_path, config)),
+            }
+        }
+
+        demos.sort_by(|l, r| l.path.cmp(&r.path));
+        self.demos = demos;
+
+        Ok(())
+    }
+
+    /// Returns the number of demos indexed by another version of the analyzer, whose results may
+    /// not be comparable with the others until the index is updated.
+    pub fn outdated(&self) -> usize {
+        self.demos.iter().filter(|demo| demo.is_outdated()).count()
+    }
+
+    /// Returns the demos that match the filter.
+    pub fn search<'a>(
+        &'a self,
+        filter: &'a DemoFilter,
+    ) -> impl Iterator<Item = &'a IndexedDemo> + 'a {
+        self.demos.iter().filter(|demo| filter.matches(demo))
+    }
+
+    /// Returns the players of the analyzed demo who are new to the roster of their clan in the
+    /// demos indexed before it.
+    ///
+    /// Only clans with a tag that played in an earlier indexed demo are checked, since every
+    /// player of a clan that was never indexed is new, and only players with a Steam ID, since the
+    /// others cannot be followed across demos.
+    pub fn roster_newcomers(&self, file: &FileInfo, analysis: &Analysis) -> Vec<RosterNewcomer> {
+        // The demo itself may be indexed under another path, e.g. a relative one
+        let other_demos = self
+            .demos
+            .iter()
+            .filter(|demo| demo.path != Path::new(&file.path) && demo.created_at != file.created_at)
+            .collect::<Vec<_>>();
+
+        let state = &analysis.state;
+
+        // Tags are inferred the same way as when the demos are indexed, so they can be compared
+        [Team::Allies, Team::Axis]
+            .into_iter()
+            .map(|team| Clan::new(state, team, &[]))
+            .filter_map(|clan| Some((clan.tag?, clan.players)))
+            .flat_map(|(tag, players)| {
+                let is_tag = |clan: &Option<String>| {
+                    clan.as_ref()
+                        .is_some_and(|clan| clan.eq_ignore_ascii_case(&tag))
+                };
+
+                let earlier_roster = other_demos
+                    .iter()
+                    .filter(|demo| demo.created_at < file.created_at)
+                    .flat_map(|demo| &demo.players)
+                    .filter(|player| is_tag(&player.clan))
+                    .map(|player| player.id.as_str())
+                    .collect::<Vec<_>>();
+
+                if earlier_roster.is_empty() {
+                    return vec![];
+                }
+
+                players
+                    .iter()
+                    .filter_map(|id| {
+                        let steam_id = SteamId::try_from(id).ok()?.to_string();
+
+                        if earlier_roster.contains(&steam_id.as_str()) {
+                            return None;
+                        }
+
+                        let mut other_clans = other_demos
+                            .iter()
+                            .flat_map(|demo| &demo.players)
+                            .filter(|player| player.id == steam_id && !is_tag(&player.clan))
+                            .filter_map(|player| player.clan.clone())
+                            .collect::<Vec<_>>();
+
+                        other_clans.sort();
+                        other_clans.dedup();
+
+                        let player = state.players.iter().find(|player| player.id == *id)?;
+
+                        Some(RosterNewcomer {
+                            name: player.name.clone(),
+                            id: steam_id,
+                            clan: tag.clone(),
+                            other_clans,
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Conditions a demo must meet to be listed by [DemoIndex::search]. Conditions that are not set
+/// match every demo.
+#[derive(Debug, Default)]
+pub struct DemoFilter {
+    /// Steam ID or name of a player in the demo.
+    pub player: Option<String>,
+
+    pub map_name: Option<String>,
+
+    /// Part of the name of either clan.
+    pub clan: Option<String>,
+
+    pub recorded_after: Option<SystemTime>,
+    pub recorded_before: Option<SystemTime>,
+}
+
+impl DemoFilter {
+    pub fn matches(&self, demo: &IndexedDemo) -> bool {
+        let has_player = self.player.as_ref().is_none_or(|player| {
+            demo.players
+                .iter()
+                .any(|p| p.id == *player || p.name.eq_ignore_ascii_case(player))
+        });
+
+        let is_on_map = self
+            .map_name
+            .as_ref()
+            .is_none_or(|map_name| demo.map_name.eq_ignore_ascii_case(map_name));
+
+        let has_clan = self.clan.as_ref().is_none_or(|clan| {
+            let clan = clan.to_lowercase();
+
+            demo.clans
+                .iter()
+                .any(|name| name.to_lowercase().contains(&clan))
+        });
+
+        let is_in_range = self
+            .recorded_after
+            .is_none_or(|after| demo.created_at >= after)
+            && self
+                .recorded_before
+                .is_none_or(|before| demo.created_at <= before);
+
+        has_player && is_on_map && has_clan && is_in_range
+    }
+}
+
+impl IndexedDemo {
+    /// Returns true if the demo was indexed by another version of the analyzer, whose results may
+    /// differ.
+    pub fn is_outdated(&self) -> bool {
+        self.schema_version != SCHEMA_VERSION || self.analyzer_version != ANALYZER_VERSION
+    }
+
+    fn new(demo_path: &Path, config: &AnalysisConfig) -> Self {
+        let (file_info, analysis) = run_analyzer(&demo_path.to_path_buf(), config);
+        let state = &analysis.state;
+        let clans = [Team::Allies, Team::Axis].map(|team| Clan::new(state, team, &[]));
+
+        Self {
+            path: demo_path.to_path_buf(),
+            created_at: file_info.created_at,
+            map_name: analysis.demo_info.map_name.clone(),
+            clans: clans.each_ref().map(|clan| clan.name()),
+            score: [Team::Allies, Team::Axis].map(|team| state.team_scores.get_team_score(team)),
+            players: state
+                .players
+                .iter()
+                .map(|player| IndexedPlayer {
+                    id: SteamId::try_from(&player.id)
+                        .map(|steam_id| steam_id.to_string())
+                        .unwrap_or(player.id.to_string()),
+                    name: player.name.clone(),
+                    clan: clans
+                        .iter()
+                        .find(|clan| clan.players.contains(&player.id))
+                        .and_then(|clan| clan.tag.clone()),
+                })
+                .collect(),
+            analyzer_version: ANALYZER_VERSION.to_string(),
+            schema_version: SCHEMA_VERSION,
+            results: result_lines(&analysis),
+        }
+    }
+}
+
+/// Returns the paths of the demo files in the directory and its subdirectories.
+fn find_demos(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut demos = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            demos.extend(find_demos(&path)?);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("dem"))
+        {
+            demos.push(path);
+        }
+    }
+
+    Ok(demos)
+}
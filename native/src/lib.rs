@@ -1,17 +1,148 @@
-use analysis::Analysis;
+pub mod association;
+pub mod card;
+pub mod escape;
+pub mod html;
+pub mod icons;
+pub mod index;
+pub mod manifest;
+pub mod markers;
+pub mod notes;
+pub mod notify;
+pub mod report;
+pub mod results;
+pub mod settings;
+pub mod storage;
+pub mod timeline;
+pub mod win_model;
+
+use crate::index::RosterNewcomer;
+use crate::manifest::ManifestEntry;
+use crate::notify::{Notification, Notifier};
+use analysis::{Analysis, AnalysisConfig, ClanRoster, GameTime};
+use clap::ValueEnum;
 use filetime::FileTime;
 use std::fs;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
+/// Version of the analyzer, stamped on every result written to a file.
+pub const ANALYZER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the meaning of the results written to files, such as the JSON output and the demo
+/// index.
+///
+/// Raised whenever a result is counted differently, e.g. when suicides stopped counting as kills by
+/// a player, so results written before are analyzed again instead of being mixed with new ones.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Time allowed for a request to a web service, such as a webhook, so an unreachable server does
+/// not hold up the program.
+pub const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns an HTTP client that gives up on requests after [HTTP_TIMEOUT].
+pub(crate) fn http_agent() -> ureq::Agent {
+    let config = ureq::Agent::config_builder()
+        .timeout_global(Some(HTTP_TIMEOUT))
+        .build();
+
+    ureq::Agent::new_with_config(config)
+}
+
 pub struct FileInfo {
     pub created_at: SystemTime,
     pub name: String,
     pub path: String,
+
+    /// Metadata about the match, when the demo was listed in a manifest.
+    pub manifest_entry: Option<ManifestEntry>,
+
+    /// Players new to the roster of their clan, when the demo was checked against a
+    /// [DemoIndex](index::DemoIndex).
+    pub roster_newcomers: Vec<RosterNewcomer>,
 }
 
-pub fn run_analyzer(demo_path: &PathBuf) -> (FileInfo, Analysis) {
+impl FileInfo {
+    /// Returns the teams expected to play in the demo, if any were listed in a manifest.
+    pub fn rosters(&self) -> &[ClanRoster] {
+        self.manifest_entry
+            .as_ref()
+            .map(|entry| entry.teams.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the console commands that play the demo and seek to the moment.
+    ///
+    /// The name of the file is used rather than [Self::name], which also tells the segments of
+    /// the demo apart.
+    pub fn seek_command(&self, time: &GameTime) -> String {
+        let file_name = Path::new(&self.path)
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        time.to_viewdemo_command(&file_name)
+    }
+}
+
+/// How the playback segments of a demo that was stopped and recorded again are analyzed.
+#[derive(Clone, Debug, Default, ValueEnum)]
+pub enum Segments {
+    /// One analysis of the whole demo, with the recording clock carried over between segments
+    #[default]
+    Joined,
+
+    /// One analysis for each segment
+    Separate,
+
+    /// One analysis for each map played, for demos that span map changes, e.g. long public server
+    /// demos recorded by HLTV
+    Maps,
+}
+
+pub fn run_analyzer(demo_path: &PathBuf, config: &AnalysisConfig) -> (FileInfo, Analysis) {
+    let bytes = read_demo(demo_path);
+    let analysis = Analysis::with_config(bytes.as_slice(), config.clone());
+
+    (file_info(demo_path), analysis)
+}
+
+/// Runs the analyzer on the whole demo, or on each of its playback segments or maps.
+pub fn run_analyzer_by_segment(
+    demo_path: &PathBuf,
+    config: &AnalysisConfig,
+    segments: &Segments,
+) -> Vec<(FileInfo, Analysis)> {
+    let (analyses, label) = match segments {
+        Segments::Joined => return vec![run_analyzer(demo_path, config)],
+        Segments::Separate => (
+            Analysis::segments_with_config(read_demo(demo_path).as_slice(), config.clone()),
+            "segment",
+        ),
+        Segments::Maps => (
+            Analysis::maps_with_config(read_demo(demo_path).as_slice(), config.clone()),
+            "map",
+        ),
+    };
+
+    let count = analyses.len();
+
+    analyses
+        .into_iter()
+        .enumerate()
+        .map(|(i, analysis)| {
+            let mut file_info = file_info(demo_path);
+
+            if count > 1 {
+                file_info.name = format!("{} ({label} {} of {count})", file_info.name, i + 1);
+            }
+
+            (file_info, analysis)
+        })
+        .collect()
+}
+
+fn read_demo(demo_path: &PathBuf) -> Vec<u8> {
     let mut file = fs::OpenOptions::new()
         .read(true)
         .open(demo_path)
@@ -22,8 +153,10 @@ pub fn run_analyzer(demo_path: &PathBuf) -> (FileInfo, Analysis) {
     file.read_to_end(&mut bytes)
         .expect("Could not read the file");
 
-    let analysis = Analysis::from(bytes.as_slice());
+    bytes
+}
 
+fn file_info(demo_path: &PathBuf) -> FileInfo {
     let created_at = fs::metadata(demo_path)
         .map_err(|_| ())
         .map(|metadata| FileTime::from_last_modification_time(&metadata))
@@ -35,7 +168,7 @@ pub fn run_analyzer(demo_path: &PathBuf) -> (FileInfo, Analysis) {
         })
         .unwrap();
 
-    let file_info = FileInfo {
+    FileInfo {
         created_at,
         name: demo_path
             .file_name()
@@ -44,7 +177,35 @@ pub fn run_analyzer(demo_path: &PathBuf) -> (FileInfo, Analysis) {
             .unwrap(),
 
         path: demo_path.to_str().map(String::from).unwrap(),
-    };
+        manifest_entry: None,
+        roster_newcomers: vec![],
+    }
+}
+
+/// Runs the analyzer, sending [Notification]s for the key moments of the analysis.
+pub fn run_analyzer_with_notifier(
+    demo_path: &PathBuf,
+    config: &AnalysisConfig,
+    segments: &Segments,
+    notifier: &impl Notifier,
+) -> Vec<(FileInfo, Analysis)> {
+    let path = demo_path.to_string_lossy();
+
+    notifier.notify(&Notification::AnalysisStarted { path: &path });
+
+    let outputs = run_analyzer_by_segment(demo_path, config, segments);
+
+    for (file_info, analysis) in &outputs {
+        notifier.notify(&Notification::AnalysisFinished {
+            path: &file_info.path,
+            analysis,
+        });
+
+        notifier.notify(&Notification::MatchResult {
+            path: &file_info.path,
+            analysis,
+        });
+    }
 
-    (file_info, analysis)
+    outputs
 }
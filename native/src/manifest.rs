@@ -0,0 +1,68 @@
+//! Lists of demos with metadata about the matches they belong to.
+
+use analysis::ClanRoster;
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Demos to analyze, read from a TOML file.
+///
+/// ```toml
+/// [[demos]]
+/// path = "final-1.dem"
+/// match_name = "Cup final"
+/// half = 1
+/// teams = [{ name = "ABC", members = ["STEAM_0:1:1234"] }]
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    pub demos: Vec<ManifestEntry>,
+}
+
+/// A demo in a [Manifest].
+#[derive(Clone, Debug, Deserialize)]
+pub struct ManifestEntry {
+    /// Path to the demo file, relative to the manifest file.
+    pub path: PathBuf,
+
+    /// Name of the match the demo belongs to.
+    pub match_name: Option<String>,
+
+    /// Half of the match recorded in the demo, starting at 1.
+    pub half: Option<u32>,
+
+    /// Teams expected to play in the match, used to label the sides.
+    #[serde(default)]
+    pub teams: Vec<ClanRoster>,
+}
+
+impl Manifest {
+    /// Reads a manifest file, resolving the demo paths against its directory.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+
+        let mut manifest: Manifest =
+            toml::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if let Some(dir) = path.parent() {
+            for demo in manifest.demos.iter_mut() {
+                demo.path = dir.join(&demo.path);
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl ManifestEntry {
+    /// Returns a label such as "Cup final, half 1", if the entry names its match or half.
+    pub fn label(&self) -> Option<String> {
+        match (&self.match_name, self.half) {
+            (Some(match_name), Some(half)) => Some(format!("{match_name}, half {half}")),
+            (Some(match_name), None) => Some(match_name.clone()),
+            (None, Some(half)) => Some(format!("Half {half}")),
+            (None, None) => None,
+        }
+    }
+}
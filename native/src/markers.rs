@@ -0,0 +1,125 @@
+//! Highlight markers to import into video editors as chapter markers, to line up edits of a
+//! recorded demo playback with the moments found by the analysis.
+
+use crate::escape;
+use analysis::{Analysis, HighlightKind, SteamId};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// A highlight at a moment of the playback.
+pub struct Marker {
+    /// Time since the start of the playback, on the configured clock.
+    pub time: Duration,
+
+    pub player: String,
+    pub kind: HighlightKind,
+}
+
+/// Returns the highlights of every player, or of the player matched by Steam ID, global ID, or
+/// name, ordered by time.
+pub fn highlight_markers(analysis: &Analysis, player: Option<&str>) -> Vec<Marker> {
+    let mut markers = analysis
+        .state
+        .players_to_watch()
+        .into_iter()
+        .filter(|watch| {
+            player.is_none_or(|query| {
+                let steam_id =
+                    SteamId::try_from(&watch.player.id).map(|steam_id| steam_id.to_string());
+
+                steam_id.as_deref() == Ok(query)
+                    || watch.player.id.to_string() == query
+                    || watch.player.name.eq_ignore_ascii_case(query)
+            })
+        })
+        .flat_map(|watch| {
+            watch.highlights.into_iter().map(|highlight| Marker {
+                time: highlight.time.offset(&analysis.config.clock),
+                player: watch.player.name.clone(),
+                kind: highlight.kind,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    markers.sort_by_key(|marker| marker.time);
+
+    markers
+}
+
+/// Formats the markers as CSV with `time,timestamp,player,highlight` columns, where the time is in
+/// seconds and the timestamp is `HH:MM:SS.mmm`.
+pub fn to_csv(markers: &[Marker]) -> String {
+    markers.iter().fold(
+        String::from("time,timestamp,player,highlight\n"),
+        |mut csv, marker| {
+            let millis = marker.time.as_millis();
+            let _ = writeln!(
+                csv,
+                "{:.3},{:02}:{:02}:{:02}.{:03},{},{}",
+                marker.time.as_secs_f64(),
+                millis / 3_600_000,
+                millis / 60_000 % 60,
+                millis / 1000 % 60,
+                millis % 1000,
+                escape::csv(&marker.player),
+                escape::csv(&marker.kind.to_string()),
+            );
+
+            csv
+        },
+    )
+}
+
+/// Formats the markers as a CMX 3600 edit decision list, with one single frame event per marker
+/// in the marker notation read by DaVinci Resolve.
+///
+/// Timecodes count frames at the frame rate of the recording, so it needs to match for the markers
+/// to land on the right frames.
+pub fn to_edl(markers: &[Marker], title: &str, frame_rate: u32) -> String {
+    let mut edl = format!("TITLE: {title}\nFCM: NON-DROP FRAME\n\n");
+
+    for (i, marker) in markers.iter().enumerate() {
+        let frame = (marker.time.as_secs_f64() * frame_rate as f64) as u64;
+        let start = timecode(frame, frame_rate);
+        let end = timecode(frame + 1, frame_rate);
+
+        let _ = writeln!(
+            edl,
+            "{:03}  001      V     C        {start} {end} {start} {end}",
+            i + 1
+        );
+        let _ = writeln!(
+            edl,
+            " |C:{} |M:{}: {} |D:1",
+            marker_color(&marker.kind),
+            marker.player,
+            marker.kind
+        );
+    }
+
+    edl
+}
+
+/// Formats a frame number as `HH:MM:SS:FF`.
+fn timecode(frame: u64, frame_rate: u32) -> String {
+    let frame_rate = frame_rate.max(1) as u64;
+    let secs = frame / frame_rate;
+
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        frame % frame_rate
+    )
+}
+
+/// Returns the color of the marker, so the kinds of highlights can be told apart on the timeline.
+fn marker_color(kind: &HighlightKind) -> &'static str {
+    match kind {
+        HighlightKind::KillStreak(_) => "ResolveColorRed",
+        HighlightKind::Capture(_) => "ResolveColorGreen",
+        HighlightKind::FirstBlood | HighlightKind::RoundEnder => "ResolveColorBlue",
+        HighlightKind::LongRangeKill(_) => "ResolveColorYellow",
+    }
+}
@@ -0,0 +1,69 @@
+//! Notes written by reviewers about demos, kept with the identities so a team shares them.
+
+use crate::settings::config_storage;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// Key of the notes in the [config_storage].
+const NOTES_KEY: &str = "notes.toml";
+
+/// Notes about demos, keyed by the name of the demo file, since the same demo is kept in a
+/// different directory on each reviewer's machine.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DemoNotes {
+    pub notes: BTreeMap<String, String>,
+}
+
+impl DemoNotes {
+    /// Returns the note about the demo at the path, if any.
+    pub fn note(&self, demo_path: &str) -> Option<&str> {
+        self.notes.get(&file_name(demo_path)).map(String::as_str)
+    }
+}
+
+/// Returns the name of the demo file, which notes are kept under.
+fn file_name(demo_path: &str) -> String {
+    Path::new(demo_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| demo_path.to_string())
+}
+
+/// Reads the notes about demos, or none if they are missing or invalid.
+pub fn load_notes() -> DemoNotes {
+    config_storage()
+        .and_then(|storage| storage.read(NOTES_KEY).ok().flatten())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the note about the demo at the path, or removes it if it is empty, on top of the notes
+/// saved by other reviewers since they were loaded, and returns all the saved notes.
+pub fn save_note(demo_path: &str, note: &str) -> io::Result<DemoNotes> {
+    let storage = config_storage().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "Could not find the configuration directory",
+        )
+    })?;
+
+    let contents = storage::update(storage.as_ref(), NOTES_KEY, |contents| {
+        let mut notes: DemoNotes = match contents {
+            Some(contents) => toml::from_str(&contents).map_err(io::Error::other)?,
+            None => DemoNotes::default(),
+        };
+
+        if note.trim().is_empty() {
+            notes.notes.remove(&file_name(demo_path));
+        } else {
+            notes.notes.insert(file_name(demo_path), note.to_string());
+        }
+
+        toml::to_string(&notes).map_err(io::Error::other)
+    })?;
+
+    toml::from_str(&contents).map_err(io::Error::other)
+}
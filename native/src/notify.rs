@@ -0,0 +1,105 @@
+//! Notifications about key moments of an analysis, for integrations such as league bots.
+
+use analysis::{Analysis, Team};
+use serde_json::{Value, json};
+
+/// Key moment of an analysis.
+pub enum Notification<'a> {
+    /// A demo file is about to be analyzed.
+    AnalysisStarted { path: &'a str },
+
+    /// A demo file was analyzed.
+    AnalysisFinished {
+        path: &'a str,
+        analysis: &'a Analysis,
+    },
+
+    /// Final team scores of an analyzed demo.
+    MatchResult {
+        path: &'a str,
+        analysis: &'a Analysis,
+    },
+}
+
+impl Notification<'_> {
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::AnalysisStarted { path } => json!({
+                "event": "analysis_started",
+                "file": path,
+            }),
+
+            Self::AnalysisFinished { path, analysis } => json!({
+                "event": "analysis_finished",
+                "file": path,
+                "map": analysis.demo_info.map_name,
+                "players": analysis.state.players.len(),
+                "rounds": analysis.state.rounds.len(),
+            }),
+
+            Self::MatchResult { path, analysis } => {
+                let allies = analysis.state.team_scores.get_team_score(Team::Allies);
+                let axis = analysis.state.team_scores.get_team_score(Team::Axis);
+
+                json!({
+                    "event": "match_result",
+                    "file": path,
+                    "teams": {
+                        "allies": allies,
+                        "axis": axis,
+                    },
+                    "winner": match allies.cmp(&axis) {
+                        std::cmp::Ordering::Greater => Some("allies"),
+                        std::cmp::Ordering::Less => Some("axis"),
+                        std::cmp::Ordering::Equal => None,
+                    },
+                })
+            }
+        }
+    }
+}
+
+/// Receiver of [Notification]s.
+///
+/// Implemented for closures, so callers can register a callback directly.
+pub trait Notifier {
+    fn notify(&self, notification: &Notification);
+}
+
+impl<F: Fn(&Notification)> Notifier for F {
+    fn notify(&self, notification: &Notification) {
+        self(notification)
+    }
+}
+
+/// Sends each [Notification] as a JSON POST request to a URL.
+pub struct Webhook {
+    pub url: String,
+    agent: ureq::Agent,
+}
+
+impl Webhook {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            agent: crate::http_agent(),
+        }
+    }
+}
+
+impl Notifier for Webhook {
+    fn notify(&self, notification: &Notification) {
+        let body = notification.to_json().to_string();
+
+        let response = self
+            .agent
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .send(body);
+
+        // Integrations are best effort, so a failure should never stop the analysis
+        if let Err(e) = response {
+            eprintln!("Could not send notification to {}: {e}", self.url);
+        }
+    }
+}
@@ -0,0 +1,1030 @@
+//! JSON report of a demo, declared as types so that its JSON Schema is derived from the same
+//! definitions that write it.
+
+use crate::{ANALYZER_VERSION, FileInfo, SCHEMA_VERSION, settings, timeline, win_model};
+use analysis::{
+    Analysis, Clan, Consistency, DeathEconomy, KillLatency, KillParticipation, MatchPace,
+    MortalityState, Narrative, Player, PlayerGlobalId, SteamId, Team,
+};
+use humantime::format_duration;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// Results of the analysis of one demo.
+#[derive(JsonSchema, Serialize)]
+pub struct Report {
+    pub file: String,
+
+    /// Version of the analyzer that wrote the report.
+    pub analyzer_version: String,
+
+    /// Version of the meaning of the results, raised whenever a result is counted differently.
+    pub schema_version: u32,
+
+    /// Clock the times in the report are measured with.
+    pub clock: String,
+
+    /// Names of the messages found in the demo that the analyzer does not understand.
+    pub extra_messages: Vec<String>,
+
+    pub directory_entries: Vec<DirectoryEntryReport>,
+
+    /// Name of the match, when the demo was listed in a manifest.
+    pub match_name: Option<String>,
+
+    /// Half of the match, when the demo was listed in a manifest.
+    pub half: Option<u32>,
+
+    pub teams: TeamScoresReport,
+    pub clans: ClansReport,
+    pub team_score_timeline: Vec<TeamScorePoint>,
+    pub team_kill_timeline: Vec<TeamKillPoint>,
+
+    /// Times the teams swapped sides.
+    pub team_swaps: Vec<String>,
+
+    pub players: Vec<PlayerReport>,
+    pub scoreboard_history: Vec<ScoreboardSnapshotReport>,
+    pub rounds: Vec<RoundReport>,
+    pub match_pace: MatchPaceReport,
+    pub death_economy: Vec<DeathEconomyReport>,
+    pub kill_feed: Vec<KillFeedEntry>,
+    pub teamkills: Vec<TeamkillReport>,
+
+    /// Rounds in which a player's score differs from the score formula, or null without one.
+    pub score_mismatches: Option<Vec<ScoreMismatchReport>>,
+
+    pub matchups: Vec<MatchupReport>,
+    pub kill_matrix: KillMatrixReport,
+    pub players_to_watch: Vec<PlayerToWatchReport>,
+    pub late_joins: Vec<LateJoinReport>,
+
+    /// Players whose Steam ID never appeared in the earlier indexed demos of their clan, empty
+    /// unless the demo was checked against an index.
+    pub roster_newcomers: Vec<RosterNewcomerReport>,
+
+    pub danger_zones: Vec<DangerZoneReport>,
+    pub match_start_time: Option<String>,
+    pub match_start_candidates: Vec<String>,
+
+    /// Results of the warmup before the match started, if there was one.
+    pub warmup: Option<WarmupReport>,
+
+    pub suspicious_events: Vec<SuspiciousEventReport>,
+
+    /// Delay between kills and the messages about them, if it could be measured.
+    pub kill_latency: Option<KillLatencyReport>,
+
+    /// Number of messages that could not be read, by message name.
+    pub malformed_messages: BTreeMap<String, usize>,
+
+    pub server_anomalies: Vec<ServerAnomalyReport>,
+    pub chat: Vec<ChatReport>,
+    pub narrative: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct DirectoryEntryReport {
+    pub name: String,
+    pub is_playback: bool,
+    pub frame_count: usize,
+    pub duration_secs: f32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct TeamScoresReport {
+    pub allies: i32,
+    pub axis: i32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ClansReport {
+    pub allies: ClanReport,
+    pub axis: ClanReport,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ClanReport {
+    pub tag: Option<String>,
+    pub players: Vec<String>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct TeamScorePoint {
+    pub time: String,
+    pub team: String,
+    pub score: i32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct TeamKillPoint {
+    pub time: String,
+    pub team: String,
+    pub kills: i32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct PlayerReport {
+    /// Steam ID of the player, or their ID in the demo when it is not a Steam ID.
+    pub id: String,
+
+    pub name: String,
+    pub team: Option<String>,
+    pub score: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub environment_deaths: usize,
+    pub suicides: usize,
+
+    /// Number of assists, or null when assists are not inferred.
+    pub assists: Option<usize>,
+
+    pub captures: usize,
+    pub objective_score: i32,
+    pub rounds_played: usize,
+    pub first_bloods: usize,
+    pub round_enders: usize,
+    pub lifespan: LifespanReport,
+    pub weapon_breakdown: Vec<WeaponCategoryReport>,
+    pub kill_distances: KillDistancesReport,
+    pub kill_timing: Vec<KillTimingReport>,
+    pub positions_held: Vec<PositionHeldReport>,
+    pub distance_traveled: f32,
+    pub shots: ShotsReport,
+    pub ammo_usage: Vec<AmmoUsageReport>,
+    pub time_to_first_kill: TimeToFirstKillReport,
+    pub consistency: ConsistencyReport,
+    pub impact_rating: f32,
+    pub kill_participation: KillParticipationReport,
+    pub kill_streaks: Vec<KillStreakReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct LifespanReport {
+    pub avg: String,
+    pub min: String,
+    pub max: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct WeaponCategoryReport {
+    pub category: String,
+    pub kills: u32,
+    pub teamkills: u32,
+    pub weapons: Vec<WeaponKillsReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct WeaponKillsReport {
+    pub weapon: String,
+    pub kills: u32,
+    pub teamkills: u32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct KillDistancesReport {
+    pub by_weapon: Vec<WeaponRangeReport>,
+    pub longest: Option<LongestKillReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct WeaponRangeReport {
+    pub weapon: String,
+    pub kills: u32,
+    pub avg_meters: f32,
+    pub long_range_kills: u32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct LongestKillReport {
+    pub meters: f32,
+    pub weapon: String,
+    pub victim: String,
+    pub time: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct KillTimingReport {
+    pub weapon: String,
+    pub kills: usize,
+    pub time_to_kill_ms: u128,
+    pub fire_interval_ms: u128,
+    pub fastest_follow_up_ms: Option<u128>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct PositionHeldReport {
+    pub start_time: String,
+    pub duration: String,
+    pub kills: usize,
+    pub origin: [f32; 3],
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ShotsReport {
+    /// Number of weapon sounds made by the player.
+    pub heard: usize,
+
+    pub estimated_accuracy: Option<f32>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct AmmoUsageReport {
+    pub weapon: String,
+    pub shots: u32,
+    pub reloads: u32,
+    pub kills_per_shot: Option<f32>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct TimeToFirstKillReport {
+    pub avg: String,
+    pub min: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ConsistencyReport {
+    pub kills_per_round: Vec<u32>,
+    pub mean: f32,
+    pub variance: f32,
+    pub index: f32,
+    pub steady: bool,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct KillParticipationReport {
+    pub per_round: Vec<f32>,
+    pub average: f32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct KillStreakReport {
+    /// Number of the life the streak was made in, starting at 1.
+    pub wave: usize,
+
+    /// Console commands that play the demo from the first kill of the streak.
+    pub seek: Option<String>,
+
+    pub kills: Vec<StreakKillReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct StreakKillReport {
+    pub weapon: String,
+    pub viewdemo_secs: f32,
+    pub recording_secs: f32,
+    pub server_secs: f32,
+    pub round: Option<usize>,
+    pub round_secs: Option<f32>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ScoreboardSnapshotReport {
+    pub round: usize,
+    pub time: String,
+    pub players: Vec<ScoreboardRowReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ScoreboardRowReport {
+    pub id: String,
+    pub score: i32,
+    pub kills: i32,
+    pub deaths: i32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct RoundReport {
+    pub round: usize,
+    pub start_time: String,
+    pub players: Vec<RoundPlayerReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct RoundPlayerReport {
+    pub id: String,
+    pub score: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub objective_score: i32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct MatchPaceReport {
+    pub avg_duration: String,
+    pub median_duration: String,
+    pub fastest_round: Option<usize>,
+    pub slowest_round: Option<usize>,
+    pub rounds: Vec<RoundPaceReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct RoundPaceReport {
+    pub round: usize,
+    pub start_time: String,
+    pub duration: String,
+    pub kills: usize,
+    pub kills_per_minute: f32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct DeathEconomyReport {
+    pub team: String,
+    pub deaths: usize,
+    pub captures: usize,
+    pub deaths_per_capture: Option<f32>,
+    pub traded_deaths: usize,
+    pub trade_kills: usize,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct KillFeedEntry {
+    pub time: String,
+    pub killer: Option<String>,
+    pub victim: String,
+    pub weapon: String,
+    pub teamkill: bool,
+
+    /// What caused the death: `player`, `suicide`, or `world`.
+    pub cause: String,
+
+    pub first_blood: bool,
+    pub round_ender: bool,
+
+    /// Chance to win the round the kill added to the killer's team, for kills between opponents.
+    pub win_probability_delta: Option<f32>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct TeamkillReport {
+    pub time: String,
+    pub round: Option<usize>,
+    pub killer: String,
+    pub victim: String,
+    pub weapon: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ScoreMismatchReport {
+    pub round: usize,
+    pub player: String,
+    pub reported: i32,
+    pub expected: i32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct MatchupReport {
+    pub killer: String,
+    pub victim: String,
+    pub kills: u32,
+
+    /// Kills by weapon name.
+    pub weapons: BTreeMap<String, u32>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct KillMatrixReport {
+    pub players: Vec<String>,
+
+    /// Kills by the player in each row of the player in each column, in the order of `players`.
+    pub kills: Vec<Vec<u32>>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct PlayerToWatchReport {
+    pub id: String,
+    pub name: String,
+    pub score: u32,
+    pub highlights: Vec<HighlightReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct HighlightReport {
+    pub time: String,
+    pub description: String,
+    pub seek: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct LateJoinReport {
+    pub id: String,
+    pub name: String,
+    pub team: Option<String>,
+    pub connected: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct RosterNewcomerReport {
+    pub id: String,
+    pub name: String,
+    pub clan: String,
+
+    /// Tags of the other clans the player played for in the indexed demos.
+    pub other_clans: Vec<String>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct DangerZoneReport {
+    pub name: String,
+    pub center: [f32; 2],
+    pub allies_deaths: usize,
+    pub axis_deaths: usize,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct WarmupReport {
+    pub rounds: usize,
+    pub captures: usize,
+    pub players: Vec<WarmupPlayerReport>,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct WarmupPlayerReport {
+    pub id: String,
+    pub name: String,
+    pub kills: usize,
+    pub deaths: usize,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct SuspiciousEventReport {
+    pub time: String,
+    pub player: String,
+    pub event: String,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct KillLatencyReport {
+    pub recorded_by_hltv: bool,
+    pub median_delay_secs: f32,
+    pub max_delay_secs: f32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ServerAnomalyReport {
+    pub time: String,
+    pub anomaly: String,
+    pub duration_secs: f32,
+}
+
+#[derive(JsonSchema, Serialize)]
+pub struct ChatReport {
+    pub time: String,
+    pub player: Option<String>,
+    pub text: String,
+}
+
+impl Report {
+    pub fn new(file: &FileInfo, analysis: &Analysis) -> Self {
+        let state = &analysis.state;
+        let config = &analysis.config;
+
+        let win_model = win_model::win_model(config);
+        let impact_ratings = state.impact_ratings(&win_model);
+
+        let pace = MatchPace::new(state);
+        let kill_matrix = state.kill_matrix();
+        let manifest_entry = file.manifest_entry.as_ref();
+
+        let [allies_clan, axis_clan] = [Team::Allies, Team::Axis].map(|team| {
+            let clan = Clan::new(state, team, file.rosters());
+
+            ClanReport {
+                tag: clan.tag,
+                players: clan.players.iter().map(|id| id.to_string()).collect(),
+            }
+        });
+
+        Self {
+            file: file.path.clone(),
+
+            analyzer_version: ANALYZER_VERSION.to_string(),
+            schema_version: SCHEMA_VERSION,
+
+            clock: format!("{:?}", config.clock).to_lowercase(),
+
+            extra_messages: analysis.demo_info.extra_messages.clone(),
+
+            directory_entries: analysis
+                .demo_info
+                .directory_entries
+                .iter()
+                .map(|entry| DirectoryEntryReport {
+                    name: entry.name.clone(),
+                    is_playback: entry.is_playback,
+                    frame_count: entry.frame_count,
+                    duration_secs: entry.duration.as_secs_f32(),
+                })
+                .collect(),
+
+            match_name: manifest_entry.and_then(|entry| entry.match_name.clone()),
+
+            half: manifest_entry.and_then(|entry| entry.half),
+
+            teams: TeamScoresReport {
+                allies: state.team_scores.get_team_score(Team::Allies),
+                axis: state.team_scores.get_team_score(Team::Axis),
+            },
+
+            clans: ClansReport {
+                allies: allies_clan,
+                axis: axis_clan,
+            },
+
+            team_score_timeline: timeline::team_score_timeline(analysis, file.rosters())
+                .iter()
+                .flat_map(|series| {
+                    series.points.iter().map(|(time, score)| TeamScorePoint {
+                        time: format_duration(Duration::new(time.as_secs(), 0)).to_string(),
+                        team: series.name.clone(),
+                        score: *score,
+                    })
+                })
+                .collect(),
+
+            team_kill_timeline: timeline::team_kill_timeline(analysis, file.rosters())
+                .iter()
+                .flat_map(|series| {
+                    series.points.iter().map(|(time, kills)| TeamKillPoint {
+                        time: format_duration(Duration::new(time.as_secs(), 0)).to_string(),
+                        team: series.name.clone(),
+                        kills: *kills,
+                    })
+                })
+                .collect(),
+
+            team_swaps: state
+                .team_swaps
+                .iter()
+                .map(|time| settings::format_game_time(time, config))
+                .collect(),
+
+            players: state
+                .players
+                .iter()
+                .map(|player| PlayerReport::new(file, analysis, player, &impact_ratings))
+                .collect(),
+
+            scoreboard_history: state
+                .scoreboard_history
+                .iter()
+                .map(|snapshot| ScoreboardSnapshotReport {
+                    round: snapshot.round,
+                    time: settings::format_game_time(&snapshot.time, config),
+                    players: snapshot
+                        .stats
+                        .iter()
+                        .map(|(id, (score, kills, deaths))| ScoreboardRowReport {
+                            id: id.to_string(),
+                            score: *score,
+                            kills: *kills,
+                            deaths: *deaths,
+                        })
+                        .collect(),
+                })
+                .collect(),
+
+            rounds: state
+                .rounds
+                .iter()
+                .enumerate()
+                .map(|(i, round)| RoundReport {
+                    round: i + 1,
+                    start_time: settings::format_game_time(round.start_time(), config),
+                    players: round
+                        .player_stats()
+                        .iter()
+                        .map(|stats| RoundPlayerReport {
+                            id: stats.player.to_string(),
+                            score: stats.score,
+                            kills: stats.kills,
+                            deaths: stats.deaths,
+                            objective_score: stats.objective_score,
+                        })
+                        .collect(),
+                })
+                .collect(),
+
+            match_pace: MatchPaceReport {
+                avg_duration: format_duration(Duration::new(pace.avg_duration.as_secs(), 0))
+                    .to_string(),
+                median_duration: format_duration(Duration::new(pace.median_duration.as_secs(), 0))
+                    .to_string(),
+                fastest_round: pace.fastest().map(|round| round.number),
+                slowest_round: pace.slowest().map(|round| round.number),
+                rounds: pace
+                    .rounds
+                    .iter()
+                    .map(|round| RoundPaceReport {
+                        round: round.number,
+                        start_time: settings::format_game_time(&round.start_time, config),
+                        duration: format_duration(Duration::new(round.duration.as_secs(), 0))
+                            .to_string(),
+                        kills: round.kills,
+                        kills_per_minute: round.kills_per_minute,
+                    })
+                    .collect(),
+            },
+
+            death_economy: [Team::Allies, Team::Axis]
+                .map(|team| DeathEconomy::new(state, team))
+                .iter()
+                .map(|economy| DeathEconomyReport {
+                    team: format!("{:?}", economy.team).to_lowercase(),
+                    deaths: economy.deaths,
+                    captures: economy.captures,
+                    deaths_per_capture: economy.deaths_per_capture(),
+                    traded_deaths: economy.traded_deaths,
+                    trade_kills: economy.trade_kills,
+                })
+                .collect(),
+
+            kill_feed: state
+                .kill_feed(Some(&win_model))
+                .into_iter()
+                .map(|entry| KillFeedEntry {
+                    time: settings::format_game_time(&entry.kill.time, config),
+                    killer: entry.kill.killer.as_ref().map(|id| id.to_string()),
+                    victim: entry.kill.victim.to_string(),
+                    weapon: entry.kill.weapon.to_string(),
+                    teamkill: entry.kill.is_teamkill,
+                    cause: format!("{:?}", entry.kill.cause).to_lowercase(),
+                    first_blood: entry.is_first_blood,
+                    round_ender: entry.is_round_ender,
+                    win_probability_delta: entry.win_probability_delta,
+                })
+                .collect(),
+
+            teamkills: state
+                .teamkills
+                .iter()
+                .map(|teamkill| TeamkillReport {
+                    time: settings::format_game_time(&teamkill.time, config),
+                    round: state.round_time(&teamkill.time).map(|(round, _)| round),
+                    killer: teamkill.killer.to_string(),
+                    victim: teamkill.victim.to_string(),
+                    weapon: teamkill.weapon.to_string(),
+                })
+                .collect(),
+
+            score_mismatches: config.score_formula.as_ref().map(|formula| {
+                state
+                    .score_mismatches(formula)
+                    .iter()
+                    .map(|mismatch| ScoreMismatchReport {
+                        round: mismatch.round,
+                        player: mismatch.player.to_string(),
+                        reported: mismatch.reported,
+                        expected: mismatch.expected,
+                    })
+                    .collect()
+            }),
+
+            matchups: state
+                .matchups()
+                .iter()
+                .map(|matchup| MatchupReport {
+                    killer: matchup.killer.to_string(),
+                    victim: matchup.victim.to_string(),
+                    kills: matchup.kills(),
+                    weapons: matchup
+                        .weapons
+                        .iter()
+                        .map(|(weapon, kills)| (format!("{weapon}"), *kills))
+                        .collect(),
+                })
+                .collect(),
+
+            kill_matrix: KillMatrixReport {
+                players: kill_matrix
+                    .players
+                    .iter()
+                    .map(|player| player.id.to_string())
+                    .collect(),
+                kills: kill_matrix.kills,
+            },
+
+            players_to_watch: state
+                .players_to_watch()
+                .iter()
+                .map(|watch| PlayerToWatchReport {
+                    id: watch.player.id.to_string(),
+                    name: watch.player.name.clone(),
+                    score: watch.score,
+                    highlights: watch
+                        .highlights
+                        .iter()
+                        .map(|highlight| HighlightReport {
+                            time: settings::format_game_time(&highlight.time, config),
+                            description: highlight.kind.to_string(),
+                            seek: file.seek_command(&highlight.time),
+                        })
+                        .collect(),
+                })
+                .collect(),
+
+            late_joins: state
+                .late_joins()
+                .into_iter()
+                .map(|player| LateJoinReport {
+                    id: player.id.to_string(),
+                    name: player.name.clone(),
+                    team: team_name(player),
+                    connected: settings::format_game_time(&player.first_connected, config),
+                })
+                .collect(),
+
+            roster_newcomers: file
+                .roster_newcomers
+                .iter()
+                .map(|newcomer| RosterNewcomerReport {
+                    id: newcomer.id.clone(),
+                    name: newcomer.name.clone(),
+                    clan: newcomer.clan.clone(),
+                    other_clans: newcomer.other_clans.clone(),
+                })
+                .collect(),
+
+            danger_zones: state
+                .danger_zones()
+                .iter()
+                .map(|zone| DangerZoneReport {
+                    name: zone.name.clone(),
+                    center: zone.center,
+                    allies_deaths: zone.allies_deaths,
+                    axis_deaths: zone.axis_deaths,
+                })
+                .collect(),
+
+            match_start_time: state
+                .match_start_time()
+                .map(|time| settings::format_game_time(time, config)),
+
+            match_start_candidates: state
+                .match_start_candidates()
+                .iter()
+                .map(|time| settings::format_game_time(time, config))
+                .collect(),
+
+            warmup: state.warmup_stats().map(|warmup| WarmupReport {
+                rounds: warmup.rounds.len(),
+                captures: warmup.captures.len(),
+                players: state
+                    .players
+                    .iter()
+                    .map(|player| WarmupPlayerReport {
+                        id: player.id.to_string(),
+                        name: player.name.clone(),
+                        kills: warmup.kills_by(&player.id),
+                        deaths: warmup.deaths_of(&player.id),
+                    })
+                    .collect(),
+            }),
+
+            suspicious_events: state
+                .suspicious_events()
+                .iter()
+                .map(|event| SuspiciousEventReport {
+                    time: settings::format_game_time(&event.time, config),
+                    player: event.player.to_string(),
+                    event: event.kind.to_string(),
+                })
+                .collect(),
+
+            kill_latency: KillLatency::new(state).map(|latency| KillLatencyReport {
+                recorded_by_hltv: latency.recorded_by_hltv,
+                median_delay_secs: latency.median_delay.as_secs_f32(),
+                max_delay_secs: latency.max_delay.as_secs_f32(),
+            }),
+
+            malformed_messages: state
+                .malformed_message_counts()
+                .into_iter()
+                .map(|(name, count)| (name.to_string(), count))
+                .collect(),
+
+            server_anomalies: state
+                .server_anomalies
+                .iter()
+                .map(|anomaly| ServerAnomalyReport {
+                    time: settings::format_game_time(&anomaly.start, config),
+                    anomaly: anomaly.kind.to_string(),
+                    duration_secs: anomaly.duration.as_secs_f32(),
+                })
+                .collect(),
+
+            chat: state
+                .chat
+                .iter()
+                .map(|message| {
+                    let sender = message
+                        .sender
+                        .as_ref()
+                        .and_then(|id| state.players.iter().find(|player| player.id == *id));
+
+                    ChatReport {
+                        time: settings::format_game_time(&message.time, config),
+                        player: sender.map(|player| player.name.clone()),
+                        text: message.text.clone(),
+                    }
+                })
+                .collect(),
+
+            narrative: Narrative::new(state, &config.narrative).to_string(),
+        }
+    }
+}
+
+impl PlayerReport {
+    fn new(
+        file: &FileInfo,
+        analysis: &Analysis,
+        player: &Player,
+        impact_ratings: &HashMap<PlayerGlobalId, f32>,
+    ) -> Self {
+        let state = &analysis.state;
+        let config = &analysis.config;
+
+        let consistency = Consistency::new(player, &state.rounds);
+        let participation = KillParticipation::new(player, state);
+
+        Self {
+            id: SteamId::try_from(&player.id)
+                .map(|steam_id| steam_id.to_string())
+                .ok()
+                .unwrap_or(player.id.to_string()),
+            name: player.name.clone(),
+            team: team_name(player),
+            score: player.stats.0,
+            kills: player.stats.1,
+            deaths: player.stats.2,
+            environment_deaths: state.environment_deaths(&player.id),
+            suicides: state.suicides(&player.id),
+            assists: config.infer_assists.then(|| state.assists_by(&player.id)),
+            captures: state.captures_by(&player.id),
+            objective_score: player.objective_score,
+            rounds_played: player.rounds_played(&state.rounds),
+            first_bloods: state.first_bloods_by(&player.id),
+            round_enders: state.round_enders_by(&player.id),
+
+            lifespan: LifespanReport {
+                avg: format_duration(player.avg_lifespan()).to_string(),
+                min: format_duration(player.min_lifespan()).to_string(),
+                max: format_duration(player.max_lifespan()).to_string(),
+            },
+
+            weapon_breakdown: player
+                .weapon_breakdown_by_category()
+                .iter()
+                .map(|category| WeaponCategoryReport {
+                    category: category.category.to_string().to_lowercase(),
+                    kills: category.kills,
+                    teamkills: category.teamkills,
+                    weapons: category
+                        .weapons
+                        .iter()
+                        .map(|(weapon, kills, teamkills)| WeaponKillsReport {
+                            weapon: weapon.to_string(),
+                            kills: *kills,
+                            teamkills: *teamkills,
+                        })
+                        .collect(),
+                })
+                .collect(),
+
+            kill_distances: KillDistancesReport {
+                by_weapon: state
+                    .weapon_ranges(&player.id)
+                    .iter()
+                    .map(|range| WeaponRangeReport {
+                        weapon: range.weapon.to_string(),
+                        kills: range.kills,
+                        avg_meters: range.avg_meters(),
+                        long_range_kills: range.long_range_kills,
+                    })
+                    .collect(),
+                longest: state
+                    .longest_kill(&player.id)
+                    .map(|kill| LongestKillReport {
+                        meters: kill.meters(),
+                        weapon: kill.weapon.to_string(),
+                        victim: kill.victim.to_string(),
+                        time: settings::format_game_time(&kill.time, config),
+                    }),
+            },
+
+            kill_timing: state
+                .weapon_timings(&player.id)
+                .iter()
+                .map(|timing| KillTimingReport {
+                    weapon: timing.weapon.to_string(),
+                    kills: timing.kills,
+                    time_to_kill_ms: timing.time_to_kill.as_millis(),
+                    fire_interval_ms: timing.fire_interval.as_millis(),
+                    fastest_follow_up_ms: timing
+                        .fastest_follow_up
+                        .map(|interval| interval.as_millis()),
+                })
+                .collect(),
+
+            positions_held: player
+                .positions_held()
+                .iter()
+                .map(|hold| PositionHeldReport {
+                    start_time: settings::format_game_time(&hold.start_time, config),
+                    duration: format_duration(Duration::new(hold.duration().as_secs(), 0))
+                        .to_string(),
+                    kills: hold.kills,
+                    origin: hold.origin,
+                })
+                .collect(),
+
+            distance_traveled: player.distance_traveled(),
+
+            shots: ShotsReport {
+                heard: player.shots.len(),
+                estimated_accuracy: player.estimated_accuracy(),
+            },
+
+            ammo_usage: player
+                .ammo
+                .iter()
+                .map(|(weapon, usage)| AmmoUsageReport {
+                    weapon: weapon.to_string(),
+                    shots: usage.shots,
+                    reloads: usage.reloads,
+                    kills_per_shot: player.kills_per_shot(weapon),
+                })
+                .collect(),
+
+            time_to_first_kill: TimeToFirstKillReport {
+                avg: format_duration(player.avg_time_to_first_kill()).to_string(),
+                min: format_duration(player.min_time_to_first_kill()).to_string(),
+            },
+
+            consistency: ConsistencyReport {
+                steady: consistency.is_steady(),
+                kills_per_round: consistency.kills_per_round,
+                mean: consistency.mean,
+                variance: consistency.variance,
+                index: consistency.index,
+            },
+
+            impact_rating: impact_ratings.get(&player.id).copied().unwrap_or_default(),
+
+            kill_participation: KillParticipationReport {
+                per_round: participation.per_round,
+                average: participation.average,
+            },
+
+            kill_streaks: player
+                .notable_kill_streaks(config.min_streak)
+                .map(|(wave, streak)| KillStreakReport {
+                    wave: wave + 1,
+                    seek: streak
+                        .kills
+                        .first()
+                        .map(|(time, _)| file.seek_command(time)),
+                    kills: streak
+                        .kills
+                        .iter()
+                        .map(|(time, weapon)| {
+                            let round_time = state.round_time(time);
+
+                            StreakKillReport {
+                                weapon: format!("{weapon}"),
+                                viewdemo_secs: time.viewdemo_offset.as_secs_f32(),
+                                recording_secs: time.real_offset.as_secs_f32(),
+                                server_secs: time.server_offset.as_secs_f32(),
+                                round: round_time.map(|(round, _)| round),
+                                round_secs: round_time
+                                    .map(|(_, since_start)| since_start.as_secs_f32()),
+                            }
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn team_name(player: &Player) -> Option<String> {
+    player.team.clone().map(|t| format!("{t:?}").to_lowercase())
+}
+
+/// Returns the JSON Schema of the JSON output, a list of [Report]s, to validate the tools that
+/// read it.
+pub fn schema() -> Value {
+    let mut schema = schemars::schema_for!(Vec<Report>);
+
+    schema.insert("title".to_string(), "dod-tools JSON output".into());
+    schema.insert(
+        "description".to_string(),
+        format!("JSON output of dod-tools {ANALYZER_VERSION}").into(),
+    );
+
+    serde_json::to_value(schema).expect("The schema should be valid JSON")
+}
@@ -0,0 +1,105 @@
+//! Summaries of the results of an analysis, to show what changed when a demo is analyzed again,
+//! e.g. with other settings or a newer version of the analyzer.
+
+use crate::settings;
+use analysis::{Analysis, Round, Team};
+use serde::{Deserialize, Serialize};
+
+/// A result that settings and analyzer fixes can affect.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ResultLine {
+    /// What the result is about, such as a round or the global ID of a player, unique among the
+    /// results of an analysis.
+    pub subject: String,
+
+    /// How the subject is shown, such as the name of the player.
+    pub label: String,
+
+    pub value: String,
+}
+
+impl ResultLine {
+    fn new(subject: impl Into<String>, label: impl Into<String>, value: String) -> Self {
+        Self {
+            subject: subject.into(),
+            label: label.into(),
+            value,
+        }
+    }
+}
+
+/// Summarizes the results that settings and analyzer fixes can affect, one line per result.
+pub fn result_lines(r: &Analysis) -> Vec<ResultLine> {
+    let mut lines = vec![ResultLine::new(
+        "score",
+        "Score",
+        format!(
+            "Allies {} - Axis {}",
+            r.state.team_scores.get_team_score(Team::Allies),
+            r.state.team_scores.get_team_score(Team::Axis)
+        ),
+    )];
+
+    if let Some(match_start_time) = r.state.match_start_time() {
+        let match_start_time = settings::format_game_time(match_start_time, &r.config);
+
+        lines.push(ResultLine::new(
+            "match_start",
+            "Match start",
+            match_start_time,
+        ));
+    }
+
+    for (i, round) in r.state.rounds.iter().enumerate() {
+        if let Round::Completed {
+            winner_stats: Some((team, kills)),
+            ..
+        } = round
+        {
+            lines.push(ResultLine::new(
+                format!("round {}", i + 1),
+                format!("Round {}", i + 1),
+                format!("{team:?} won with {kills} kills"),
+            ));
+        }
+    }
+
+    for player in &r.state.players {
+        let (_, kills, deaths) = player.stats;
+
+        let streaks = player
+            .notable_kill_streaks(r.config.min_streak)
+            .map(|(_, streak)| streak.kills.len().to_string())
+            .collect::<Vec<_>>();
+
+        lines.push(ResultLine::new(
+            format!("player {}", player.id),
+            player.name.clone(),
+            format!("{kills}/{deaths}, kill streaks [{}]", streaks.join(", ")),
+        ));
+    }
+
+    lines
+}
+
+/// Returns the results that are new, changed, or gone, as `label: before → after` when there was
+/// a result about the same subject before.
+pub fn result_changes(old: &[ResultLine], new: &[ResultLine]) -> Vec<String> {
+    let before = |line: &ResultLine| old.iter().find(|old_line| old_line.subject == line.subject);
+
+    let changed = new.iter().filter_map(|line| match before(line) {
+        Some(old_line) if old_line.value == line.value => None,
+        Some(old_line) => Some(format!(
+            "{}: {} → {}",
+            line.label, old_line.value, line.value
+        )),
+        None => Some(format!("{}: {} (new)", line.label, line.value)),
+    });
+
+    let removed = old
+        .iter()
+        .filter(|old_line| !new.iter().any(|line| line.subject == old_line.subject))
+        .map(|old_line| format!("{}: {} (removed)", old_line.label, old_line.value));
+
+    changed.chain(removed).collect()
+}
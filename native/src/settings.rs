@@ -0,0 +1,188 @@
+//! Analysis options saved between runs.
+
+use crate::storage::{self, LocalStorage, Storage};
+use analysis::{AnalysisConfig, Clock, GameTime, IdentityMap, PlayerGlobalId};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use std::{env, fs, io};
+
+/// Returns the directory for the program's files in the user's configuration directory.
+fn config_dir() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    config_dir.map(|config_dir| config_dir.join("dod-tools"))
+}
+
+/// Returns the location of the settings file in the user's configuration directory.
+pub fn settings_path() -> Option<PathBuf> {
+    config_dir().map(|config_dir| config_dir.join("settings.toml"))
+}
+
+/// Key of the players declared to be the same person in the [config_storage].
+const IDENTITIES_KEY: &str = "identities.toml";
+
+/// Returns where the files shared by the reviewers of a team are kept, such as the players
+/// declared to be the same person and the notes about demos: the shared storage if one is set, so
+/// every reviewer sees the same files, or the configuration directory.
+pub fn config_storage() -> Option<Box<dyn Storage>> {
+    match storage::shared_storage() {
+        Some(shared_storage) => Some(Box::new(shared_storage)),
+        None => config_dir().map(|dir| Box::new(LocalStorage { dir }) as Box<dyn Storage>),
+    }
+}
+
+/// Options saved in the settings file: the analysis options, and the options of the program that
+/// runs it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    #[serde(flatten)]
+    pub analysis: AnalysisConfig,
+
+    #[serde(flatten)]
+    pub display: DisplaySettings,
+
+    /// Font loaded by the GUI on launch, preferred over the built-in fonts.
+    pub font_path: Option<PathBuf>,
+}
+
+/// Options that change how results are shown, but not the results.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    /// Offset from UTC, in minutes, used to display dates and times.
+    pub utc_offset_minutes: i32,
+
+    /// RGB color used for the Allies team.
+    pub allies_color: [u8; 3],
+
+    /// RGB color used for the Axis team.
+    pub axis_color: [u8; 3],
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            utc_offset_minutes: 0,
+            allies_color: [0, 100, 0],
+            axis_color: [139, 0, 0],
+        }
+    }
+}
+
+/// Reads the saved settings, falling back to the defaults if there are none or they are invalid.
+pub fn load_settings() -> Settings {
+    let settings: Settings = settings_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default();
+
+    Settings {
+        analysis: AnalysisConfig {
+            identities: load_identities(),
+            ..settings.analysis
+        },
+        ..settings
+    }
+}
+
+/// Writes the settings to the settings file, creating its directory if needed.
+pub fn save_settings(settings: &Settings) -> io::Result<()> {
+    let contents = toml::to_string(settings).map_err(io::Error::other)?;
+
+    write_config_file(settings_path(), contents)
+}
+
+/// Reads the players declared to be the same person, or none if they are missing or invalid.
+pub fn load_identities() -> IdentityMap {
+    config_storage()
+        .and_then(|storage| storage.read(IDENTITIES_KEY).ok().flatten())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Declares that the players with these IDs are the same person, on top of the identities
+/// declared by other reviewers since they were loaded, and returns all the saved identities.
+pub fn merge_identities(ids: &[PlayerGlobalId]) -> io::Result<IdentityMap> {
+    let storage = config_storage().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "Could not find the configuration directory",
+        )
+    })?;
+
+    let contents = storage::update(storage.as_ref(), IDENTITIES_KEY, |contents| {
+        let mut identities: IdentityMap = match contents {
+            Some(contents) => toml::from_str(&contents).map_err(io::Error::other)?,
+            None => IdentityMap::default(),
+        };
+
+        identities.merge(ids);
+
+        toml::to_string(&identities).map_err(io::Error::other)
+    })?;
+
+    toml::from_str(&contents).map_err(io::Error::other)
+}
+
+fn write_config_file(path: Option<PathBuf>, contents: String) -> io::Result<()> {
+    let path = path.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "Could not find the configuration directory",
+        )
+    })?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, contents)
+}
+
+/// Formats a moment in the demo as `1m 23s` on the configured clock.
+pub fn format_game_time(time: &GameTime, config: &AnalysisConfig) -> String {
+    format_clock_time(time, &config.clock)
+}
+
+/// Formats a moment in the demo as `1m 23s` on the clock.
+pub fn format_clock_time(time: &GameTime, clock: &Clock) -> String {
+    let offset = time.offset(clock);
+
+    humantime::format_duration(Duration::new(offset.as_secs(), 0)).to_string()
+}
+
+/// Formats a time as RFC 3339 in the configured UTC offset.
+pub fn format_local_time(time: SystemTime, display: &DisplaySettings) -> String {
+    let offset = display.utc_offset_minutes;
+    let offset_duration = Duration::from_secs(offset.unsigned_abs() as u64 * 60);
+
+    let local_time = if offset >= 0 {
+        time.checked_add(offset_duration)
+    } else {
+        time.checked_sub(offset_duration)
+    }
+    .unwrap_or(time);
+
+    let formatted = humantime::format_rfc3339_seconds(local_time).to_string();
+
+    if offset == 0 {
+        return formatted;
+    }
+
+    // The time is already shifted, so only the UTC designator needs replacing
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.unsigned_abs() / 60, offset.unsigned_abs() % 60);
+
+    format!(
+        "{}{sign}{hours:02}:{minutes:02}",
+        formatted.trim_end_matches('Z')
+    )
+}
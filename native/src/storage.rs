@@ -0,0 +1,454 @@
+//! Places where the program keeps the files it shares between runs, so a team can keep one copy
+//! for all its reviewers instead of one on each machine.
+//!
+//! The files kept in a [Storage] are the identities, the notes about demos, and the demo index,
+//! which is also the cache of the results that a demo is compared with when it is analyzed again.
+//! The settings stay in each reviewer's configuration directory, since they are a matter of taste.
+//! The backends are local files and a web server; there is no database backend.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+/// Environment variable with the base URL of an [HttpStorage] to use instead of the configuration
+/// directory.
+pub const STORAGE_URL_VAR: &str = "DOD_TOOLS_STORAGE_URL";
+
+/// Environment variable with the secret that an [HttpStorage] sends as a bearer token, and that
+/// [serve] requires of every request.
+pub const STORAGE_TOKEN_VAR: &str = "DOD_TOOLS_STORAGE_TOKEN";
+
+/// Number of times [update] reads a key again when someone else changed it in the meantime.
+const UPDATE_ATTEMPTS: usize = 3;
+
+/// Time [serve] gives a client to send its whole request and read the response before moving on
+/// to the next one.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest contents [serve] accepts for a key, far above the size of a large demo index.
+const MAX_CONTENTS_SIZE: usize = 64 * 1024 * 1024;
+
+/// Backend that stores text contents under keys, such as `identities.toml`.
+pub trait Storage {
+    /// Returns the contents stored under the key, or [None] if nothing was stored under it yet.
+    fn read(&self, key: &str) -> io::Result<Option<String>>;
+
+    /// Stores the contents under the key, replacing what was stored under it.
+    ///
+    /// Fails with a [Conflict] if the storage can tell that someone else changed the key since it
+    /// was last read from this storage, so their changes are not overwritten.
+    fn write(&self, key: &str, contents: &str) -> io::Result<()>;
+}
+
+/// Error of a [Storage::write] to a key that someone else changed since it was read.
+#[derive(Debug)]
+pub struct Conflict {
+    pub key: String,
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} was changed by someone else since it was read",
+            self.key
+        )
+    }
+}
+
+impl Error for Conflict {}
+
+/// Reads the contents stored under the key, changes them, and stores the result, starting over
+/// with the new contents if someone else changed them in the meantime.
+///
+/// Returns the contents that were stored.
+pub fn update(
+    storage: &dyn Storage,
+    key: &str,
+    change: impl Fn(Option<String>) -> io::Result<String>,
+) -> io::Result<String> {
+    let mut attempts = 0;
+
+    loop {
+        let contents = change(storage.read(key)?)?;
+
+        match storage.write(key, &contents) {
+            Ok(()) => return Ok(contents),
+            Err(e) if is_conflict(&e) && attempts + 1 < UPDATE_ATTEMPTS => attempts += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn is_conflict(e: &io::Error) -> bool {
+    e.get_ref().is_some_and(|inner| inner.is::<Conflict>())
+}
+
+/// Stores each key as a file in a directory.
+///
+/// Keys are paths relative to the directory, so the default storage, with an empty directory,
+/// reads and writes paths as given.
+#[derive(Debug, Default)]
+pub struct LocalStorage {
+    pub dir: PathBuf,
+}
+
+impl Storage for LocalStorage {
+    fn read(&self, key: &str) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.dir.join(key)) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> {
+        let path = self.dir.join(key);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, contents)
+    }
+}
+
+/// Stores each key on a web server, reading it with a GET request to `{url}/{key}` and writing it
+/// with a PUT request to the same URL.
+///
+/// Any server that stores the bodies it is sent works, e.g. a WebDAV share, so a team can point
+/// every reviewer's machine at the same one. Writes are conditional on the `ETag` of the last read,
+/// so a server that supports `If-Match` rejects a write over changes made by another reviewer.
+pub struct HttpStorage {
+    pub url: String,
+
+    /// Secret sent in the `Authorization` header of every request, if the server requires one.
+    token: Option<String>,
+
+    agent: ureq::Agent,
+
+    /// `ETag` of each key when it was last read or written, or [None] if it did not exist then.
+    versions: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl HttpStorage {
+    pub fn new(url: String, token: Option<String>) -> Self {
+        Self {
+            url,
+            token,
+            agent: crate::http_agent(),
+            versions: Default::default(),
+        }
+    }
+
+    fn key_url(&self, key: &str) -> String {
+        format!("{}/{key}", self.url.trim_end_matches('/'))
+    }
+
+    fn authorize<B>(&self, request: ureq::RequestBuilder<B>) -> ureq::RequestBuilder<B> {
+        match &self.token {
+            Some(token) => request.header("Authorization", format!("Bearer {token}")),
+            None => request,
+        }
+    }
+
+    fn set_version(&self, key: &str, version: Option<String>) {
+        if let Ok(mut versions) = self.versions.lock() {
+            versions.insert(key.to_string(), version);
+        }
+    }
+}
+
+/// Returns the `ETag` header of a response, if the server sent one.
+fn etag<T>(response: &ureq::http::Response<T>) -> Option<String> {
+    response
+        .headers()
+        .get("ETag")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+impl Storage for HttpStorage {
+    fn read(&self, key: &str) -> io::Result<Option<String>> {
+        match self.authorize(self.agent.get(&self.key_url(key))).call() {
+            Ok(response) => {
+                self.set_version(key, etag(&response));
+
+                response
+                    .into_body()
+                    .read_to_string()
+                    .map(Some)
+                    .map_err(io::Error::other)
+            }
+            Err(ureq::Error::StatusCode(404)) => {
+                self.set_version(key, None);
+
+                Ok(None)
+            }
+            Err(e) => Err(io::Error::other(e)),
+        }
+    }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> {
+        let mut request = self.authorize(self.agent.put(&self.key_url(key)));
+
+        let version = self
+            .versions
+            .lock()
+            .ok()
+            .and_then(|versions| versions.get(key).cloned());
+
+        // Keys that were never read are written as they are, like a local file
+        match version {
+            Some(Some(etag)) => request = request.header("If-Match", etag),
+            Some(None) => request = request.header("If-None-Match", "*"),
+            None => {}
+        }
+
+        match request.send(contents) {
+            Ok(response) => {
+                match etag(&response) {
+                    Some(etag) => self.set_version(key, Some(etag)),
+                    None => {
+                        if let Ok(mut versions) = self.versions.lock() {
+                            versions.remove(key);
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            Err(ureq::Error::StatusCode(412)) => Err(io::Error::other(Conflict {
+                key: key.to_string(),
+            })),
+            Err(e) => Err(io::Error::other(e)),
+        }
+    }
+}
+
+/// Returns the [HttpStorage] set with the [STORAGE_URL_VAR] environment variable, if any, which
+/// authenticates with the [storage_token].
+pub fn shared_storage() -> Option<HttpStorage> {
+    std::env::var(STORAGE_URL_VAR)
+        .ok()
+        .filter(|url| !url.is_empty())
+        .map(|url| HttpStorage::new(url, storage_token()))
+}
+
+/// Returns the secret set with the [STORAGE_TOKEN_VAR] environment variable, if any.
+pub fn storage_token() -> Option<String> {
+    std::env::var(STORAGE_TOKEN_VAR)
+        .ok()
+        .filter(|token| !token.is_empty())
+}
+
+/// Returns the storage shared by a team if one is set, or the local files, for files given by
+/// path such as the demo index.
+pub fn shared_or_local_storage() -> Box<dyn Storage> {
+    match shared_storage() {
+        Some(shared_storage) => Box::new(shared_storage),
+        None => Box::new(LocalStorage::default()),
+    }
+}
+
+/// Serves the keys of a [LocalStorage] to the [HttpStorage] of each reviewer, so a team can share
+/// one without setting up a web server.
+///
+/// Requests are handled one at a time, so a write cannot slip in between the check of another
+/// write's `If-Match` and its change. Keys that would leave the directory are not found, and
+/// requests without the token as their bearer token are unauthorized.
+///
+/// The server speaks plain HTTP, which sends the token in the clear, so it must not be exposed
+/// beyond a network that only the reviewers can reach.
+pub fn serve(listener: TcpListener, storage: &LocalStorage, token: &str) -> io::Result<()> {
+    for stream in listener.incoming() {
+        // A client that hangs up, sends garbage, or is too slow only fails its own request
+        if let Err(e) = stream.and_then(|stream| handle_request(stream, storage, token)) {
+            eprintln!("Could not handle a storage request: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_request(stream: TcpStream, storage: &LocalStorage, token: &str) -> io::Result<()> {
+    let mut stream = DeadlineStream {
+        stream,
+        deadline: Instant::now() + REQUEST_TIMEOUT,
+    };
+
+    let mut reader = BufReader::new(&mut stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let key = parts
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+
+    // Header names are case-insensitive
+    let mut headers = HashMap::new();
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+
+        let Some((name, value)) = line.split_once(':') else {
+            break;
+        };
+
+        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+
+    let is_authorized = headers
+        .get("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|bearer| is_same_secret(bearer, token));
+
+    // The body of a client without the token is not even read
+    if !is_authorized {
+        drop(reader);
+
+        return write!(
+            stream,
+            "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Bearer\r\n\
+             Content-Length: 0\r\nConnection: close\r\n\r\n"
+        );
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or_default();
+
+    if content_length > MAX_CONTENTS_SIZE {
+        return Err(io::Error::other(format!(
+            "contents of {content_length} bytes are too large to store"
+        )));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    drop(reader);
+
+    let (status, contents) = match (method.as_str(), is_storage_key(&key)) {
+        (_, false) => ("404 Not Found", None),
+        ("GET", true) => match storage.read(&key) {
+            Ok(Some(contents)) => ("200 OK", Some(contents)),
+            Ok(None) => ("404 Not Found", None),
+            Err(_) => ("500 Internal Server Error", None),
+        },
+        ("PUT", true) => match (storage.read(&key), String::from_utf8(body)) {
+            (Ok(current), Ok(contents)) => {
+                let current_version = current.as_deref().map(version);
+
+                let is_changed = headers
+                    .get("if-match")
+                    .is_some_and(|etag| current_version.as_ref() != Some(etag));
+                let is_created = current.is_some()
+                    && headers.get("if-none-match").is_some_and(|etag| etag == "*");
+
+                if is_changed || is_created {
+                    ("412 Precondition Failed", None)
+                } else if storage.write(&key, &contents).is_ok() {
+                    // Only the ETag of the new contents is sent back
+                    ("200 OK", Some(contents))
+                } else {
+                    ("500 Internal Server Error", None)
+                }
+            }
+            (Err(_), _) => ("500 Internal Server Error", None),
+            (_, Err(_)) => ("400 Bad Request", None),
+        },
+        _ => ("405 Method Not Allowed", None),
+    };
+
+    let etag = contents
+        .as_deref()
+        .map(|contents| format!("ETag: {}\r\n", version(contents)))
+        .unwrap_or_default();
+
+    let body = match method.as_str() {
+        "GET" => contents.unwrap_or_default(),
+        _ => String::new(),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\n{etag}Connection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Connection of a client to [serve] that fails to read or write once its deadline has passed, so
+/// a client that sends its request a byte at a time cannot hold the server past it.
+struct DeadlineStream {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl DeadlineStream {
+    /// Returns the time left until the deadline, or an error if it has passed.
+    fn time_left(&self) -> io::Result<Duration> {
+        self.deadline
+            .checked_duration_since(Instant::now())
+            .filter(|time_left| !time_left.is_zero())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::TimedOut, "request took too long"))
+    }
+}
+
+impl Read for DeadlineStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.set_read_timeout(Some(self.time_left()?))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Write for DeadlineStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.set_write_timeout(Some(self.time_left()?))?;
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// Returns true if the secrets are equal, comparing every byte so that the time taken does not
+/// tell how much of a guess was right.
+fn is_same_secret(guess: &str, secret: &str) -> bool {
+    guess.len() == secret.len()
+        && guess
+            .bytes()
+            .zip(secret.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Returns true if the key names a file inside the directory of the storage.
+fn is_storage_key(key: &str) -> bool {
+    !key.is_empty()
+        && Path::new(key)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Returns the `ETag` of the contents of a key, which changes whenever they do.
+fn version(contents: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+
+    format!("\"{:016x}\"", hasher.finish())
+}
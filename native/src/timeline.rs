@@ -0,0 +1,102 @@
+//! Team scores and kills over time, shared by the reports and exports.
+
+use crate::escape;
+use analysis::{Analysis, Clan, ClanRoster, Team};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Running total of one team over time, such as its score.
+pub struct TimelineSeries {
+    /// Clan name when scores follow clans, otherwise the side.
+    pub name: String,
+
+    /// Side the scores belong to, or the side the clan started on when scores follow clans.
+    pub team: Team,
+
+    /// Time of each change and the total after it.
+    pub points: Vec<(Duration, i32)>,
+}
+
+/// Returns the team score timeline of each team, per clan or per side depending on the
+/// [AnalysisConfig](analysis::AnalysisConfig).
+pub fn team_score_timeline(analysis: &Analysis, rosters: &[ClanRoster]) -> [TimelineSeries; 2] {
+    let state = &analysis.state;
+
+    [Team::Allies, Team::Axis].map(|team| {
+        if analysis.config.scores_by_clan {
+            // Follow the players that started on this team across swaps
+            let name = Clan::new(state, state.final_side(&team), rosters).name();
+
+            let points = state
+                .clan_score_timeline(&team)
+                .into_iter()
+                .map(|(time, score)| (time.viewdemo_offset, score))
+                .collect();
+
+            TimelineSeries { name, team, points }
+        } else {
+            let points = state
+                .team_scores
+                .iter()
+                .filter(|(_, t, _)| *t == team)
+                .map(|(time, _, score)| (time.viewdemo_offset, *score))
+                .collect();
+
+            TimelineSeries {
+                name: format!("{team:?}"),
+                team,
+                points,
+            }
+        }
+    })
+}
+
+/// Returns the kills made by each team over time, excluding teamkills and suicides, per clan or
+/// per side like [team_score_timeline].
+pub fn team_kill_timeline(analysis: &Analysis, rosters: &[ClanRoster]) -> [TimelineSeries; 2] {
+    let state = &analysis.state;
+
+    [Team::Allies, Team::Axis].map(|team| {
+        let (name, points) = if analysis.config.scores_by_clan {
+            let name = Clan::new(state, state.final_side(&team), rosters).name();
+
+            (name, state.clan_kill_timeline(&team))
+        } else {
+            (format!("{team:?}"), state.team_kill_timeline(&team))
+        };
+
+        TimelineSeries {
+            name,
+            team,
+            points: points
+                .into_iter()
+                .map(|(time, kills)| (time.viewdemo_offset, kills))
+                .collect(),
+        }
+    })
+}
+
+/// Formats the timeline as CSV with `time,team,score` columns, where the time is in seconds.
+pub fn to_csv(series: &[TimelineSeries]) -> String {
+    let mut rows = series
+        .iter()
+        .flat_map(|series| {
+            series
+                .points
+                .iter()
+                .map(move |(time, score)| (*time, &series.name, *score))
+        })
+        .collect::<Vec<_>>();
+
+    rows.sort_by_key(|(time, _, _)| *time);
+
+    rows.into_iter().fold(
+        String::from("time,team,score\n"),
+        |mut csv, (time, name, score)| {
+            let name = escape::csv(name);
+            let _ = writeln!(csv, "{:.3},{name},{score}", time.as_secs_f64());
+
+            csv
+        },
+    )
+}
@@ -0,0 +1,25 @@
+//! Parameters of the round win probability model used to rate the impact of kills.
+
+use analysis::{AnalysisConfig, WinModel};
+use std::path::Path;
+use std::{fs, io};
+
+/// Parameters shipped with the program.
+const DEFAULT_WIN_MODEL: &str = include_str!("win_model.toml");
+
+/// Returns the parameters shipped with the program.
+pub fn default_win_model() -> WinModel {
+    toml::from_str(DEFAULT_WIN_MODEL).expect("The shipped win model should be valid")
+}
+
+/// Returns the model set in the [AnalysisConfig], or the one shipped with the program.
+pub fn win_model(config: &AnalysisConfig) -> WinModel {
+    config.win_model.clone().unwrap_or_else(default_win_model)
+}
+
+/// Reads parameters written by the `train-win-model` command.
+pub fn load_win_model(path: &Path) -> io::Result<WinModel> {
+    let contents = fs::read_to_string(path)?;
+
+    toml::from_str(&contents).map_err(io::Error::other)
+}